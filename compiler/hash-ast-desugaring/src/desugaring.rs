@@ -2,16 +2,18 @@
 //! implementation.
 use hash_ast::{
     ast::{
-        AstNode, AstNodes, BindingPat, Block, BlockExpr, BodyBlock, BoolLit, BoolLitPat,
-        BreakStatement, ConstructorCallArg, ConstructorCallArgs, ConstructorCallExpr,
-        ConstructorPat, Expr, ExprKind, ForLoopBlock, IfBlock, IfClause, IfPat, IgnorePat, Lit,
-        LitExpr, LitPat, LoopBlock, MatchBlock, MatchCase, MatchOrigin, Name, Pat, TuplePatEntry,
+        AstNode, AstNodes, BinOp, BinaryExpr, BindingPat, Block, BlockExpr, BodyBlock, BoolLit,
+        BoolLitPat, BreakStatement, ConstructorCallArg, ConstructorCallArgs, ConstructorCallExpr,
+        ConstructorPat, Expr, ExprKind, ForLoopBlock, IfBlock, IfClause, IfPat, IgnorePat, IntLit,
+        InterpolatedStrLit, Lit, LitExpr, LitPat, LoopBlock, MatchBlock, MatchCase, MatchOrigin,
+        Name, Pat, PropagateExpr, RangeEnd, RangeExpr, ReturnStatement, TuplePatEntry,
         VariableExpr, WhileLoopBlock,
     },
     ast_nodes,
 };
 use hash_reporting::macros::panic_on_span;
-use hash_source::location::Span;
+use hash_source::{constant::IntConstantValue, location::Span};
+use num_bigint::BigInt;
 
 use crate::visitor::AstDesugaring;
 
@@ -400,4 +402,363 @@ impl<'s> AstDesugaring<'s> {
             origin: MatchOrigin::If,
         })
     }
+    /// This function is responsible for converting an [InterpolatedStrLit]
+    /// into calls to the `format` function of the `Format` trait that is
+    /// defined within the standard library, the results of which are
+    /// concatenated. This is not obvious from the function definition
+    /// because it accepts a [Expr], not specifically an
+    /// [InterpolatedStrLit]. This is because it operates by using
+    /// [AstNode::replace] in order to convert the literal into the
+    /// concatenation.
+    ///
+    /// The process is as follows: de-sugaring an interpolated string from:
+    /// ```text
+    /// f"value is {x + 1}, not {y}"
+    /// ```
+    ///
+    /// Is converted to:
+    ///
+    /// ```text
+    /// "value is " + format(x + 1) + ", not " + format(y)
+    /// ```
+    ///
+    /// The string segments of the literal are left as they are, since they
+    /// don't need to be formatted.
+    pub(crate) fn desugar_interpolated_str(&self, node: Expr, parent_span: Span) -> Expr {
+        let segments = match node.into_kind() {
+            ExprKind::LitExpr(LitExpr(lit)) => match lit.into_body() {
+                Lit::InterpolatedStr(InterpolatedStrLit { segments }) => segments,
+                lit => panic_on_span!(
+                    self.source_location(parent_span),
+                    self.source_map,
+                    "lowering: expected interpolated string literal, got {:?}",
+                    lit
+                ),
+            },
+            kind => panic_on_span!(
+                self.source_location(parent_span),
+                self.source_map,
+                "lowering: expected interpolated string literal, got {:?}",
+                kind
+            ),
+        };
+
+        // Wrap each of the interpolated expressions in a call to `format`
+        let mut segments = segments.nodes.into_iter().map(|segment| {
+            let span = segment.span();
+
+            match segment.kind() {
+                ExprKind::LitExpr(LitExpr(lit)) if matches!(lit.body(), Lit::Str(_)) => segment,
+                _ => AstNode::new(
+                    Expr::new(ExprKind::ConstructorCall(ConstructorCallExpr {
+                        subject: AstNode::new(
+                            Expr::new(ExprKind::Variable(VariableExpr {
+                                name: AstNode::new(Name { ident: "format".into() }, span),
+                            })),
+                            span,
+                        ),
+                        args: AstNode::new(
+                            ConstructorCallArgs {
+                                entries: ast_nodes![AstNode::new(
+                                    ConstructorCallArg { name: None, value: segment },
+                                    span
+                                )],
+                            },
+                            span,
+                        ),
+                    })),
+                    span,
+                ),
+            }
+        });
+
+        // ##Safety: the parser never produces an interpolated string without segments
+        let first = segments.next().unwrap();
+
+        // Concatenate all of the segments from left to right
+        segments
+            .fold(first, |lhs, rhs| {
+                let span = lhs.span().join(rhs.span());
+
+                AstNode::new(
+                    Expr::new(ExprKind::BinaryExpr(BinaryExpr {
+                        lhs,
+                        rhs,
+                        operator: AstNode::new(BinOp::Add, span),
+                    })),
+                    span,
+                )
+            })
+            .into_body()
+    }
+
+    /// This function is responsible for converting a [RangeExpr] into a
+    /// construction of the `RangeIterator` struct that is defined within the
+    /// standard library. This is not obvious from the function definition
+    /// because it accepts a [Expr], not specifically a [RangeExpr]. This is
+    /// because it operates by using [AstNode::replace] in order to convert
+    /// the range into the constructor call.
+    ///
+    /// The process is as follows: de-sugaring a range from:
+    /// ```text
+    /// <lo>..<hi>
+    /// ```
+    ///
+    /// Is converted to:
+    ///
+    /// ```text
+    /// RangeIterator(current = <lo>, final = <hi>, step = 1)
+    /// ```
+    ///
+    /// The final bound of the `RangeIterator` is exclusive, so an inclusive
+    /// range `<lo>..=<hi>` uses `<hi> + 1` as the final bound.
+    pub(crate) fn desugar_range_expr(&self, node: Expr, parent_span: Span) -> Expr {
+        let RangeExpr { lo, hi, end } = match node.into_kind() {
+            ExprKind::Range(range) => range,
+            kind => panic_on_span!(
+                self.source_location(parent_span),
+                self.source_map,
+                "lowering: expected range expression, got {:?}",
+                kind
+            ),
+        };
+
+        let make_int_lit = |value: i32, span: Span| {
+            let value = IntConstantValue { value: BigInt::from(value), suffix: None };
+
+            AstNode::new(
+                Expr::new(ExprKind::LitExpr(LitExpr(AstNode::new(
+                    Lit::Int(IntLit(value.into())),
+                    span,
+                )))),
+                span,
+            )
+        };
+
+        // Since the `RangeIterator` excludes the final bound, we need to bump the
+        // upper bound for inclusive ranges
+        let hi = match end {
+            RangeEnd::Excluded => hi,
+            RangeEnd::Included => {
+                let span = hi.span();
+
+                AstNode::new(
+                    Expr::new(ExprKind::BinaryExpr(BinaryExpr {
+                        lhs: hi,
+                        rhs: make_int_lit(1, span),
+                        operator: AstNode::new(BinOp::Add, span),
+                    })),
+                    span,
+                )
+            }
+        };
+
+        let step = make_int_lit(1, parent_span);
+
+        let make_arg = |name: &str, value: AstNode<Expr>| {
+            let span = value.span();
+
+            AstNode::new(
+                ConstructorCallArg {
+                    name: Some(AstNode::new(Name { ident: name.into() }, span)),
+                    value,
+                },
+                span,
+            )
+        };
+
+        Expr::new(ExprKind::ConstructorCall(ConstructorCallExpr {
+            subject: AstNode::new(
+                Expr::new(ExprKind::Variable(VariableExpr {
+                    name: AstNode::new(Name { ident: "RangeIterator".into() }, parent_span),
+                })),
+                parent_span,
+            ),
+            args: AstNode::new(
+                ConstructorCallArgs {
+                    entries: ast_nodes![
+                        make_arg("current", lo),
+                        make_arg("final", hi),
+                        make_arg("step", step)
+                    ],
+                },
+                parent_span,
+            ),
+        }))
+    }
+
+    /// This function is responsible for converting a pipeline [BinaryExpr],
+    /// which uses the `|>` operator, into a call with the left-hand side as
+    /// the first argument. This is not obvious from the function definition
+    /// because it accepts a [Expr], not specifically a [BinaryExpr]. This is
+    /// because it operates by using [AstNode::replace] in order to convert
+    /// the pipeline into the call.
+    ///
+    /// The process is as follows: de-sugaring a pipeline from:
+    /// ```text
+    /// <lhs> |> <subject>(<args>)
+    /// ```
+    ///
+    /// Is converted to:
+    ///
+    /// ```text
+    /// <subject>(<lhs>, <args>)
+    /// ```
+    ///
+    /// If the right-hand side isn't a call, e.g. `<lhs> |> <subject>`, then
+    /// the right-hand side is called with the left-hand side as the only
+    /// argument, `<subject>(<lhs>)`. Pipelines are left associative, so
+    /// `xs |> filter(p) |> collect()` becomes `collect(filter(xs, p))`.
+    pub(crate) fn desugar_pipeline(&self, node: Expr, parent_span: Span) -> Expr {
+        let (lhs, rhs) = match node.into_kind() {
+            ExprKind::BinaryExpr(BinaryExpr { lhs, rhs, operator })
+                if *operator.body() == BinOp::Pipe =>
+            {
+                (lhs, rhs)
+            }
+            kind => panic_on_span!(
+                self.source_location(parent_span),
+                self.source_map,
+                "lowering: expected pipeline expression, got {:?}",
+                kind
+            ),
+        };
+
+        let lhs_span = lhs.span();
+        let arg = AstNode::new(ConstructorCallArg { name: None, value: lhs }, lhs_span);
+
+        let rhs_span = rhs.span();
+
+        match rhs.into_body().into_kind() {
+            ExprKind::ConstructorCall(ConstructorCallExpr { subject, mut args }) => {
+                args.body_mut().entries.nodes.insert(0, arg);
+
+                Expr::new(ExprKind::ConstructorCall(ConstructorCallExpr { subject, args }))
+            }
+            kind => Expr::new(ExprKind::ConstructorCall(ConstructorCallExpr {
+                subject: AstNode::new(Expr::new(kind), rhs_span),
+                args: AstNode::new(ConstructorCallArgs { entries: ast_nodes![arg] }, rhs_span),
+            })),
+        }
+    }
+
+    /// This function is responsible for converting a [PropagateExpr] into a
+    /// [MatchBlock] that returns early from the current function when the
+    /// subject is an error. This is not obvious from the function definition
+    /// because it accepts a [Expr], not specifically a [PropagateExpr]. This
+    /// is because it operates by using [AstNode::replace] in order to convert
+    /// the expression into the match block.
+    ///
+    /// The process is as follows: de-sugaring an error propagation from:
+    /// ```text
+    /// <subject>?
+    /// ```
+    ///
+    /// Is converted to:
+    ///
+    /// ```text
+    /// match <subject> {
+    ///     Ok(value) => value;
+    ///     Err(error) => return Err(error);
+    /// }
+    /// ```
+    ///
+    /// The match block is marked with [MatchOrigin::Propagate], so that using
+    /// the operator outside of a function body is reported by the semantic
+    /// analysis pass as a misuse of `?` rather than of the generated `return`,
+    /// and so that the typechecker can report a subject that isn't a
+    /// `Result`.
+    pub(crate) fn desugar_propagate_expr(&self, node: Expr, parent_span: Span) -> Expr {
+        let subject = match node.into_kind() {
+            ExprKind::Propagate(PropagateExpr { subject }) => subject,
+            kind => panic_on_span!(
+                self.source_location(parent_span),
+                self.source_map,
+                "lowering: expected error propagation expression, got {:?}",
+                kind
+            ),
+        };
+
+        let subject_span = subject.span();
+
+        let make_name = |label: &str| AstNode::new(Name { ident: label.into() }, parent_span);
+
+        let make_variable = |label: &str| {
+            AstNode::new(
+                Expr::new(ExprKind::Variable(VariableExpr { name: make_name(label) })),
+                parent_span,
+            )
+        };
+
+        // Utility to create a constructor pattern like `Ok(value)` which binds the
+        // single field of the variant
+        let make_variant_pat = |variant: &str, binding: &str| {
+            AstNode::new(
+                Pat::Constructor(ConstructorPat {
+                    subject: AstNode::new(
+                        Pat::Binding(BindingPat {
+                            name: make_name(variant),
+                            visibility: None,
+                            mutability: None,
+                        }),
+                        parent_span,
+                    ),
+                    fields: ast_nodes![AstNode::new(
+                        TuplePatEntry {
+                            name: None,
+                            pat: AstNode::new(
+                                Pat::Binding(BindingPat {
+                                    name: make_name(binding),
+                                    visibility: None,
+                                    mutability: None,
+                                }),
+                                parent_span,
+                            ),
+                        },
+                        parent_span
+                    )],
+                }),
+                parent_span,
+            )
+        };
+
+        // Re-wrap the error so that the function returns `Err(error)`
+        let wrapped_error = AstNode::new(
+            Expr::new(ExprKind::ConstructorCall(ConstructorCallExpr {
+                subject: make_variable("Err"),
+                args: AstNode::new(
+                    ConstructorCallArgs {
+                        entries: ast_nodes![AstNode::new(
+                            ConstructorCallArg { name: None, value: make_variable("error") },
+                            parent_span
+                        )],
+                    },
+                    parent_span,
+                ),
+            })),
+            parent_span,
+        );
+
+        let cases = ast_nodes![
+            AstNode::new(
+                MatchCase { pat: make_variant_pat("Ok", "value"), expr: make_variable("value") },
+                subject_span
+            ),
+            AstNode::new(
+                MatchCase {
+                    pat: make_variant_pat("Err", "error"),
+                    expr: AstNode::new(
+                        Expr::new(ExprKind::Return(ReturnStatement(Some(wrapped_error)))),
+                        parent_span,
+                    ),
+                },
+                parent_span
+            ),
+        ];
+
+        Expr::new(ExprKind::Block(BlockExpr(AstNode::new(
+            Block::Match(MatchBlock { subject, cases, origin: MatchOrigin::Propagate }),
+            parent_span,
+        ))))
+    }
 }
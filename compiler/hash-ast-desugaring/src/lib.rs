@@ -1,10 +1,13 @@
-//! Hash AST lowering passes crate. This crate holds an implementation for the
-//! visitor pattern on the AST in order to `lower` it to a simpler version so
-//! that later stages can work on it without having to operate on similar
-//! constructs and duplicating logic.
+//! Hash AST lowering passes crate. This crate holds an implementation of a
+//! [hash_ast::fold::AstFolder] on the AST in order to `lower` it to a simpler
+//! version so that later stages can work on it without having to operate on
+//! similar constructs and duplicating logic.
 #![feature(generic_associated_types)]
 
-use hash_ast::{ast::OwnsAstNode, visitor::AstVisitorMut};
+use hash_ast::{
+    ast::OwnsAstNode,
+    fold::{fold_in_place, AstFolder},
+};
 use hash_pipeline::{sources::Workspace, traits::Desugar, CompilerResult};
 use hash_source::SourceId;
 use std::collections::HashSet;
@@ -60,7 +63,7 @@ impl<'pool> Desugar<'pool> for AstDesugarer {
                     let source = node_map.get_interactive_block_mut(id);
                     let mut desugarer = AstDesugaring::new(source_map, entry_point);
 
-                    desugarer.visit_body_block(&(), source.node_ref_mut()).unwrap();
+                    fold_in_place(source.node_mut(), |block| desugarer.fold_body_block(block));
                 }
             }
 
@@ -83,7 +86,7 @@ impl<'pool> Desugar<'pool> for AstDesugarer {
                 for expr in module.node_mut().contents.iter_mut() {
                     scope.spawn(|_| {
                         let mut desugarer = AstDesugaring::new(source_map, SourceId::Module(*id));
-                        desugarer.visit_expr(&(), expr.ast_ref_mut()).unwrap()
+                        fold_in_place(expr, |expr| desugarer.fold_expr(expr))
                     })
                 }
             }
@@ -0,0 +1,69 @@
+//! Hash semantic analysis module for validating the attributes that are
+//! applied to declarations.
+
+use std::collections::HashSet;
+
+use hash_ast::ast::Declaration;
+
+use crate::{
+    attributes::{lookup_attribute, AttributeTarget},
+    diagnostics::error::AnalysisErrorKind,
+};
+
+use super::SemanticAnalyser;
+
+impl SemanticAnalyser<'_> {
+    /// This function checks that each attribute of the given [Declaration]:
+    ///
+    /// - Is a known attribute within the attribute registry.
+    ///
+    /// - Is only applied once to the declaration.
+    ///
+    /// - Can be applied to the kind of declaration.
+    ///
+    /// - Is given arguments that match the shape that the attribute accepts.
+    pub(crate) fn check_declaration_attributes(&mut self, declaration: &Declaration) {
+        let target = AttributeTarget::from_declaration(declaration);
+        let mut seen = HashSet::new();
+
+        for attribute in declaration.attributes.iter() {
+            let name = attribute.name.ident;
+
+            let info = match lookup_attribute(name) {
+                Some(info) => info,
+                None => {
+                    self.append_error(
+                        AnalysisErrorKind::UnknownAttribute { name },
+                        attribute.name.span(),
+                    );
+                    continue;
+                }
+            };
+
+            if !seen.insert(name) {
+                self.append_error(AnalysisErrorKind::DuplicateAttribute { name }, attribute.span());
+                continue;
+            }
+
+            if !info.targets.contains(&target) {
+                self.append_error(
+                    AnalysisErrorKind::IllegalAttributeTarget {
+                        name,
+                        target,
+                        allowed_targets: info.targets,
+                    },
+                    attribute.span(),
+                );
+            }
+
+            let args = attribute.args.as_ref();
+
+            if !info.args.accepts(args.map(|args| args.body())) {
+                self.append_error(
+                    AnalysisErrorKind::InvalidAttributeArgs { name, expected: info.args },
+                    args.map_or_else(|| attribute.span(), |args| args.span()),
+                );
+            }
+        }
+    }
+}
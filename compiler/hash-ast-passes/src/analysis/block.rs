@@ -55,6 +55,7 @@ impl SemanticAnalyser<'_> {
 
         // We need to set the block to being whatever the origin is set to!
         let old_block_origin = mem::replace(&mut self.current_block, origin);
+        let outer_scopes = self.enter_constant_block();
 
         // We have to manually walk this block because we want to skip any erroneous
         // statements.
@@ -64,6 +65,7 @@ impl SemanticAnalyser<'_> {
             }
         }
 
+        self.exit_constant_block(outer_scopes);
         self.current_block = old_block_origin;
     }
 }
@@ -0,0 +1,258 @@
+//! Hash semantic analysis module for resolving the bindings that function
+//! literals capture from their enclosing scopes, and for validating how the
+//! captured bindings are used.
+
+use std::{collections::HashMap, mem};
+
+use hash_ast::ast::{AccessKind, AstNodeRef, Capture, Expr, ExprKind, Mutability};
+use hash_source::{identifier::Identifier, location::Span};
+
+use crate::diagnostics::error::AnalysisErrorKind;
+
+use super::SemanticAnalyser;
+
+/// A binding that was declared within a runtime scope, such as a function body
+/// or a match case.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LocalBinding {
+    /// Whether the binding was declared as mutable.
+    pub(crate) mutability: Mutability,
+    /// The span of the name of the binding in its declaration.
+    pub(crate) span: Span,
+}
+
+/// A scope containing all of the [LocalBinding]s declared within it.
+pub(crate) type LocalScope = HashMap<Identifier, LocalBinding>;
+
+/// A function literal that the analyser is currently within.
+#[derive(Debug)]
+pub(crate) struct FnFrame {
+    /// The number of scopes that were entered when the function was entered,
+    /// any binding that is declared in a scope below this depth is captured by
+    /// the function.
+    depth: usize,
+    /// The bindings that the function captures so far.
+    captures: Vec<Capture>,
+}
+
+impl SemanticAnalyser<'_> {
+    /// Enter a new runtime scope.
+    pub(crate) fn enter_scope(&mut self) {
+        self.scopes.push(LocalScope::new());
+    }
+
+    /// Exit the current runtime scope, forgetting all of the bindings that
+    /// were declared in it.
+    pub(crate) fn exit_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    /// Enter a function literal. This creates a new scope for the parameters
+    /// of the function.
+    pub(crate) fn enter_fn(&mut self) {
+        self.fn_frames.push(FnFrame { depth: self.scopes.len(), captures: vec![] });
+        self.enter_scope();
+    }
+
+    /// Exit the current function literal, returning all of the bindings that
+    /// the function captures.
+    pub(crate) fn exit_fn(&mut self) -> Vec<Capture> {
+        self.exit_scope();
+        self.fn_frames.pop().map(|frame| frame.captures).unwrap_or_default()
+    }
+
+    /// Enter a constant block such as a `mod` or an `impl` block. Members of
+    /// constant blocks cannot refer to any runtime bindings, so the block
+    /// starts from an empty scope stack. The outer scopes and functions are
+    /// returned so that they can be restored with [Self::exit_constant_block].
+    pub(crate) fn enter_constant_block(&mut self) -> (Vec<LocalScope>, Vec<FnFrame>) {
+        (mem::take(&mut self.scopes), mem::take(&mut self.fn_frames))
+    }
+
+    /// Exit a constant block, restoring the outer scopes and functions.
+    pub(crate) fn exit_constant_block(&mut self, outer: (Vec<LocalScope>, Vec<FnFrame>)) {
+        (self.scopes, self.fn_frames) = outer;
+    }
+
+    /// Declare a binding within the current scope. Bindings that are declared
+    /// outside of any runtime scope are constants, and so they are never
+    /// captured.
+    pub(crate) fn declare_local(&mut self, name: Identifier, mutability: Mutability, span: Span) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name, LocalBinding { mutability, span });
+        }
+    }
+
+    /// Find the innermost declaration of the given binding, returning the
+    /// depth of the scope that it was declared in.
+    fn resolve_local(&self, name: Identifier) -> Option<(usize, LocalBinding)> {
+        self.scopes
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(depth, scope)| scope.get(&name).map(|binding| (depth, *binding)))
+    }
+
+    /// Record a use of a binding, adding it to the captures of every function
+    /// that it was declared outside of. Bindings that don't resolve to any
+    /// runtime scope are either constants or are undefined, which is reported
+    /// by the typechecker.
+    pub(crate) fn record_binding_use(&mut self, name: Identifier, span: Span) {
+        let depth = match self.resolve_local(name) {
+            Some((depth, _)) => depth,
+            None => return,
+        };
+
+        for frame in self.fn_frames.iter_mut().rev().take_while(|frame| frame.depth > depth) {
+            if !frame.captures.iter().any(|capture| capture.name == name) {
+                frame.captures.push(Capture { name, span, is_mutated: false });
+            }
+        }
+    }
+
+    /// Check an expression that is being mutated, e.g. the left-hand side of
+    /// an assignment. If the expression refers to a binding that is captured by
+    /// the current function, then the capture is marked as being mutated. It
+    /// is an error for a function to mutate a captured binding which was not
+    /// declared as mutable.
+    pub(crate) fn check_mutated_expr(&mut self, expr: AstNodeRef<Expr>) {
+        // Find the binding that the expression is mutating, accessing a property
+        // or indexing a binding mutates the binding itself.
+        let mut subject = expr.body();
+
+        let name = loop {
+            match subject.kind() {
+                ExprKind::Variable(variable) => break variable.name.ident,
+                ExprKind::Access(access) if access.kind == AccessKind::Property => {
+                    subject = access.subject.body();
+                }
+                ExprKind::Index(index) => subject = index.subject.body(),
+                _ => return,
+            }
+        };
+
+        let (depth, binding) = match self.resolve_local(name) {
+            Some(local) => local,
+            None => return,
+        };
+
+        let mut is_captured = false;
+
+        for frame in self.fn_frames.iter_mut().rev().take_while(|frame| frame.depth > depth) {
+            if let Some(capture) = frame.captures.iter_mut().find(|capture| capture.name == name) {
+                capture.is_mutated = true;
+                is_captured = true;
+            }
+        }
+
+        if is_captured && binding.mutability == Mutability::Immutable {
+            self.append_error(
+                AnalysisErrorKind::MutatingImmutableCapture {
+                    name,
+                    binding: self.source_location(binding.span),
+                },
+                expr.span(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use hash_ast::{ast::OwnsAstNode, json::to_json};
+    use hash_parser::HashParser;
+    use hash_pipeline::{
+        settings::CompilerSettings,
+        sources::{Module, Workspace},
+        traits::{Parser, SemanticPass},
+    };
+    use hash_source::{ModuleKind, SourceId};
+    use serde_json::{json, Value};
+
+    use crate::HashSemanticAnalysis;
+
+    /// Collect the resolved captures of each of the function literals within
+    /// the given JSON of a tree, in the order that the functions appear.
+    fn collect_captures(json: &Value, captures: &mut Vec<Value>) {
+        match json {
+            Value::Object(fields) => {
+                if let Some(Value::Array(resolved)) = fields.get("captures") {
+                    let resolved = resolved
+                        .iter()
+                        .map(|capture| json!([capture["name"], capture["is_mutated"]]))
+                        .collect();
+                    captures.push(Value::Array(resolved));
+                }
+
+                fields.values().for_each(|field| collect_captures(field, captures));
+            }
+            Value::Array(items) => items.iter().for_each(|item| collect_captures(item, captures)),
+            _ => {}
+        }
+    }
+
+    /// Parse and analyse the given source, returning the captures of each of
+    /// the function literals within it.
+    fn analyse_captures(contents: &str) -> Vec<Value> {
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+
+        let mut workspace = Workspace::new();
+        let module = Module::new(PathBuf::from("captures.hash"));
+        let module_id = workspace.add_module(contents.to_string(), module, ModuleKind::Normal);
+        let source_id = SourceId::Module(module_id);
+
+        HashParser::new()
+            .parse(source_id, &mut workspace, &CompilerSettings::default(), &pool)
+            .unwrap();
+
+        let mut analysis = HashSemanticAnalysis;
+        let mut state = analysis.make_state().unwrap();
+        assert!(analysis.perform_pass(source_id, &mut workspace, &mut state, &pool).is_ok());
+
+        let mut captures = vec![];
+        collect_captures(
+            &to_json(workspace.node_map().get_module(module_id).node()),
+            &mut captures,
+        );
+        captures
+    }
+
+    #[test]
+    fn resolve_captures() {
+        let captures = analyse_captures(
+            "main := () => {
+                x := 1;
+                mut y := 2;
+
+                shadowing := () => {
+                    x := 3;
+                    y = x;
+                };
+
+                outer := () => {
+                    z := 4;
+                    inner := () => x + z;
+                };
+
+                match y {
+                    w => {
+                        case := () => w + y;
+                    };
+                };
+            };",
+        );
+
+        assert_eq!(
+            captures,
+            [
+                json!([]),
+                json!([["y", true]]),
+                json!([["x", false]]),
+                json!([["x", false], ["z", false]]),
+                json!([["w", false], ["y", false]]),
+            ]
+        );
+    }
+}
@@ -7,6 +7,7 @@ use hash_source::{
     SourceId, SourceMap,
 };
 
+use self::captures::{FnFrame, LocalScope};
 use crate::diagnostics::{
     error::{AnalysisError, AnalysisErrorKind},
     origins::BlockOrigin,
@@ -14,7 +15,9 @@ use crate::diagnostics::{
     Diagnostic,
 };
 
+mod attributes;
 mod block;
+mod captures;
 mod pat;
 
 pub struct SemanticAnalyser<'s> {
@@ -33,6 +36,11 @@ pub struct SemanticAnalyser<'s> {
     /// The current scope of the traversal, representing which block the
     /// analyser is walking.
     pub(crate) current_block: BlockOrigin,
+    /// The runtime scopes that the analyser is currently within, used to
+    /// resolve which bindings function literals capture.
+    pub(crate) scopes: Vec<LocalScope>,
+    /// The function literals that the analyser is currently within.
+    pub(crate) fn_frames: Vec<FnFrame>,
 }
 
 impl<'s> SemanticAnalyser<'s> {
@@ -46,6 +54,8 @@ impl<'s> SemanticAnalyser<'s> {
             source_id,
             source_map,
             current_block: BlockOrigin::Root,
+            scopes: vec![],
+            fn_frames: vec![],
         }
     }
 
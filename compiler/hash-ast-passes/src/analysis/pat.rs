@@ -1,7 +1,9 @@
 //! Hash semantic analysis module for validating various constructs relating to
 //! patterns within the AST.
 
-use hash_ast::ast::{AstNodes, Pat, TuplePatEntry};
+use std::cmp::Ordering;
+
+use hash_ast::ast::{AstNodeRef, AstNodes, LitPat, Pat, RangeEnd, RangePat, TuplePatEntry};
 
 use crate::diagnostics::{error::AnalysisErrorKind, origins::PatOrigin};
 
@@ -83,4 +85,48 @@ impl SemanticAnalyser<'_> {
             }
         }
     }
+
+    /// Check that the bounds of a range pattern can be compared, and that the
+    /// range isn't empty. Range patterns are inclusive of their lower bound,
+    /// so a range such as `5..=1` or `1..1` can never match any subject.
+    ///
+    /// Bounds that are of differing kinds, e.g. `1..='c'` are not reported here
+    /// since they will fail to unify during typechecking.
+    pub(crate) fn check_range_pat(&mut self, node: AstNodeRef<RangePat>) {
+        let RangePat { lo, hi, end } = node.body();
+
+        let mut has_illegal_bound = false;
+
+        for bound in [lo, hi] {
+            if matches!(bound.body(), LitPat::Str(_) | LitPat::Bool(_)) {
+                self.append_error(AnalysisErrorKind::IllegalRangePatBound, bound.span());
+                has_illegal_bound = true;
+            }
+        }
+
+        if has_illegal_bound {
+            return;
+        }
+
+        let ordering = match (lo.body(), hi.body()) {
+            (LitPat::Int(lo), LitPat::Int(hi)) => {
+                lo.0.value().value.partial_cmp(&hi.0.value().value)
+            }
+            (LitPat::Float(lo), LitPat::Float(hi)) => {
+                lo.0.value().as_f64().partial_cmp(&hi.0.value().as_f64())
+            }
+            (LitPat::Char(lo), LitPat::Char(hi)) => lo.0.partial_cmp(&hi.0),
+            _ => None,
+        };
+
+        match ordering {
+            Some(Ordering::Greater) => {
+                self.append_error(AnalysisErrorKind::InvertedRangePat, node.span())
+            }
+            Some(Ordering::Equal) if *end == RangeEnd::Excluded => {
+                self.append_error(AnalysisErrorKind::EmptyRangePat, node.span())
+            }
+            _ => {}
+        }
+    }
 }
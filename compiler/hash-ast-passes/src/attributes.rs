@@ -0,0 +1,140 @@
+//! Hash attribute registry. This file holds the definitions of all of the
+//! attributes that can be applied to declarations, along with the targets
+//! that each attribute can be applied to and the shape of the arguments that
+//! the attribute accepts.
+
+use std::fmt::Display;
+
+use hash_ast::ast::{Block, BlockExpr, ConstructorCallArgs, Declaration, ExprKind, Lit, LitExpr};
+use hash_source::identifier::Identifier;
+
+/// The kind of declaration that an attribute is applied to, which is derived
+/// from the value of the declaration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AttributeTarget {
+    Fn,
+    TyFn,
+    Struct,
+    Enum,
+    Trait,
+    Mod,
+    Impl,
+    /// Any other declaration, e.g. `x := 3;`.
+    Declaration,
+}
+
+impl AttributeTarget {
+    /// All of the possible attribute targets.
+    pub(crate) const ALL: &'static [AttributeTarget] = &[
+        AttributeTarget::Fn,
+        AttributeTarget::TyFn,
+        AttributeTarget::Struct,
+        AttributeTarget::Enum,
+        AttributeTarget::Trait,
+        AttributeTarget::Mod,
+        AttributeTarget::Impl,
+        AttributeTarget::Declaration,
+    ];
+
+    /// Compute the [AttributeTarget] of a [Declaration].
+    pub(crate) fn from_declaration(declaration: &Declaration) -> Self {
+        match declaration.value.as_ref().map(|value| value.kind()) {
+            Some(ExprKind::FnDef(_)) => AttributeTarget::Fn,
+            Some(ExprKind::TyFnDef(_)) => AttributeTarget::TyFn,
+            Some(ExprKind::StructDef(_)) => AttributeTarget::Struct,
+            Some(ExprKind::EnumDef(_)) => AttributeTarget::Enum,
+            Some(ExprKind::TraitDef(_)) => AttributeTarget::Trait,
+            Some(ExprKind::Block(BlockExpr(block))) => match block.body() {
+                Block::Mod(_) => AttributeTarget::Mod,
+                Block::Impl(_) => AttributeTarget::Impl,
+                _ => AttributeTarget::Declaration,
+            },
+            _ => AttributeTarget::Declaration,
+        }
+    }
+}
+
+impl Display for AttributeTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AttributeTarget::Fn => write!(f, "function definition"),
+            AttributeTarget::TyFn => write!(f, "type function definition"),
+            AttributeTarget::Struct => write!(f, "struct definition"),
+            AttributeTarget::Enum => write!(f, "enum definition"),
+            AttributeTarget::Trait => write!(f, "trait definition"),
+            AttributeTarget::Mod => write!(f, "`mod` block"),
+            AttributeTarget::Impl => write!(f, "`impl` block"),
+            AttributeTarget::Declaration => write!(f, "declaration"),
+        }
+    }
+}
+
+/// The shape of the arguments that an attribute accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AttributeArgs {
+    /// The attribute does not accept any arguments, e.g. `#[inline]`.
+    None,
+    /// The attribute accepts an optional string literal, e.g.
+    /// `#[deprecated("use foo instead")]`.
+    OptionalStr,
+}
+
+impl AttributeArgs {
+    /// Check whether the given arguments of an attribute match the shape.
+    pub(crate) fn accepts(&self, args: Option<&ConstructorCallArgs>) -> bool {
+        match (self, args) {
+            (_, None) => true,
+            (AttributeArgs::None, Some(_)) => false,
+            (AttributeArgs::OptionalStr, Some(args)) => match args.entries.nodes.as_slice() {
+                [arg] => {
+                    arg.name.is_none()
+                        && matches!(
+                            arg.value.kind(),
+                            ExprKind::LitExpr(LitExpr(lit)) if matches!(
+                                lit.body(),
+                                Lit::Str(_) | Lit::RawStr(_) | Lit::MultiLineStr(_)
+                            )
+                        )
+                }
+                _ => false,
+            },
+        }
+    }
+}
+
+impl Display for AttributeArgs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AttributeArgs::None => write!(f, "no arguments"),
+            AttributeArgs::OptionalStr => write!(f, "an optional string literal"),
+        }
+    }
+}
+
+/// The definition of an attribute within the registry.
+#[derive(Debug)]
+pub(crate) struct AttributeInfo {
+    /// The name of the attribute.
+    pub(crate) name: &'static str,
+    /// The kinds of declarations that the attribute can be applied to.
+    pub(crate) targets: &'static [AttributeTarget],
+    /// The arguments that the attribute accepts.
+    pub(crate) args: AttributeArgs,
+}
+
+/// All of the attributes that are known to the compiler.
+pub(crate) static ATTRIBUTES: &[AttributeInfo] = &[
+    AttributeInfo { name: "inline", targets: &[AttributeTarget::Fn], args: AttributeArgs::None },
+    AttributeInfo {
+        name: "deprecated",
+        targets: AttributeTarget::ALL,
+        args: AttributeArgs::OptionalStr,
+    },
+    AttributeInfo { name: "test", targets: &[AttributeTarget::Fn], args: AttributeArgs::None },
+];
+
+/// Find the definition of an attribute within the registry by its name.
+pub(crate) fn lookup_attribute(name: Identifier) -> Option<&'static AttributeInfo> {
+    let name: &str = name.into();
+    ATTRIBUTES.iter().find(|attribute| attribute.name == name)
+}
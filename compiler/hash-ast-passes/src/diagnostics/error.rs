@@ -8,6 +8,8 @@ use hash_reporting::{
 };
 use hash_source::{identifier::Identifier, location::SourceLocation, ModuleKind};
 
+use crate::attributes::{AttributeArgs, AttributeTarget};
+
 use super::{
     directives::DirectiveArgument,
     origins::{BlockOrigin, PatOrigin},
@@ -38,6 +40,9 @@ pub(crate) enum AnalysisErrorKind {
     /// When a `return` statement is found outside of a function or in scope
     /// that doesn't relate to the function.
     UsingReturnOutsideOfFn,
+    /// When the error propagation operator `?` is used outside of a function,
+    /// since it returns early from the current function.
+    UsingPropagateOutsideOfFn,
     /// When there is a non-declarative expression in either the root scope
     /// (module) or in a `impl` / `mod` block.
     NonDeclarativeExpression { origin: BlockOrigin },
@@ -55,9 +60,18 @@ pub(crate) enum AnalysisErrorKind {
     /// When a pattern is used within a particular context that is not allowed
     ///
     /// Currently, this is only used to notify that `float` patterns aren't
-    /// allowed in pattern positions. Later this will change as float
-    /// patterns should be allowed within range patterns.
+    /// allowed in pattern positions, they can only be used as the bounds of
+    /// range patterns.
     DisallowedFloatPat,
+    /// When the bound of a range pattern is a literal that cannot be compared,
+    /// such as a string or a boolean.
+    IllegalRangePatBound,
+    /// When a range pattern has a lower bound that is greater than the upper
+    /// bound, e.g. `5..=1`.
+    InvertedRangePat,
+    /// When an exclusive range pattern has equal bounds, e.g. `1..1`, which
+    /// means that the range is empty.
+    EmptyRangePat,
     /// When compound patterns such as constructors and tuples have named fields
     /// before un-named fields.
     AmbiguousPatFieldOrder { origin: PatOrigin },
@@ -80,6 +94,29 @@ pub(crate) enum AnalysisErrorKind {
         expected: DirectiveArgument,
         given: DirectiveArgument,
     },
+    /// When an attribute that isn't within the attribute registry is applied
+    /// to a declaration.
+    UnknownAttribute { name: Identifier },
+    /// When the same attribute is applied to a declaration more than once.
+    DuplicateAttribute { name: Identifier },
+    /// When an attribute is applied to a kind of declaration that it doesn't
+    /// support, e.g. `#[inline]` on a struct definition.
+    IllegalAttributeTarget {
+        name: Identifier,
+        target: AttributeTarget,
+        allowed_targets: &'static [AttributeTarget],
+    },
+    /// When an attribute is given arguments that don't match the arguments
+    /// that the attribute accepts.
+    InvalidAttributeArgs { name: Identifier, expected: AttributeArgs },
+    /// When a function literal mutates a binding that it captures from an
+    /// enclosing scope, but the binding was not declared as mutable.
+    MutatingImmutableCapture {
+        /// The name of the captured binding.
+        name: Identifier,
+        /// Where the binding was declared.
+        binding: SourceLocation,
+    },
 }
 
 impl From<AnalysisError> for Report {
@@ -112,6 +149,16 @@ impl From<AnalysisError> for Report {
                         "here",
                     )));
             }
+            AnalysisErrorKind::UsingPropagateOutsideOfFn => {
+                builder.with_error_code(HashErrorCode::UsingReturnOutsideFn);
+
+                builder.with_message("use of the `?` operator outside of a function").add_element(
+                    ReportElement::CodeBlock(ReportCodeBlock::new(
+                        err.location,
+                        "this returns early from the current function",
+                    )),
+                );
+            }
             AnalysisErrorKind::MultipleSpreadPats { origin } => {
                 builder
                     .with_message(format!(
@@ -245,6 +292,107 @@ impl From<AnalysisError> for Report {
                     .add_element(ReportElement::Note(ReportNote::new(
                         ReportNoteKind::Note,
                         "float-like literals are disallowed within patterns because performing comparisons is not possible",
+                    )))
+                    .add_element(ReportElement::Note(ReportNote::new(
+                        ReportNoteKind::Help,
+                        "consider using a range pattern instead, e.g. `1.0..=2.0`",
+                    )));
+            }
+            AnalysisErrorKind::IllegalRangePatBound => {
+                builder
+                    .with_message("only `int`, `float` and `char` literals can be used as the bounds of a range pattern")
+                    .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                        err.location,
+                        "this literal cannot be used as a bound",
+                    )));
+            }
+            AnalysisErrorKind::InvertedRangePat => {
+                builder
+                    .with_message(
+                        "lower bound of the range pattern is greater than the upper bound",
+                    )
+                    .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                        err.location,
+                        "this range pattern can never match",
+                    )));
+            }
+            AnalysisErrorKind::EmptyRangePat => {
+                builder
+                    .with_message("exclusive range pattern is empty")
+                    .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                        err.location,
+                        "this range pattern can never match",
+                    )))
+                    .add_element(ReportElement::Note(ReportNote::new(
+                        ReportNoteKind::Help,
+                        "use `..=` to include the upper bound within the range",
+                    )));
+            }
+            AnalysisErrorKind::UnknownAttribute { name } => {
+                builder.with_message(format!("unknown attribute `{}`", name)).add_element(
+                    ReportElement::CodeBlock(ReportCodeBlock::new(
+                        err.location,
+                        "this attribute does not exist",
+                    )),
+                );
+            }
+            AnalysisErrorKind::DuplicateAttribute { name } => {
+                builder
+                    .with_message(format!("the `{}` attribute is applied more than once", name))
+                    .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                        err.location,
+                        "consider removing this attribute",
+                    )));
+            }
+            AnalysisErrorKind::IllegalAttributeTarget { name, target, allowed_targets } => {
+                let allowed_targets = allowed_targets
+                    .iter()
+                    .map(|target| format!("{}s", target))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                builder
+                    .with_message(format!(
+                        "the `{}` attribute cannot be applied to a {}",
+                        name, target
+                    ))
+                    .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                        err.location,
+                        format!("not allowed on a {}", target),
+                    )))
+                    .add_element(ReportElement::Note(ReportNote::new(
+                        ReportNoteKind::Note,
+                        format!(
+                            "the `{}` attribute can only be applied to {}",
+                            name, allowed_targets
+                        ),
+                    )));
+            }
+            AnalysisErrorKind::InvalidAttributeArgs { name, expected } => {
+                builder
+                    .with_message(format!("the `{}` attribute expects {}", name, expected))
+                    .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                        err.location,
+                        "unexpected arguments",
+                    )));
+            }
+            AnalysisErrorKind::MutatingImmutableCapture { name, binding } => {
+                builder
+                    .with_message(format!(
+                        "cannot mutate the captured immutable binding `{}`",
+                        name
+                    ))
+                    .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                        err.location,
+                        format!("`{}` is mutated within this function", name),
+                    )))
+                    .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                        binding,
+                        format!("`{}` is declared as immutable here", name),
+                    )))
+                    .add_element(ReportElement::Note(ReportNote::new(
+                        ReportNoteKind::Help,
+                        format!("consider declaring the binding as mutable: `mut {}`", name),
                     )));
             }
         };
@@ -5,6 +5,7 @@
 #![feature(generic_associated_types)]
 
 pub mod analysis;
+pub(crate) mod attributes;
 pub(crate) mod diagnostics;
 pub mod visitor;
 
@@ -6,8 +6,8 @@
 use ::if_chain::if_chain;
 use hash_ast::{
     ast::{
-        BindingPat, Block, BlockExpr, ExprKind, LitExpr, ModulePatEntry, Mutability, ParamOrigin,
-        Pat, TuplePatEntry,
+        BindingPat, Block, BlockExpr, ExprKind, LitExpr, MatchOrigin, ModulePatEntry, Mutability,
+        ParamOrigin, Pat, TuplePatEntry,
     },
     visitor::{walk, AstVisitor},
 };
@@ -135,6 +135,47 @@ impl AstVisitor for SemanticAnalyser<'_> {
         Ok(())
     }
 
+    type RawStrLitRet = ();
+
+    fn visit_raw_str_lit(
+        &mut self,
+        _: &Self::Ctx,
+        _: hash_ast::ast::AstNodeRef<hash_ast::ast::RawStrLit>,
+    ) -> Result<Self::RawStrLitRet, Self::Error> {
+        Ok(())
+    }
+
+    type ByteStrLitRet = ();
+
+    fn visit_byte_str_lit(
+        &mut self,
+        _: &Self::Ctx,
+        _: hash_ast::ast::AstNodeRef<hash_ast::ast::ByteStrLit>,
+    ) -> Result<Self::ByteStrLitRet, Self::Error> {
+        Ok(())
+    }
+
+    type MultiLineStrLitRet = ();
+
+    fn visit_multi_line_str_lit(
+        &mut self,
+        _: &Self::Ctx,
+        _: hash_ast::ast::AstNodeRef<hash_ast::ast::MultiLineStrLit>,
+    ) -> Result<Self::MultiLineStrLitRet, Self::Error> {
+        Ok(())
+    }
+
+    type InterpolatedStrLitRet = ();
+
+    fn visit_interpolated_str_lit(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: hash_ast::ast::AstNodeRef<hash_ast::ast::InterpolatedStrLit>,
+    ) -> Result<Self::InterpolatedStrLitRet, Self::Error> {
+        let _ = walk::walk_interpolated_str_lit(self, ctx, node);
+        Ok(())
+    }
+
     type CharLitRet = ();
 
     fn visit_char_lit(
@@ -211,8 +252,9 @@ impl AstVisitor for SemanticAnalyser<'_> {
     fn visit_variable_expr(
         &mut self,
         _: &Self::Ctx,
-        _: hash_ast::ast::AstNodeRef<hash_ast::ast::VariableExpr>,
+        node: hash_ast::ast::AstNodeRef<hash_ast::ast::VariableExpr>,
     ) -> Result<Self::VariableExprRet, Self::Error> {
+        self.record_binding_use(node.name.ident, node.span());
         Ok(())
     }
 
@@ -330,6 +372,12 @@ impl AstVisitor for SemanticAnalyser<'_> {
         node: hash_ast::ast::AstNodeRef<hash_ast::ast::RefExpr>,
     ) -> Result<Self::RefExprRet, Self::Error> {
         let _ = walk::walk_ref_expr(self, ctx, node);
+
+        // Taking a mutable reference to a binding allows it to be mutated
+        if matches!(node.mutability.as_ref().map(|m| *m.body()), Some(Mutability::Mutable)) {
+            self.check_mutated_expr(node.inner_expr.ast_ref());
+        }
+
         Ok(())
     }
 
@@ -580,8 +628,18 @@ impl AstVisitor for SemanticAnalyser<'_> {
         // Swap the values with a new `true` and save the old state.
         let last_in_fn = mem::replace(&mut self.is_in_fn, true);
 
+        // The parameters of the function are declared in the scope of the function,
+        // and any binding from an outer scope that is used is a capture.
+        self.enter_fn();
+
+        for param in node.params.iter() {
+            self.declare_local(param.name.ident, Mutability::Immutable, param.name.span());
+        }
+
         let _ = walk::walk_fn_def(self, ctx, node);
 
+        node.captures.set(self.exit_fn());
+
         // Reset the value to the old value
         self.is_in_fn = last_in_fn;
 
@@ -632,7 +690,11 @@ impl AstVisitor for SemanticAnalyser<'_> {
         ctx: &Self::Ctx,
         node: hash_ast::ast::AstNodeRef<hash_ast::ast::MatchCase>,
     ) -> Result<Self::MatchCaseRet, Self::Error> {
+        // The bindings of the case pattern are only in scope within the case
+        self.enter_scope();
         let _ = walk::walk_match_case(self, ctx, node);
+        self.exit_scope();
+
         Ok(())
     }
 
@@ -643,6 +705,23 @@ impl AstVisitor for SemanticAnalyser<'_> {
         ctx: &Self::Ctx,
         node: hash_ast::ast::AstNodeRef<hash_ast::ast::MatchBlock>,
     ) -> Result<Self::MatchBlockRet, Self::Error> {
+        // The match block of a de-sugared `?` contains a generated `return`,
+        // which shouldn't be reported as a `return` that is written outside
+        // of a function.
+        if node.origin == MatchOrigin::Propagate && !self.is_in_fn {
+            self.append_error(AnalysisErrorKind::UsingPropagateOutsideOfFn, node.span());
+
+            let _ = self.visit_expr(ctx, node.subject.ast_ref());
+
+            let last_in_fn = mem::replace(&mut self.is_in_fn, true);
+            for case in node.cases.ast_ref_iter() {
+                let _ = self.visit_match_case(ctx, case);
+            }
+            self.is_in_fn = last_in_fn;
+
+            return Ok(());
+        }
+
         let _ = walk::walk_match_block(self, ctx, node);
         Ok(())
     }
@@ -764,7 +843,9 @@ impl AstVisitor for SemanticAnalyser<'_> {
 
         let old_block_origin = mem::replace(&mut self.current_block, BlockOrigin::Body);
 
+        self.enter_scope();
         let _ = walk::walk_body_block(self, ctx, node);
+        self.exit_scope();
 
         self.current_block = old_block_origin;
 
@@ -844,6 +925,17 @@ impl AstVisitor for SemanticAnalyser<'_> {
         Ok(())
     }
 
+    type AttributeRet = ();
+
+    fn visit_attribute(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: hash_ast::ast::AstNodeRef<hash_ast::ast::Attribute>,
+    ) -> Result<Self::AttributeRet, Self::Error> {
+        let _ = walk::walk_attribute(self, ctx, node);
+        Ok(())
+    }
+
     type DeclarationRet = ();
 
     fn visit_declaration(
@@ -851,7 +943,23 @@ impl AstVisitor for SemanticAnalyser<'_> {
         ctx: &Self::Ctx,
         node: hash_ast::ast::AstNodeRef<hash_ast::ast::Declaration>,
     ) -> Result<Self::DeclarationRet, Self::Error> {
-        let _ = walk::walk_declaration(self, ctx, node);
+        self.check_declaration_attributes(node.body());
+
+        for attribute in node.attributes.iter() {
+            self.visit_attribute(ctx, attribute.ast_ref())?;
+        }
+
+        // The value is visited before the pattern because the bindings that the
+        // pattern declares are not in scope within the value.
+        if let Some(value) = node.value.as_ref() {
+            self.visit_expr(ctx, value.ast_ref())?;
+        }
+
+        if let Some(ty) = node.ty.as_ref() {
+            self.visit_ty(ctx, ty.ast_ref())?;
+        }
+
+        self.visit_pat(ctx, node.pat.ast_ref())?;
         Ok(())
     }
 
@@ -875,6 +983,8 @@ impl AstVisitor for SemanticAnalyser<'_> {
         node: hash_ast::ast::AstNodeRef<hash_ast::ast::AssignExpr>,
     ) -> Result<Self::AssignExprRet, Self::Error> {
         let _ = walk::walk_assign_expr(self, ctx, node);
+        self.check_mutated_expr(node.lhs.ast_ref());
+
         Ok(())
     }
 
@@ -886,6 +996,8 @@ impl AstVisitor for SemanticAnalyser<'_> {
         node: hash_ast::ast::AstNodeRef<hash_ast::ast::AssignOpExpr>,
     ) -> Result<Self::AssignOpExprRet, Self::Error> {
         let _ = walk::walk_assign_op_statement(self, ctx, node);
+        self.check_mutated_expr(node.lhs.ast_ref());
+
         Ok(())
     }
 
@@ -922,6 +1034,38 @@ impl AstVisitor for SemanticAnalyser<'_> {
         Ok(())
     }
 
+    type RangeExprRet = ();
+
+    fn visit_range_expr(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: hash_ast::ast::AstNodeRef<hash_ast::ast::RangeExpr>,
+    ) -> Result<Self::RangeExprRet, Self::Error> {
+        let _ = walk::walk_range_expr(self, ctx, node);
+        Ok(())
+    }
+
+    type PropagateExprRet = ();
+
+    fn visit_propagate_expr(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: hash_ast::ast::AstNodeRef<hash_ast::ast::PropagateExpr>,
+    ) -> Result<Self::PropagateExprRet, Self::Error> {
+        let _ = walk::walk_propagate_expr(self, ctx, node);
+        Ok(())
+    }
+
+    type ErrExprRet = ();
+
+    fn visit_err_expr(
+        &mut self,
+        _: &Self::Ctx,
+        _: hash_ast::ast::AstNodeRef<hash_ast::ast::ErrExpr>,
+    ) -> Result<Self::ErrExprRet, Self::Error> {
+        Ok(())
+    }
+
     type StructDefRet = ();
 
     fn visit_struct_def(
@@ -1079,8 +1223,12 @@ impl AstVisitor for SemanticAnalyser<'_> {
     fn visit_spread_pat(
         &mut self,
         _: &Self::Ctx,
-        _: hash_ast::ast::AstNodeRef<hash_ast::ast::SpreadPat>,
+        node: hash_ast::ast::AstNodeRef<hash_ast::ast::SpreadPat>,
     ) -> Result<Self::SpreadPatRet, Self::Error> {
+        if let Some(name) = node.name.as_ref() {
+            self.declare_local(name.ident, Mutability::Immutable, name.span());
+        }
+
         Ok(())
     }
 
@@ -1175,7 +1323,13 @@ impl AstVisitor for SemanticAnalyser<'_> {
         _: &Self::Ctx,
         node: hash_ast::ast::AstNodeRef<hash_ast::ast::BindingPat>,
     ) -> Result<Self::BindingPatRet, Self::Error> {
-        let BindingPat { mutability, visibility, .. } = node.body();
+        let BindingPat { name, mutability, visibility } = node.body();
+
+        self.declare_local(
+            name.ident,
+            mutability.as_ref().map_or(Mutability::Immutable, |m| *m.body()),
+            name.span(),
+        );
 
         // If the pattern is present in a declaration that is within a constant block,
         // it it not allowed to be declared to be mutable. If we are not in a
@@ -1210,6 +1364,29 @@ impl AstVisitor for SemanticAnalyser<'_> {
         Ok(())
     }
 
+    type RangePatRet = ();
+
+    fn visit_range_pat(
+        &mut self,
+        _: &Self::Ctx,
+        node: hash_ast::ast::AstNodeRef<hash_ast::ast::RangePat>,
+    ) -> Result<Self::RangePatRet, Self::Error> {
+        // We don't walk the bounds of the range since float literals are allowed
+        // within range patterns.
+        self.check_range_pat(node);
+        Ok(())
+    }
+
+    type ErrPatRet = ();
+
+    fn visit_err_pat(
+        &mut self,
+        _: &Self::Ctx,
+        _: hash_ast::ast::AstNodeRef<hash_ast::ast::ErrPat>,
+    ) -> Result<Self::ErrPatRet, Self::Error> {
+        Ok(())
+    }
+
     type ModulePatEntryRet = ();
 
     fn visit_module_pat_entry(
@@ -1,13 +1,21 @@
 //! Frontend-agnostic Hash AST (abstract syntax tree) type definitions.
 
-use hash_source::{identifier::Identifier, location::Span, string::Str};
+use hash_source::{
+    constant::{FloatConstant, IntConstant},
+    identifier::Identifier,
+    location::Span,
+    string::{ByteStr, Str},
+};
 use hash_utils::counter;
+use hash_utils_visitor_macros::define_tree;
 use replace_with::replace_with_or_abort;
+use serde::{ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
 use std::{
     fmt::Display,
     hash::Hash,
     ops::{Deref, DerefMut},
     path::PathBuf,
+    sync::OnceLock,
 };
 
 counter! {
@@ -78,6 +86,48 @@ impl<T> AstNode<T> {
     pub fn with_body<'u, U>(&self, body: &'u U) -> AstNodeRef<'u, U> {
         AstNodeRef { body, span: self.span, id: self.id }
     }
+
+    /// Convert the body of this [AstNode] into a new body, the [Span] and
+    /// [AstNodeId] of the node are kept.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> AstNode<U> {
+        AstNode { body: Box::new(f(*self.body)), span: self.span, id: self.id }
+    }
+
+    /// Create a node with a given body, location and id. This is used to
+    /// rebuild a node that shares its [AstNodeId] with the node that it is
+    /// nested within, such as the body of a [Block::Body].
+    pub fn with_id(body: T, span: Span, id: AstNodeId) -> Self {
+        Self { body: Box::new(body), span, id }
+    }
+}
+
+/// [AstNode]s are serialised as an object with the `id` and `span` of the
+/// node, and the `body` of the node.
+impl<T: Serialize> Serialize for AstNode<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut node = serializer.serialize_struct("AstNode", 3)?;
+        node.serialize_field("id", &self.id.0)?;
+        node.serialize_field("span", &self.span)?;
+        node.serialize_field("body", self.body())?;
+        node.end()
+    }
+}
+
+/// The serialised form of an [AstNode], the `id` of the node is ignored.
+#[derive(Deserialize)]
+struct SerialisedAstNode<T> {
+    span: Span,
+    body: T,
+}
+
+/// A deserialised [AstNode] is given a new [AstNodeId] rather than the one
+/// that was serialised, since the ids must be unique amongst all of the nodes
+/// that exist in the compiler.
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for AstNode<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let SerialisedAstNode { span, body } = SerialisedAstNode::deserialize(deserializer)?;
+        Ok(AstNode::new(body, span))
+    }
 }
 
 #[derive(Debug)]
@@ -198,7 +248,7 @@ pub trait OwnsAstNode<T> {
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct AstNodes<T> {
     pub nodes: Vec<AstNode<T>>,
 
@@ -269,1261 +319,1551 @@ impl<T> DerefMut for AstNode<T> {
     }
 }
 
-/// A single name/symbol.
-#[derive(Hash, Debug, Clone, Copy)]
-pub struct Name {
-    // The name of the symbol.
-    pub ident: Identifier,
-}
+// The node types of the AST, from which the visitors in [crate::visitor] are
+// generated. Each type that is a node in the tree is marked with `#[node]`, so
+// adding a node to the AST only requires defining its type here.
+define_tree! {
+    /// A single name/symbol.
+    #[node]
+    #[derive(Hash, Debug, Clone, Copy, Serialize, Deserialize)]
+    pub struct Name {
+        // The name of the symbol.
+        pub ident: Identifier,
+    }
 
-impl Name {
-    /// Function to check whether a [Name] has a particular associated name with
-    /// it.
-    pub fn is(&self, other: impl Into<Identifier>) -> bool {
-        self.ident == other.into()
+    impl Name {
+        /// Function to check whether a [Name] has a particular associated name with
+        /// it.
+        pub fn is(&self, other: impl Into<Identifier>) -> bool {
+            self.ident == other.into()
+        }
     }
-}
 
-/// A concrete/"named" type.
-#[derive(Debug, PartialEq, Clone)]
-pub struct NamedTy {
-    /// The name of the type.
-    pub name: AstNode<Name>,
-}
+    /// A concrete/"named" type.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct NamedTy {
+        /// The name of the type.
+        pub name: AstNode<Name>,
+    }
 
-/// Access type denotes the access of a property of some inner type.
-#[derive(Debug, PartialEq, Clone)]
-pub struct AccessTy {
-    /// The subject of the access
-    pub subject: AstNode<Ty>,
-    /// the property that is access of the `subject` type
-    pub property: AstNode<Name>,
-}
+    /// Access type denotes the access of a property of some inner type.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct AccessTy {
+        /// The subject of the access
+        pub subject: AstNode<Ty>,
+        /// the property that is access of the `subject` type
+        pub property: AstNode<Name>,
+    }
 
-/// Reference kind representing either a raw reference or a normal reference.
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub enum RefKind {
-    /// Raw reference type
-    Raw,
-    /// Normal reference type
-    Normal,
-}
+    /// Reference kind representing either a raw reference or a normal reference.
+    #[node]
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+    pub enum RefKind {
+        /// Raw reference type
+        Raw,
+        /// Normal reference type
+        Normal,
+    }
 
-/// A reference type.
-#[derive(Debug, PartialEq, Clone)]
-pub struct RefTy {
-    /// Inner type of the reference type
-    pub inner: AstNode<Ty>,
-    /// Whether this reference is a `raw` reference or normal reference (normal
-    /// by default).
-    pub kind: Option<AstNode<RefKind>>,
-    /// Mutability of the reference (immutable by default)
-    pub mutability: Option<AstNode<Mutability>>,
-}
+    /// A reference type.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct RefTy {
+        /// Inner type of the reference type
+        pub inner: AstNode<Ty>,
+        /// Whether this reference is a `raw` reference or normal reference (normal
+        /// by default).
+        pub kind: Option<AstNode<RefKind>>,
+        /// Mutability of the reference (immutable by default)
+        pub mutability: Option<AstNode<Mutability>>,
+    }
 
-/// A type argument `<T: u32>`
-#[derive(Debug, PartialEq, Clone)]
-pub struct TyArg {
-    /// An optional name to the argument
-    pub name: Option<AstNode<Name>>,
-    /// The assigned value of the type argument
-    pub ty: AstNode<Ty>,
-}
+    /// A type argument `<T: u32>`
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct TyArg {
+        /// An optional name to the argument
+        pub name: Option<AstNode<Name>>,
+        /// The assigned value of the type argument
+        pub ty: AstNode<Ty>,
+    }
 
-/// The tuple type.
-#[derive(Debug, PartialEq, Clone)]
-pub struct TupleTy {
-    pub entries: AstNodes<TyArg>,
-}
+    /// The tuple type.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct TupleTy {
+        pub entries: AstNodes<TyArg>,
+    }
 
-/// The list type, , e.g. `{str}`.
-#[derive(Debug, PartialEq, Clone)]
-pub struct ListTy {
-    pub inner: AstNode<Ty>,
-}
+    /// The list type, , e.g. `{str}`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct ListTy {
+        pub inner: AstNode<Ty>,
+    }
 
-/// The set type, , e.g. `{str}`.
-#[derive(Debug, PartialEq, Clone)]
-pub struct SetTy {
-    pub inner: AstNode<Ty>,
-}
+    /// The set type, , e.g. `{str}`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct SetTy {
+        pub inner: AstNode<Ty>,
+    }
 
-/// The map type, e.g. `{str: u32}`.
-#[derive(Debug, PartialEq, Clone)]
-pub struct MapTy {
-    pub key: AstNode<Ty>,
-    pub value: AstNode<Ty>,
-}
+    /// The map type, e.g. `{str: u32}`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct MapTy {
+        pub key: AstNode<Ty>,
+        pub value: AstNode<Ty>,
+    }
 
-/// The function type.
-#[derive(Debug, PartialEq, Clone)]
-pub struct FnTy {
-    /// Any defined parameters for the function type
-    pub params: AstNodes<TyArg>,
-    /// Optional return type
-    pub return_ty: AstNode<Ty>,
-}
+    /// The function type.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct FnTy {
+        /// Any defined parameters for the function type
+        pub params: AstNodes<TyArg>,
+        /// Optional return type
+        pub return_ty: AstNode<Ty>,
+    }
 
-/// A type function e.g. `<T = u32, E: Conv ~ Eq> -> Result<T, E>`
-#[derive(Debug, PartialEq, Clone)]
-pub struct TyFn {
-    /// The parameters of the type function
-    pub params: AstNodes<Param>,
-    /// Return type of the function
-    pub return_ty: AstNode<Ty>,
-}
+    /// A type function e.g. `<T = u32, E: Conv ~ Eq> -> Result<T, E>`
+    #[node(visit = "ty_fn_ty")]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct TyFn {
+        /// The parameters of the type function
+        pub params: AstNodes<Param>,
+        /// Return type of the function
+        pub return_ty: AstNode<Ty>,
+    }
 
-/// A type function call specifies a call to a type function with the specified
-/// function name in the form of a [Ty] (which can only be a [NamedTy] then
-/// followed by arguments. For example: `Conv<u32>` or `(Foo<bar>)<baz>`
-#[derive(Debug, PartialEq, Clone)]
-pub struct TyFnCall {
-    pub subject: AstNode<Expr>,
-    pub args: AstNodes<TyArg>,
-}
+    /// A type function call specifies a call to a type function with the specified
+    /// function name in the form of a [Ty] (which can only be a [NamedTy] then
+    /// followed by arguments. For example: `Conv<u32>` or `(Foo<bar>)<baz>`
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct TyFnCall {
+        pub subject: AstNode<Expr>,
+        pub args: AstNodes<TyArg>,
+    }
 
-/// A merge type meaning that multiple types are considered to be
-/// specified in place of one, e.g. `Conv ~ Eq`
-#[derive(Debug, PartialEq, Clone)]
-pub struct MergeTy {
-    pub lhs: AstNode<Ty>,
-    pub rhs: AstNode<Ty>,
-}
+    /// A merge type meaning that multiple types are considered to be
+    /// specified in place of one, e.g. `Conv ~ Eq`
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct MergeTy {
+        pub lhs: AstNode<Ty>,
+        pub rhs: AstNode<Ty>,
+    }
 
-/// A union type meaning that multiple types are accepted, e.g. `f64 | i64`
-#[derive(Debug, PartialEq, Clone)]
-pub struct UnionTy {
-    pub lhs: AstNode<Ty>,
-    pub rhs: AstNode<Ty>,
-}
+    /// A union type meaning that multiple types are accepted, e.g. `f64 | i64`
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct UnionTy {
+        pub lhs: AstNode<Ty>,
+        pub rhs: AstNode<Ty>,
+    }
 
-/// Binary type operators enumeration.
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub enum BinTyOp {
-    /// The union of two types, essentially an or, e.g `f64 | u64`
-    Union,
-    /// The intersection between two types, essentially an `and`, `Ord ~ Eq`
-    Merge,
-}
+    /// Binary type operators enumeration.
+    #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+    pub enum BinTyOp {
+        /// The union of two types, essentially an or, e.g `f64 | u64`
+        Union,
+        /// The intersection between two types, essentially an `and`, `Ord ~ Eq`
+        Merge,
+    }
 
-impl BinTyOp {
-    /// Compute the precedence for an operator
-    pub fn infix_binding_power(&self) -> (u8, u8) {
-        match self {
-            BinTyOp::Merge => (2, 3),
-            BinTyOp::Union => (4, 5),
+    impl BinTyOp {
+        /// Compute the precedence for an operator
+        pub fn infix_binding_power(&self) -> (u8, u8) {
+            match self {
+                BinTyOp::Merge => (2, 3),
+                BinTyOp::Union => (4, 5),
+            }
         }
     }
-}
 
-/// A type.
-#[derive(Debug, PartialEq, Clone)]
-pub enum Ty {
-    /// Access type, access the property of some inner type
-    Access(AccessTy),
-    /// Tuple type
-    Tuple(TupleTy),
-    /// list type
-    List(ListTy),
-    /// Set type
-    Set(SetTy),
-    /// Map type
-    Map(MapTy),
-    /// Function type
-    Fn(FnTy),
-    /// Named type, similar to a binding
-    Named(NamedTy),
-    /// Reference type, the reference type of the inner type
-    Ref(RefTy),
-    /// Merge type, the intersection of two types
-    Merge(MergeTy),
-    /// Union type, the union of two types
-    Union(UnionTy),
-    /// Type function type
-    TyFn(TyFn),
-    /// Type function call
-    TyFnCall(TyFnCall),
-}
+    /// A type.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub enum Ty {
+        /// Access type, access the property of some inner type
+        Access(AccessTy),
+        /// Tuple type
+        Tuple(TupleTy),
+        /// list type
+        List(ListTy),
+        /// Set type
+        Set(SetTy),
+        /// Map type
+        Map(MapTy),
+        /// Function type
+        Fn(FnTy),
+        /// Named type, similar to a binding
+        Named(NamedTy),
+        /// Reference type, the reference type of the inner type
+        Ref(RefTy),
+        /// Merge type, the intersection of two types
+        Merge(MergeTy),
+        /// Union type, the union of two types
+        Union(UnionTy),
+        /// Type function type
+        TyFn(TyFn),
+        /// Type function call
+        TyFnCall(TyFnCall),
+    }
 
-/// A set literal, e.g. `{1, 2, 3}`.
-#[derive(Debug, PartialEq, Clone)]
-pub struct SetLit {
-    /// The elements of the set literal.
-    pub elements: AstNodes<Expr>,
-}
+    /// A set literal, e.g. `{1, 2, 3}`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct SetLit {
+        /// The elements of the set literal.
+        pub elements: AstNodes<Expr>,
+    }
 
-/// A list literal, e.g. `[1, 2, 3]`.
-#[derive(Debug, PartialEq, Clone)]
-pub struct ListLit {
-    /// The elements of the list literal.
-    pub elements: AstNodes<Expr>,
-}
+    /// A list literal, e.g. `[1, 2, 3]`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct ListLit {
+        /// The elements of the list literal.
+        pub elements: AstNodes<Expr>,
+    }
 
-/// An entry within a tuple type, which may contain an optional name
-/// annotation and or a type annotation, for example:
-///
-/// ```text
-/// (foo : u32 = 2, ..., k = 2)
-///  ^^^   ^^^   ^
-/// name   type  value
-/// ```
-#[derive(Debug, PartialEq, Clone)]
-pub struct TupleLitEntry {
-    pub name: Option<AstNode<Name>>,
-    pub ty: Option<AstNode<Ty>>,
-    pub value: AstNode<Expr>,
-}
+    /// An entry within a tuple type, which may contain an optional name
+    /// annotation and or a type annotation, for example:
+    ///
+    /// ```text
+    /// (foo : u32 = 2, ..., k = 2)
+    ///  ^^^   ^^^   ^
+    /// name   type  value
+    /// ```
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct TupleLitEntry {
+        pub name: Option<AstNode<Name>>,
+        pub ty: Option<AstNode<Ty>>,
+        pub value: AstNode<Expr>,
+    }
 
-/// A tuple literal, e.g. `(1, 'A', "foo")`.
-#[derive(Debug, PartialEq, Clone)]
-pub struct TupleLit {
-    /// The elements of the tuple literal.
-    pub elements: AstNodes<TupleLitEntry>,
-}
+    /// A tuple literal, e.g. `(1, 'A', "foo")`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct TupleLit {
+        /// The elements of the tuple literal.
+        pub elements: AstNodes<TupleLitEntry>,
+    }
 
-/// A map literal entry, e.g. `"foo": 1`.
-#[derive(Debug, PartialEq, Clone)]
-pub struct MapLitEntry {
-    pub key: AstNode<Expr>,
-    pub value: AstNode<Expr>,
-}
+    /// A map literal entry, e.g. `"foo": 1`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct MapLitEntry {
+        pub key: AstNode<Expr>,
+        pub value: AstNode<Expr>,
+    }
 
-/// A map literal, e.g. `{"foo": 1, "bar": 2}`.
-#[derive(Debug, PartialEq, Clone)]
-pub struct MapLit {
-    /// The elements of the map literal (key-value pairs).
-    pub elements: AstNodes<MapLitEntry>,
-}
+    /// A map literal, e.g. `{"foo": 1, "bar": 2}`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct MapLit {
+        /// The elements of the map literal (key-value pairs).
+        #[node_field(name = "entries")]
+        pub elements: AstNodes<MapLitEntry>,
+    }
 
-/// A string literal.
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub struct StrLit(pub Str);
-
-/// A character literal.
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub struct CharLit(pub char);
-
-/// An integer literal.
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub struct IntLit(pub u64);
-
-/// A float literal.
-#[derive(Debug, PartialEq, Clone)]
-pub struct FloatLit(pub f64);
-
-/// A boolean literal.
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub struct BoolLit(pub bool);
-
-/// A literal.
-#[derive(Debug, PartialEq, Clone)]
-pub enum Lit {
-    Str(StrLit),
-    Char(CharLit),
-    Int(IntLit),
-    Float(FloatLit),
-    Bool(BoolLit),
-    Set(SetLit),
-    Map(MapLit),
-    List(ListLit),
-    Tuple(TupleLit),
-}
+    /// A string literal.
+    #[node]
+    #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+    pub struct StrLit(pub Str);
+
+    /// A raw string literal, in which escape sequences are not processed, e.g.
+    /// `r"C:\path"` or `r#"contains "quotes""#`.
+    #[node]
+    #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+    pub struct RawStrLit(pub Str);
+
+    /// A byte string literal, e.g. `b"bytes\xff"`.
+    #[node]
+    #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+    pub struct ByteStrLit(pub ByteStr);
+
+    /// A multi-line string literal, the stored string has the common indentation
+    /// of the lines stripped.
+    #[node]
+    #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+    pub struct MultiLineStrLit(pub Str);
+
+    /// An interpolated string literal, e.g. `"value is {x + 1}"`. The literal is
+    /// made up of segments, which are either string literals or the interpolated
+    /// expressions. Interpolated string literals are de-sugared into calls to the
+    /// `format` function of the `Format` trait.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct InterpolatedStrLit {
+        /// The segments of the literal, in order.
+        pub segments: AstNodes<Expr>,
+    }
 
-impl Lit {
-    /// This function is used to determine if the current literal tree only
-    /// contains constants. Constants are other literals that are not subject
-    /// to change, e.g. a number like `5` or a string `hello`. This function
-    /// implements short circuiting behaviour and thus should check if the
-    /// literal is constant in the minimal time possible.
-    pub fn is_constant(&self) -> bool {
-        let is_expr_lit_and_const = |expr: &AstNode<Expr>| -> bool {
-            match expr.kind() {
-                ExprKind::LitExpr(LitExpr(lit)) => lit.is_constant(),
-                _ => false,
-            }
-        };
+    /// A character literal.
+    #[node]
+    #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+    pub struct CharLit(pub char);
+
+    /// An integer literal, with an optional type suffix, e.g. `255u8`.
+    #[node]
+    #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+    pub struct IntLit(pub IntConstant);
+
+    /// A float literal, with an optional type suffix, e.g. `1.5f64`.
+    #[node]
+    #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+    pub struct FloatLit(pub FloatConstant);
+
+    /// A boolean literal.
+    #[node]
+    #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+    pub struct BoolLit(pub bool);
+
+    /// A literal.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub enum Lit {
+        Str(StrLit),
+        RawStr(RawStrLit),
+        ByteStr(ByteStrLit),
+        MultiLineStr(MultiLineStrLit),
+        InterpolatedStr(InterpolatedStrLit),
+        Char(CharLit),
+        Int(IntLit),
+        Float(FloatLit),
+        Bool(BoolLit),
+        Set(SetLit),
+        Map(MapLit),
+        List(ListLit),
+        Tuple(TupleLit),
+    }
 
-        // Recurse over the literals for `set`, `map` and `tuple to see if they are
-        // constant.
-        match self {
-            Lit::List(ListLit { elements }) | Lit::Set(SetLit { elements }) => {
-                !elements.iter().any(|expr| !is_expr_lit_and_const(expr))
+    impl Lit {
+        /// This function is used to determine if the current literal tree only
+        /// contains constants. Constants are other literals that are not subject
+        /// to change, e.g. a number like `5` or a string `hello`. This function
+        /// implements short circuiting behaviour and thus should check if the
+        /// literal is constant in the minimal time possible.
+        pub fn is_constant(&self) -> bool {
+            let is_expr_lit_and_const = |expr: &AstNode<Expr>| -> bool {
+                match expr.kind() {
+                    ExprKind::LitExpr(LitExpr(lit)) => lit.is_constant(),
+                    _ => false,
+                }
+            };
+
+            // Recurse over the literals for `set`, `map` and `tuple to see if they are
+            // constant.
+            match self {
+                Lit::List(ListLit { elements }) | Lit::Set(SetLit { elements }) => {
+                    !elements.iter().any(|expr| !is_expr_lit_and_const(expr))
+                }
+                Lit::Tuple(TupleLit { elements }) => {
+                    !elements.iter().any(|entry| !is_expr_lit_and_const(&entry.body().value))
+                }
+                Lit::Map(MapLit { elements }) => !elements.iter().any(|entry| {
+                    !is_expr_lit_and_const(&entry.body().key)
+                        || !is_expr_lit_and_const(&entry.body().value)
+                }),
+                _ => true,
             }
-            Lit::Tuple(TupleLit { elements }) => {
-                !elements.iter().any(|entry| !is_expr_lit_and_const(&entry.body().value))
-            }
-            Lit::Map(MapLit { elements }) => !elements.iter().any(|entry| {
-                !is_expr_lit_and_const(&entry.body().key)
-                    || !is_expr_lit_and_const(&entry.body().value)
-            }),
-            _ => true,
         }
     }
-}
 
-/// An alternative pattern, e.g. `Red | Blue`.
-#[derive(Debug, PartialEq, Clone)]
-pub struct OrPat {
-    /// The variants of the "or" pattern
-    pub variants: AstNodes<Pat>,
-}
+    /// An alternative pattern, e.g. `Red | Blue`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct OrPat {
+        /// The variants of the "or" pattern
+        pub variants: AstNodes<Pat>,
+    }
 
-/// A conditional pattern, e.g. `x if x == 42`.
-#[derive(Debug, PartialEq, Clone)]
-pub struct IfPat {
-    /// The pattern part of the conditional.
-    pub pat: AstNode<Pat>,
-    /// The expression part of the conditional.
-    pub condition: AstNode<Expr>,
-}
+    /// A conditional pattern, e.g. `x if x == 42`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct IfPat {
+        /// The pattern part of the conditional.
+        pub pat: AstNode<Pat>,
+        /// The expression part of the conditional.
+        pub condition: AstNode<Expr>,
+    }
 
-/// An construct pattern, e.g. `Some((x, y)), animals::Dog(name = "viktor", age
-/// = 3)`.
-#[derive(Debug, PartialEq, Clone)]
-pub struct ConstructorPat {
-    /// The subject of the constructor pattern.
-    pub subject: AstNode<Pat>,
-    /// The arguments of the enum variant as patterns.
-    pub fields: AstNodes<TuplePatEntry>,
-}
+    /// An construct pattern, e.g. `Some((x, y)), animals::Dog(name = "viktor", age
+    /// = 3)`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct ConstructorPat {
+        /// The subject of the constructor pattern.
+        pub subject: AstNode<Pat>,
+        /// The arguments of the enum variant as patterns.
+        #[node_field(name = "args")]
+        pub fields: AstNodes<TuplePatEntry>,
+    }
 
-/// A module pattern entry, e.g. `{..., name: (fst, snd), ...}`.
-#[derive(Debug, PartialEq, Clone)]
-pub struct ModulePatEntry {
-    /// The name of the field.
-    pub name: AstNode<Name>,
-    /// The pattern to match the field's value with.
-    pub pat: AstNode<Pat>,
-}
+    /// A module pattern entry, e.g. `{..., name: (fst, snd), ...}`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct ModulePatEntry {
+        /// The name of the field.
+        pub name: AstNode<Name>,
+        /// The pattern to match the field's value with.
+        pub pat: AstNode<Pat>,
+    }
 
-/// A module pattern, e.g. `{ fgets, fputs, }`
-#[derive(Debug, PartialEq, Clone)]
-pub struct ModulePat {
-    /// The entries of a module pattern
-    pub fields: AstNodes<ModulePatEntry>,
-}
+    /// A module pattern, e.g. `{ fgets, fputs, }`
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct ModulePat {
+        /// The entries of a module pattern
+        pub fields: AstNodes<ModulePatEntry>,
+    }
 
-/// A tuple pattern entry
-#[derive(Debug, PartialEq, Clone)]
-pub struct TuplePatEntry {
-    pub name: Option<AstNode<Name>>,
-    pub pat: AstNode<Pat>,
-}
+    /// A tuple pattern entry
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct TuplePatEntry {
+        pub name: Option<AstNode<Name>>,
+        pub pat: AstNode<Pat>,
+    }
 
-/// A tuple pattern, e.g. `(1, 2, x)`
-#[derive(Debug, PartialEq, Clone)]
-pub struct TuplePat {
-    /// The element of the tuple, as patterns.
-    pub fields: AstNodes<TuplePatEntry>,
-}
+    /// A tuple pattern, e.g. `(1, 2, x)`
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct TuplePat {
+        /// The element of the tuple, as patterns.
+        #[node_field(name = "elements")]
+        pub fields: AstNodes<TuplePatEntry>,
+    }
 
-impl TuplePat {
-    /// Function used to check if the pattern is nameless or not. If the pattern
-    /// has at least one member that contains a `name` field, then it is
-    /// considered to be named.
-    pub fn is_nameless_pat(&self) -> bool {
-        !self.fields.iter().any(|pat| pat.body().name.is_some())
+    impl TuplePat {
+        /// Function used to check if the pattern is nameless or not. If the pattern
+        /// has at least one member that contains a `name` field, then it is
+        /// considered to be named.
+        pub fn is_nameless_pat(&self) -> bool {
+            !self.fields.iter().any(|pat| pat.body().name.is_some())
+        }
     }
-}
 
-/// A list pattern, e.g. `[x, 1, ..]`
-#[derive(Debug, PartialEq, Clone)]
-pub struct ListPat {
-    /// The element of the tuple, as patterns.
-    pub fields: AstNodes<Pat>,
-}
+    /// A list pattern, e.g. `[x, 1, ..]`
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct ListPat {
+        /// The element of the tuple, as patterns.
+        #[node_field(name = "elements")]
+        pub fields: AstNodes<Pat>,
+    }
 
-/// A string literal pattern.
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub struct StrLitPat(pub Str);
-
-/// A character literal pattern.
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub struct CharLitPat(pub char);
-
-/// An integer literal pattern.
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub struct IntLitPat(pub u64);
-
-/// A float literal pattern.
-#[derive(Debug, PartialEq, Clone)]
-pub struct FloatLitPat(pub f64);
-
-/// A boolean literal pattern.
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub struct BoolLitPat(pub bool);
-
-/// A literal pattern, e.g. `1`, `3.4`, `"foo"`, `false`.
-#[derive(Debug, PartialEq, Clone)]
-pub enum LitPat {
-    Str(StrLitPat),
-    Char(CharLitPat),
-    Int(IntLitPat),
-    Float(FloatLitPat),
-    Bool(BoolLitPat),
-}
+    /// A string literal pattern.
+    #[node]
+    #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+    pub struct StrLitPat(pub Str);
+
+    /// A character literal pattern.
+    #[node]
+    #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+    pub struct CharLitPat(pub char);
+
+    /// An integer literal pattern.
+    #[node]
+    #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+    pub struct IntLitPat(pub IntConstant);
+
+    /// A float literal pattern.
+    #[node]
+    #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+    pub struct FloatLitPat(pub FloatConstant);
+
+    /// A boolean literal pattern.
+    #[node]
+    #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+    pub struct BoolLitPat(pub bool);
+
+    /// A literal pattern, e.g. `1`, `3.4`, `"foo"`, `false`.
+    #[node]
+    #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+    pub enum LitPat {
+        Str(StrLitPat),
+        Char(CharLitPat),
+        Int(IntLitPat),
+        Float(FloatLitPat),
+        Bool(BoolLitPat),
+    }
 
-/// An access pattern, denoting the access of a property from
-/// another pattern.
-#[derive(Debug, PartialEq, Clone)]
-pub struct AccessPat {
-    /// The subject of the access pattern
-    pub subject: AstNode<Pat>,
-    /// The property of the subject to access.
-    pub property: AstNode<Name>,
-}
+    /// An access pattern, denoting the access of a property from
+    /// another pattern.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct AccessPat {
+        /// The subject of the access pattern
+        pub subject: AstNode<Pat>,
+        /// The property of the subject to access.
+        pub property: AstNode<Name>,
+    }
 
-/// A pattern name binding.
-#[derive(Debug, PartialEq, Clone)]
-pub struct BindingPat {
-    /// The identifier that the name bind is using
-    pub name: AstNode<Name>,
-    /// Visibility of the binding (`priv` by default)
-    pub visibility: Option<AstNode<Visibility>>,
-    /// Mutability of the binding (immutable by default)
-    pub mutability: Option<AstNode<Mutability>>,
-}
+    /// A pattern name binding.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct BindingPat {
+        /// The identifier that the name bind is using
+        pub name: AstNode<Name>,
+        /// Visibility of the binding (`priv` by default)
+        pub visibility: Option<AstNode<Visibility>>,
+        /// Mutability of the binding (immutable by default)
+        pub mutability: Option<AstNode<Mutability>>,
+    }
 
-/// A pattern spread
-#[derive(Debug, PartialEq, Clone)]
-pub struct SpreadPat {
-    pub name: Option<AstNode<Name>>,
-}
+    /// A pattern spread
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct SpreadPat {
+        pub name: Option<AstNode<Name>>,
+    }
 
-/// The catch-all, i.e "ignore" pattern.
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub struct IgnorePat;
-
-/// A pattern. e.g. `Ok(Dog {props = (1, x)})`.
-#[derive(Debug, PartialEq, Clone)]
-pub enum Pat {
-    /// An access pattern is one that denotes the access of a property from
-    /// another pattern. This is used to denote namespace accesses like
-    /// `a::b::c`
-    Access(AccessPat),
-    /// A simple binding pattern, assign some value to the name of the pattern
-    Binding(BindingPat),
-    /// A representation of a constructor in the pattern space. Constructors in
-    /// patterns can either be enum or struct values. The subject of the
-    /// constructor can be either an [Pat::Access] or a [Pat::Binding].
-    Constructor(ConstructorPat),
-    /// Module pattern is used to destructure entries from an import.
-    Module(ModulePat),
-    /// A tuple pattern is a collection of patterns, e.g `(1, x, 'c')`
-    Tuple(TuplePat),
-    List(ListPat),
-    Lit(LitPat),
-    Or(OrPat),
-    If(IfPat),
-    Ignore(IgnorePat),
-    Spread(SpreadPat),
-}
+    /// The catch-all, i.e "ignore" pattern.
+    #[node]
+    #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+    pub struct IgnorePat;
+
+    /// A pattern that could not be parsed, the parser inserts this in place of
+    /// the malformed pattern when it recovers from a syntax error.
+    #[node]
+    #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+    pub struct ErrPat;
+
+    /// Whether the upper bound of a range is included within the range.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    pub enum RangeEnd {
+        /// The upper bound is excluded from the range, e.g. `a..b`.
+        Excluded,
+        /// The upper bound is included within the range, e.g. `a..=b`.
+        Included,
+    }
 
-/// Enum representing whether a declaration is public or private
-/// within module scope.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum Visibility {
-    /// The binding is private to outer scopes. This is assumed by default.
-    Private,
-    /// The binding is public to outer scopes. The modifier has no
-    /// effect if used within inner module scopes like a function.
-    Public,
-}
+    impl Display for RangeEnd {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                RangeEnd::Excluded => write!(f, ".."),
+                RangeEnd::Included => write!(f, "..="),
+            }
+        }
+    }
 
-impl Display for Visibility {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Visibility::Private => write!(f, "private"),
-            Visibility::Public => write!(f, "public"),
+    /// A range pattern, matching any value that lies between the two literal
+    /// bounds, e.g. `'a'..='z'` or `0..10`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct RangePat {
+        /// The lower bound of the range.
+        pub lo: AstNode<LitPat>,
+        /// The upper bound of the range.
+        pub hi: AstNode<LitPat>,
+        /// Whether the upper bound is included within the range.
+        pub end: RangeEnd,
+    }
+
+    /// A pattern. e.g. `Ok(Dog {props = (1, x)})`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub enum Pat {
+        /// An access pattern is one that denotes the access of a property from
+        /// another pattern. This is used to denote namespace accesses like
+        /// `a::b::c`
+        Access(AccessPat),
+        /// A simple binding pattern, assign some value to the name of the pattern
+        Binding(BindingPat),
+        /// A representation of a constructor in the pattern space. Constructors in
+        /// patterns can either be enum or struct values. The subject of the
+        /// constructor can be either an [Pat::Access] or a [Pat::Binding].
+        Constructor(ConstructorPat),
+        /// Module pattern is used to destructure entries from an import.
+        Module(ModulePat),
+        /// A tuple pattern is a collection of patterns, e.g `(1, x, 'c')`
+        Tuple(TuplePat),
+        List(ListPat),
+        Lit(LitPat),
+        Or(OrPat),
+        If(IfPat),
+        Ignore(IgnorePat),
+        Spread(SpreadPat),
+        /// A range pattern, e.g. `1..=5`
+        Range(RangePat),
+        /// A pattern that failed to parse
+        Err(ErrPat),
+    }
+
+    /// Enum representing whether a declaration is public or private
+    /// within module scope.
+    #[node(visit = "visibility_modifier")]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    pub enum Visibility {
+        /// The binding is private to outer scopes. This is assumed by default.
+        Private,
+        /// The binding is public to outer scopes. The modifier has no
+        /// effect if used within inner module scopes like a function.
+        Public,
+    }
+
+    impl Display for Visibility {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Visibility::Private => write!(f, "private"),
+                Visibility::Public => write!(f, "public"),
+            }
         }
     }
-}
 
-/// Enum representing whether a [BindingPat] is declared as being mutable
-/// or immutable.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum Mutability {
-    /// Declare that the binding can be re-assigned.
-    Mutable,
-    /// Declare that the binding cannot be re-assigned or methods that require
-    /// mutable access cannot take this binding. This is assumed by default.
-    Immutable,
-}
+    /// Enum representing whether a [BindingPat] is declared as being mutable
+    /// or immutable.
+    #[node(visit = "mutability_modifier")]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    pub enum Mutability {
+        /// Declare that the binding can be re-assigned.
+        Mutable,
+        /// Declare that the binding cannot be re-assigned or methods that require
+        /// mutable access cannot take this binding. This is assumed by default.
+        Immutable,
+    }
 
-impl Display for Mutability {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Mutability::Mutable => write!(f, "mutable"),
-            Mutability::Immutable => write!(f, "immutable"),
+    impl Display for Mutability {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Mutability::Mutable => write!(f, "mutable"),
+                Mutability::Immutable => write!(f, "immutable"),
+            }
         }
     }
-}
 
-/// A type function, e.g. `<T, U: Conv<U>> => ...`.
-///
-/// Used in struct, enum, trait, and function definitions.
-#[derive(Debug, PartialEq, Clone)]
-pub struct TyFnDef {
-    /// The type arguments of the function.
-    pub params: AstNodes<Param>,
-    /// Optional return type of the type function
-    pub return_ty: Option<AstNode<Ty>>,
-    /// The body of the type function,
-    pub body: AstNode<Expr>,
-}
+    /// A type function, e.g. `<T, U: Conv<U>> => ...`.
+    ///
+    /// Used in struct, enum, trait, and function definitions.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct TyFnDef {
+        /// The type arguments of the function.
+        pub params: AstNodes<Param>,
+        /// Optional return type of the type function
+        pub return_ty: Option<AstNode<Ty>>,
+        /// The body of the type function,
+        pub body: AstNode<Expr>,
+    }
 
-/// A declaration, e.g. `x := 3;`.
-#[derive(Debug, PartialEq, Clone)]
-pub struct Declaration {
-    /// The pattern to bind the right-hand side to.
-    pub pat: AstNode<Pat>,
+    /// An attribute that is applied to a declaration, e.g. `#[inline]` or
+    /// `#[deprecated("use foo instead")]`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct Attribute {
+        /// The name of the attribute.
+        pub name: AstNode<Name>,
+        /// The arguments that are given to the attribute, if any.
+        pub args: Option<AstNode<ConstructorCallArgs>>,
+    }
 
-    /// Any associated type with the expression
-    pub ty: Option<AstNode<Ty>>,
+    /// A declaration, e.g. `x := 3;`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct Declaration {
+        /// Any attributes that are applied to the declaration.
+        pub attributes: AstNodes<Attribute>,
 
-    /// Any value that is assigned to the binding, simply
-    /// an expression.
-    pub value: Option<AstNode<Expr>>,
-}
+        /// The pattern to bind the right-hand side to.
+        pub pat: AstNode<Pat>,
 
-/// A merge declaration (adding implementations to traits/structs), e.g. `x ~=
-/// impl { ... };`.
-#[derive(Debug, PartialEq, Clone)]
-pub struct MergeDeclaration {
-    /// The expression to bind the right-hand side to.
-    pub decl: AstNode<Expr>,
+        /// Any associated type with the expression
+        pub ty: Option<AstNode<Ty>>,
 
-    /// Any value that is assigned to the binding, simply
-    /// an expression.
-    pub value: AstNode<Expr>,
-}
+        /// Any value that is assigned to the binding, simply
+        /// an expression.
+        pub value: Option<AstNode<Expr>>,
+    }
 
-/// Unary operators that are defined within the core of the language.
-#[derive(Debug, Clone)]
-pub enum UnOp {
-    // Bitwise logical inversion
-    BitNot,
-    /// Logical inversion.
-    Not,
-    /// The operator '-' for negation
-    Neg,
-}
+    /// A merge declaration (adding implementations to traits/structs), e.g. `x ~=
+    /// impl { ... };`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct MergeDeclaration {
+        /// The expression to bind the right-hand side to.
+        pub decl: AstNode<Expr>,
+
+        /// Any value that is assigned to the binding, simply
+        /// an expression.
+        pub value: AstNode<Expr>,
+    }
 
-impl Display for UnOp {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            UnOp::BitNot => write!(f, "~"),
-            UnOp::Not => write!(f, "!"),
-            UnOp::Neg => write!(f, "-"),
+    /// Unary operators that are defined within the core of the language.
+    #[node(visit = "unary_operator", ret = "UnaryOperatorRet")]
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub enum UnOp {
+        // Bitwise logical inversion
+        BitNot,
+        /// Logical inversion.
+        Not,
+        /// The operator '-' for negation
+        Neg,
+    }
+
+    impl Display for UnOp {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                UnOp::BitNot => write!(f, "~"),
+                UnOp::Not => write!(f, "!"),
+                UnOp::Neg => write!(f, "-"),
+            }
         }
     }
-}
 
-/// Binary operators that are defined within the core of the language.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum BinOp {
-    /// '=='
-    EqEq,
-    /// '!='
-    NotEq,
-    /// '|'
-    BitOr,
-    /// '||'
-    Or,
-    /// '&'
-    BitAnd,
-    /// '&&'
-    And,
-    /// '^'
-    BitXor,
-    /// '^^'
-    Exp,
-    /// '>'
-    Gt,
-    /// '>='
-    GtEq,
-    /// '<'
-    Lt,
-    /// '<='
-    LtEq,
-    /// '>>'
-    Shr,
-    /// '<<'
-    Shl,
-    /// '+'
-    Add,
-    /// '-'
-    Sub,
-    /// '*'
-    Mul,
-    /// '/'
-    Div,
-    /// '%'
-    Mod,
-    /// 'as'
-    As,
-    /// `~`
-    Merge,
-}
+    /// Binary operators that are defined within the core of the language.
+    #[node(visit = "binary_operator", ret = "BinaryOperatorRet")]
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+    pub enum BinOp {
+        /// '=='
+        EqEq,
+        /// '!='
+        NotEq,
+        /// '|'
+        BitOr,
+        /// '||'
+        Or,
+        /// '&'
+        BitAnd,
+        /// '&&'
+        And,
+        /// '^'
+        BitXor,
+        /// '^^'
+        Exp,
+        /// '>'
+        Gt,
+        /// '>='
+        GtEq,
+        /// '<'
+        Lt,
+        /// '<='
+        LtEq,
+        /// '>>'
+        Shr,
+        /// '<<'
+        Shl,
+        /// '+'
+        Add,
+        /// '-'
+        Sub,
+        /// '*'
+        Mul,
+        /// '/'
+        Div,
+        /// '%'
+        Mod,
+        /// 'as'
+        As,
+        /// `~`
+        Merge,
+        /// `|>`
+        Pipe,
+    }
 
-impl Display for BinOp {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            BinOp::EqEq => write!(f, "=="),
-            BinOp::NotEq => write!(f, "!="),
-            BinOp::BitOr => write!(f, "|"),
-            BinOp::Or => write!(f, "||"),
-            BinOp::BitAnd => write!(f, "&"),
-            BinOp::And => write!(f, "&&"),
-            BinOp::BitXor => write!(f, "^"),
-            BinOp::Exp => write!(f, "^^"),
-            BinOp::Gt => write!(f, ">"),
-            BinOp::GtEq => write!(f, ">="),
-            BinOp::Lt => write!(f, "<"),
-            BinOp::LtEq => write!(f, "<="),
-            BinOp::Shr => write!(f, "<<"),
-            BinOp::Shl => write!(f, ">>"),
-            BinOp::Add => write!(f, "+"),
-            BinOp::Sub => write!(f, "-"),
-            BinOp::Mul => write!(f, "*"),
-            BinOp::Div => write!(f, "/"),
-            BinOp::Mod => write!(f, "%"),
-            BinOp::As => write!(f, "as"),
-            BinOp::Merge => write!(f, "~"),
+    impl Display for BinOp {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                BinOp::EqEq => write!(f, "=="),
+                BinOp::NotEq => write!(f, "!="),
+                BinOp::BitOr => write!(f, "|"),
+                BinOp::Or => write!(f, "||"),
+                BinOp::BitAnd => write!(f, "&"),
+                BinOp::And => write!(f, "&&"),
+                BinOp::BitXor => write!(f, "^"),
+                BinOp::Exp => write!(f, "^^"),
+                BinOp::Gt => write!(f, ">"),
+                BinOp::GtEq => write!(f, ">="),
+                BinOp::Lt => write!(f, "<"),
+                BinOp::LtEq => write!(f, "<="),
+                BinOp::Shr => write!(f, ">>"),
+                BinOp::Shl => write!(f, "<<"),
+                BinOp::Add => write!(f, "+"),
+                BinOp::Sub => write!(f, "-"),
+                BinOp::Mul => write!(f, "*"),
+                BinOp::Div => write!(f, "/"),
+                BinOp::Mod => write!(f, "%"),
+                BinOp::As => write!(f, "as"),
+                BinOp::Merge => write!(f, "~"),
+                BinOp::Pipe => write!(f, "|>"),
+            }
         }
     }
-}
 
-impl BinOp {
-    /// Compute the precedence for an operator
-    pub fn infix_binding_power(&self) -> (u8, u8) {
-        match self {
-            BinOp::Or => (2, 3),
-            BinOp::And => (4, 5),
-            BinOp::EqEq | BinOp::NotEq => (6, 5),
-            BinOp::Gt | BinOp::GtEq | BinOp::Lt | BinOp::LtEq => (7, 8),
-            BinOp::BitOr | BinOp::BitXor => (9, 10),
-            BinOp::BitAnd => (11, 12),
-            BinOp::Shr | BinOp::Shl => (13, 14),
-            BinOp::Add | BinOp::Sub => (15, 16),
-            BinOp::Mul | BinOp::Div | BinOp::Mod => (17, 18),
-            BinOp::Exp => (20, 19),
-            BinOp::As => (21, 22),
-            BinOp::Merge => (23, 24),
+    impl BinOp {
+        /// Compute the precedence for an operator
+        pub fn infix_binding_power(&self) -> (u8, u8) {
+            match self {
+                BinOp::Or => (2, 3),
+                BinOp::And => (4, 5),
+                BinOp::EqEq | BinOp::NotEq => (6, 5),
+                BinOp::Gt | BinOp::GtEq | BinOp::Lt | BinOp::LtEq => (7, 8),
+                BinOp::Pipe => (8, 9),
+                BinOp::BitOr | BinOp::BitXor => (9, 10),
+                BinOp::BitAnd => (11, 12),
+                BinOp::Shr | BinOp::Shl => (13, 14),
+                BinOp::Add | BinOp::Sub => (15, 16),
+                BinOp::Mul | BinOp::Div | BinOp::Mod => (17, 18),
+                BinOp::Exp => (20, 19),
+                BinOp::As => (21, 22),
+                BinOp::Merge => (23, 24),
+            }
+        }
+
+        /// This returns if an operator is actually re-assignable. By re-assignable,
+        /// this is in the sense that you can add a '=' to mean that you are
+        /// performing a re-assigning operation using the left
+        /// hand-side expression as a starting point and the rhs as the other
+        /// argument to the operator. For example, `a += b` is re-assigning
+        /// because it means `a = a + b`.
+        pub fn is_re_assignable(&self) -> bool {
+            matches!(
+                self,
+                BinOp::BitOr
+                    | BinOp::Or
+                    | BinOp::BitAnd
+                    | BinOp::And
+                    | BinOp::BitXor
+                    | BinOp::Exp
+                    | BinOp::Shr
+                    | BinOp::Shl
+                    | BinOp::Add
+                    | BinOp::Sub
+                    | BinOp::Mul
+                    | BinOp::Div
+                    | BinOp::Mod
+            )
         }
     }
 
-    /// This returns if an operator is actually re-assignable. By re-assignable,
-    /// this is in the sense that you can add a '=' to mean that you are
-    /// performing a re-assigning operation using the left
-    /// hand-side expression as a starting point and the rhs as the other
-    /// argument to the operator. For example, `a += b` is re-assigning
-    /// because it means `a = a + b`.
-    pub fn is_re_assignable(&self) -> bool {
-        matches!(
-            self,
-            BinOp::BitOr
-                | BinOp::Or
-                | BinOp::BitAnd
-                | BinOp::And
-                | BinOp::BitXor
-                | BinOp::Exp
-                | BinOp::Shr
-                | BinOp::Shl
-                | BinOp::Add
-                | BinOp::Sub
-                | BinOp::Mul
-                | BinOp::Div
-                | BinOp::Mod
-        )
+    /// An assign expression, e.g. `x = 4;`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct AssignExpr {
+        /// The left-hand side of the assignment.
+        ///
+        /// This should resolve to either a variable or a struct field.
+        pub lhs: AstNode<Expr>,
+        /// The right-hand side of the assignment.
+        ///
+        /// The value will be assigned to the left-hand side.
+        pub rhs: AstNode<Expr>,
     }
-}
 
-/// An assign expression, e.g. `x = 4;`.
-#[derive(Debug, PartialEq, Clone)]
-pub struct AssignExpr {
-    /// The left-hand side of the assignment.
-    ///
-    /// This should resolve to either a variable or a struct field.
-    pub lhs: AstNode<Expr>,
-    /// The right-hand side of the assignment.
-    ///
-    /// The value will be assigned to the left-hand side.
-    pub rhs: AstNode<Expr>,
-}
+    /// An assign expression, e.g. `x += 4;`.
+    #[node(walk = "AssignOpStatement")]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct AssignOpExpr {
+        /// The left-hand side of the assignment.
+        ///
+        /// This should resolve to either a variable or a struct field.
+        pub lhs: AstNode<Expr>,
+        /// The right-hand side of the assignment.
+        ///
+        /// The value will be assigned to the left-hand side.
+        pub rhs: AstNode<Expr>,
+
+        /// Operator that is applied with the assignment on the lhs with the rhs
+        /// value.
+        ///
+        /// Note: Some binary operators are not allowed to be in the location.
+        pub operator: AstNode<BinOp>,
+    }
 
-/// An assign expression, e.g. `x += 4;`.
-#[derive(Debug, PartialEq, Clone)]
-pub struct AssignOpExpr {
-    /// The left-hand side of the assignment.
-    ///
-    /// This should resolve to either a variable or a struct field.
-    pub lhs: AstNode<Expr>,
-    /// The right-hand side of the assignment.
-    ///
-    /// The value will be assigned to the left-hand side.
-    pub rhs: AstNode<Expr>,
+    /// A struct definition, e.g. `struct Foo = { bar: int; };`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct StructDef {
+        /// The fields of the struct, in the form of [Param].
+        pub entries: AstNodes<Param>,
+    }
+
+    /// A variant of an enum definition, e.g. `Some(T)`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct EnumDefEntry {
+        /// The name of the enum variant.
+        pub name: AstNode<Name>,
+        /// The arguments of the enum variant, if any.
+        pub args: AstNodes<Ty>,
+    }
+
+    /// An enum definition, e.g. `enum Option = <T> => { Some(T); None; };`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct EnumDef {
+        /// The variants of the enum, in the form of [EnumDefEntry].
+        pub entries: AstNodes<EnumDefEntry>,
+    }
 
-    /// Operator that is applied with the assignment on the lhs with the rhs
-    /// value.
+    /// A trait definition, e.g. `add := <T> => trait { add: (T, T) -> T; }`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct TraitDef {
+        pub members: AstNodes<Expr>,
+    }
+
+    /// A return statement.
     ///
-    /// Note: Some binary operators are not allowed to be in the location.
-    pub operator: AstNode<BinOp>,
-}
+    /// Has an optional return expression, which becomes `void` if [None] is given.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct ReturnStatement(pub Option<AstNode<Expr>>);
+
+    /// Break statement (only in loop context).
+    #[node]
+    #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+    pub struct BreakStatement;
+
+    /// Continue statement (only in loop context).
+    #[node]
+    #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+    pub struct ContinueStatement;
+
+    /// A branch/"case" of a `match` block.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct MatchCase {
+        /// The pattern of the `match` case.
+        pub pat: AstNode<Pat>,
+        /// The expression corresponding to the match case.
+        ///
+        /// Will be executed if the pattern succeeds.
+        pub expr: AstNode<Expr>,
+    }
 
-/// A struct definition, e.g. `struct Foo = { bar: int; };`.
-#[derive(Debug, PartialEq, Clone)]
-pub struct StructDef {
-    /// The fields of the struct, in the form of [Param].
-    pub entries: AstNodes<Param>,
-}
+    /// The origin of a match block
+    #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+    pub enum MatchOrigin {
+        If,
+        Match,
+        For,
+        While,
+        Propagate,
+    }
 
-/// A variant of an enum definition, e.g. `Some(T)`.
-#[derive(Debug, PartialEq, Clone)]
-pub struct EnumDefEntry {
-    /// The name of the enum variant.
-    pub name: AstNode<Name>,
-    /// The arguments of the enum variant, if any.
-    pub args: AstNodes<Ty>,
-}
+    /// A `match` block.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct MatchBlock {
+        /// The expression to match on.
+        pub subject: AstNode<Expr>,
+        /// The match cases to execute.
+        pub cases: AstNodes<MatchCase>,
+        /// Whether the match block represents a for, while, if or match statement
+        pub origin: MatchOrigin,
+    }
 
-/// An enum definition, e.g. `enum Option = <T> => { Some(T); None; };`.
-#[derive(Debug, PartialEq, Clone)]
-pub struct EnumDef {
-    /// The variants of the enum, in the form of [EnumDefEntry].
-    pub entries: AstNodes<EnumDefEntry>,
-}
+    /// A body block.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct BodyBlock {
+        /// Zero or more statements.
+        pub statements: AstNodes<Expr>,
+        /// Zero or one expression.
+        pub expr: Option<AstNode<Expr>>,
+    }
 
-/// A trait definition, e.g. `add := <T> => trait { add: (T, T) -> T; }`.
-#[derive(Debug, PartialEq, Clone)]
-pub struct TraitDef {
-    pub members: AstNodes<Expr>,
-}
+    impl BodyBlock {
+        /// Get the members of the body block: the list of statements as well as the
+        /// optional ending expression.
+        pub fn members(&self) -> impl Iterator<Item = AstNodeRef<Expr>> + '_ {
+            self.statements.ast_ref_iter().chain(self.expr.as_ref().map(|x| x.ast_ref()))
+        }
+    }
 
-/// A return statement.
-///
-/// Has an optional return expression, which becomes `void` if [None] is given.
-#[derive(Debug, PartialEq, Clone)]
-pub struct ReturnStatement(pub Option<AstNode<Expr>>);
-
-/// Break statement (only in loop context).
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub struct BreakStatement;
-
-/// Continue statement (only in loop context).
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub struct ContinueStatement;
-
-/// A branch/"case" of a `match` block.
-#[derive(Debug, PartialEq, Clone)]
-pub struct MatchCase {
-    /// The pattern of the `match` case.
-    pub pat: AstNode<Pat>,
-    /// The expression corresponding to the match case.
-    ///
-    /// Will be executed if the pattern succeeds.
-    pub expr: AstNode<Expr>,
-}
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct LoopBlock(pub AstNode<Block>);
 
-/// The origin of a match block
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub enum MatchOrigin {
-    If,
-    Match,
-    For,
-    While,
-}
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct ForLoopBlock {
+        pub pat: AstNode<Pat>,
+        pub iterator: AstNode<Expr>,
+        pub body: AstNode<Block>,
+    }
 
-/// A `match` block.
-#[derive(Debug, PartialEq, Clone)]
-pub struct MatchBlock {
-    /// The expression to match on.
-    pub subject: AstNode<Expr>,
-    /// The match cases to execute.
-    pub cases: AstNodes<MatchCase>,
-    /// Whether the match block represents a for, while, if or match statement
-    pub origin: MatchOrigin,
-}
+    /// A `while` loop, e.g. `while x > 2 { ... }`
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct WhileLoopBlock {
+        /// The condition of the the `while` loop.
+        pub condition: AstNode<Expr>,
+        /// The body of the `while` loop.
+        pub body: AstNode<Block>,
+    }
 
-/// A body block.
-#[derive(Debug, PartialEq, Clone)]
-pub struct BodyBlock {
-    /// Zero or more statements.
-    pub statements: AstNodes<Expr>,
-    /// Zero or one expression.
-    pub expr: Option<AstNode<Expr>>,
-}
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct IfClause {
+        /// The condition of the `if` block.
+        pub condition: AstNode<Expr>,
+        /// The body of the `if-statement`
+        pub body: AstNode<Block>,
+    }
 
-impl BodyBlock {
-    /// Get the members of the body block: the list of statements as well as the
-    /// optional ending expression.
-    pub fn members(&self) -> impl Iterator<Item = AstNodeRef<Expr>> + '_ {
-        self.statements.ast_ref_iter().chain(self.expr.as_ref().map(|x| x.ast_ref()))
+    /// An `if` block consisting of the condition, block and an optional else clause
+    /// e.g. `if x { ... } else { y }`
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct IfBlock {
+        pub clauses: AstNodes<IfClause>,
+        /// The else clause.
+        pub otherwise: Option<AstNode<Block>>,
     }
-}
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct LoopBlock(pub AstNode<Block>);
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct ModBlock(pub AstNode<BodyBlock>);
+
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct ImplBlock(pub AstNode<BodyBlock>);
+
+    /// A block.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub enum Block {
+        /// A match block.
+        Match(MatchBlock),
+        /// A loop block. The inner block is the loop body.
+        Loop(LoopBlock),
+        /// A for-loop block. This is later transpiled into a more simpler
+        /// construct using a `loop` and a `match` clause.
+        ///
+        /// Since for loops are used for iterators in hash, we transpile the
+        /// construct into a primitive loop. An iterator can be traversed by
+        /// calling the next function on the iterator. Since next returns a
+        /// Option type, we need to check if there is a value or if it returns None.
+        /// If a value does exist, we essentially perform an assignment to the
+        /// pattern provided. If None, the branch immediately breaks the for
+        /// loop.
+        ///
+        /// A rough outline of what the transpilation process for a for loop looks
+        /// like:
+        ///
+        /// Take the original for-loop:
+        ///
+        /// ```text
+        /// for <pat> in <iterator> {
+        ///      <block>
+        /// }
+        /// ```
+        ///
+        /// convert it to:
+        ///
+        /// ```text
+        /// loop {
+        ///     match next(<iterator>) {
+        ///         Some(<pat>) => <block>;
+        ///         None        => break;
+        ///     }
+        /// }
+        /// ```
+        For(ForLoopBlock),
+        /// A while-loop block. This is later transpiled into a `loop` and `match`
+        /// clause.
+        ///
+        /// In general, a while loop transpilation process occurs by transferring
+        /// the looping condition into a match block, which compares a boolean
+        /// condition. If the boolean condition evaluates to false, the loop
+        /// will immediately break. Otherwise the body expression is expected. A
+        /// rough outline of what the transpilation process for a while loop looks
+        /// like:
+        ///
+        /// ```text
+        /// while <condition> {
+        ///      <block>
+        /// }
+        /// ```
+        ///
+        /// Is converted to:
+        ///
+        /// ```text
+        /// loop {
+        ///     match <condition> {
+        ///         true  => <block>;
+        ///         false => break;
+        ///     }
+        /// }
+        /// ```
+        While(WhileLoopBlock),
+
+        /// The AST representation of an if-block.
+        ///
+        /// ## Transpilation
+        /// We transpile if-else blocks into match blocks in order to simplify
+        /// the typechecking process and optimisation efforts.
+        ///
+        /// Firstly, since we always want to check each case, we convert the
+        /// if statement into a series of and-patterns, where the right hand-side
+        /// pattern is the condition to execute the branch...
+        ///
+        /// For example:
+        /// ```text
+        /// if a { a_branch } else if b { b_branch } else { c_branch }
+        /// ```
+        /// will be transformed into...
+        /// ```text
+        /// match true {
+        ///     _ if a => a_branch
+        ///     _ if b => b_branch
+        ///     _ => c_branch
+        /// }
+        /// ```
+        ///
+        /// Additionally, if no 'else' clause is specified, we fill it with an
+        /// empty block since an if-block could be assigned to any variable and
+        /// therefore we need to know the outcome of all branches for
+        /// typechecking.
+        If(IfBlock),
+        /// A module block. The inner block becomes an inner module of the current
+        /// module.
+        Mod(ModBlock),
+        /// A body block.
+        Body(BodyBlock),
+        /// An implementation block
+        Impl(ImplBlock),
+    }
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct ForLoopBlock {
-    pub pat: AstNode<Pat>,
-    pub iterator: AstNode<Expr>,
-    pub body: AstNode<Block>,
-}
+    impl Block {
+        pub fn as_str(&self) -> &str {
+            match self {
+                Block::Match(_) => "match(..)",
+                Block::Loop(_) => "loop(..)",
+                Block::For(_) => "for(..)",
+                Block::While(_) => "while(..)",
+                Block::If(_) => "if(..)",
+                Block::Mod(_) => "mod(..)",
+                Block::Body(_) => "body(..)",
+                Block::Impl(_) => "impl(..)",
+            }
+        }
+    }
 
-/// A `while` loop, e.g. `while x > 2 { ... }`
-#[derive(Debug, PartialEq, Clone)]
-pub struct WhileLoopBlock {
-    /// The condition of the the `while` loop.
-    pub condition: AstNode<Expr>,
-    /// The body of the `while` loop.
-    pub body: AstNode<Block>,
-}
+    /// This enum describes the origin kind of the subject that a parameter
+    /// unification occurred on.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    pub enum ParamOrigin {
+        /// If at the current time, it's not known the origin of the parameter list,
+        /// the function will default to using this.
+        Unknown,
+        Tuple,
+        Struct,
+        Fn,
+        TyFn,
+        EnumVariant,
+        /// List pattern parameters, the parameters are all the same, but it's
+        /// used to represent the inner terms of the list pattern since spread
+        /// patterns may become named parameters.
+        ListPat,
+        /// Module pattern
+        ModulePat,
+    }
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct IfClause {
-    /// The condition of the `if` block.
-    pub condition: AstNode<Expr>,
-    /// The body of the `if-statement`
-    pub body: AstNode<Block>,
-}
+    impl Display for ParamOrigin {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                ParamOrigin::Unknown => write!(f, "unknown"),
+                ParamOrigin::Tuple => write!(f, "tuple"),
+                ParamOrigin::Struct => write!(f, "struct"),
+                ParamOrigin::Fn => write!(f, "function"),
+                ParamOrigin::TyFn => write!(f, "type function"),
+                ParamOrigin::EnumVariant => write!(f, "enum variant"),
+                ParamOrigin::ListPat => write!(f, "list pattern"),
+                ParamOrigin::ModulePat => write!(f, "module pattern"),
+            }
+        }
+    }
 
-/// An `if` block consisting of the condition, block and an optional else clause
-/// e.g. `if x { ... } else { y }`
-#[derive(Debug, PartialEq, Clone)]
-pub struct IfBlock {
-    pub clauses: AstNodes<IfClause>,
-    /// The else clause.
-    pub otherwise: Option<AstNode<Block>>,
-}
+    /// A function definition parameter.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct Param {
+        /// The name of the argument.
+        pub name: AstNode<Name>,
+        /// The type of the argument, if any.
+        pub ty: Option<AstNode<Ty>>,
+        /// Default value of the argument if provided.
+        ///
+        /// If the value is provided, this makes it a named argument
+        /// which means that they can be specified by putting the name of the
+        /// argument.
+        pub default: Option<AstNode<Expr>>,
+        /// The origin of the parameter, whether it is from a struct field, function
+        /// def, type function def, etc.
+        pub origin: ParamOrigin,
+    }
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct ModBlock(pub AstNode<BodyBlock>);
-
-#[derive(Debug, PartialEq, Clone)]
-pub struct ImplBlock(pub AstNode<BodyBlock>);
-
-/// A block.
-#[derive(Debug, PartialEq, Clone)]
-pub enum Block {
-    /// A match block.
-    Match(MatchBlock),
-    /// A loop block. The inner block is the loop body.
-    Loop(LoopBlock),
-    /// A for-loop block. This is later transpiled into a more simpler
-    /// construct using a `loop` and a `match` clause.
-    ///
-    /// Since for loops are used for iterators in hash, we transpile the
-    /// construct into a primitive loop. An iterator can be traversed by
-    /// calling the next function on the iterator. Since next returns a
-    /// Option type, we need to check if there is a value or if it returns None.
-    /// If a value does exist, we essentially perform an assignment to the
-    /// pattern provided. If None, the branch immediately breaks the for
-    /// loop.
-    ///
-    /// A rough outline of what the transpilation process for a for loop looks
-    /// like:
-    ///
-    /// Take the original for-loop:
-    ///
-    /// ```text
-    /// for <pat> in <iterator> {
-    ///      <block>
-    /// }
-    /// ```
-    ///
-    /// convert it to:
-    ///
-    /// ```text
-    /// loop {
-    ///     match next(<iterator>) {
-    ///         Some(<pat>) => <block>;
-    ///         None        => break;
-    ///     }
-    /// }
-    /// ```
-    For(ForLoopBlock),
-    /// A while-loop block. This is later transpiled into a `loop` and `match`
-    /// clause.
-    ///
-    /// In general, a while loop transpilation process occurs by transferring
-    /// the looping condition into a match block, which compares a boolean
-    /// condition. If the boolean condition evaluates to false, the loop
-    /// will immediately break. Otherwise the body expression is expected. A
-    /// rough outline of what the transpilation process for a while loop looks
-    /// like:
-    ///
-    /// ```text
-    /// while <condition> {
-    ///      <block>
-    /// }
-    /// ```
-    ///
-    /// Is converted to:
-    ///
-    /// ```text
-    /// loop {
-    ///     match <condition> {
-    ///         true  => <block>;
-    ///         false => break;
-    ///     }
-    /// }
-    /// ```
-    While(WhileLoopBlock),
+    /// A function definition.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct FnDef {
+        /// The parameters of the function definition.
+        #[node_field(name = "args")]
+        pub params: AstNodes<Param>,
+        /// The return type of the function definition.
+        ///
+        /// Will be inferred if [None].
+        pub return_ty: Option<AstNode<Ty>>,
+        /// The body/contents of the function, in the form of an expression.
+        pub fn_body: AstNode<Expr>,
+        /// The bindings from enclosing scopes that the function captures.
+        pub captures: FnCaptures,
+    }
 
-    /// The AST representation of an if-block.
-    ///
-    /// ## Transpilation
-    /// We transpile if-else blocks into match blocks in order to simplify
-    /// the typechecking process and optimisation efforts.
-    ///
-    /// Firstly, since we always want to check each case, we convert the
-    /// if statement into a series of and-patterns, where the right hand-side
-    /// pattern is the condition to execute the branch...
-    ///
-    /// For example:
-    /// ```text
-    /// if a { a_branch } else if b { b_branch } else { c_branch }
-    /// ```
-    /// will be transformed into...
-    /// ```text
-    /// match true {
-    ///     _ if a => a_branch
-    ///     _ if b => b_branch
-    ///     _ => c_branch
-    /// }
-    /// ```
-    ///
-    /// Additionally, if no 'else' clause is specified, we fill it with an
-    /// empty block since an if-block could be assigned to any variable and
-    /// therefore we need to know the outcome of all branches for
-    /// typechecking.
-    If(IfBlock),
-    /// A module block. The inner block becomes an inner module of the current
-    /// module.
-    Mod(ModBlock),
-    /// A body block.
-    Body(BodyBlock),
-    /// An implementation block
-    Impl(ImplBlock),
-}
+    /// A binding from an enclosing scope that is referenced within the body of a
+    /// function literal, e.g. `y` in `(x: i32) => x + y`.
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+    pub struct Capture {
+        /// The name of the captured binding.
+        pub name: Identifier,
+        /// The span of the first use of the binding within the function.
+        pub span: Span,
+        /// Whether the function mutates the captured binding.
+        pub is_mutated: bool,
+    }
 
-impl Block {
-    pub fn as_str(&self) -> &str {
-        match self {
-            Block::Match(_) => "match(..)",
-            Block::Loop(_) => "loop(..)",
-            Block::For(_) => "for(..)",
-            Block::While(_) => "while(..)",
-            Block::If(_) => "if(..)",
-            Block::Mod(_) => "mod(..)",
-            Block::Body(_) => "body(..)",
-            Block::Impl(_) => "impl(..)",
+    /// The capture set of a function literal. The parser always produces an empty
+    /// set, it is only filled in once the semantic analysis pass has resolved the
+    /// free variables of the function body. The set is only ever written once,
+    /// which is why it can be filled in whilst the tree is being shared between
+    /// the analysis workers.
+    #[derive(Debug, PartialEq, Eq, Clone, Default)]
+    pub struct FnCaptures(OnceLock<Vec<Capture>>);
+
+    /// [FnCaptures] are serialised as a list of the captures, or as `null` if
+    /// the captures have not been resolved yet.
+    impl Serialize for FnCaptures {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.get().serialize(serializer)
         }
     }
-}
 
-/// This enum describes the origin kind of the subject that a parameter
-/// unification occurred on.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ParamOrigin {
-    /// If at the current time, it's not known the origin of the parameter list,
-    /// the function will default to using this.
-    Unknown,
-    Tuple,
-    Struct,
-    Fn,
-    TyFn,
-    EnumVariant,
-    /// List pattern parameters, the parameters are all the same, but it's
-    /// used to represent the inner terms of the list pattern since spread
-    /// patterns may become named parameters.
-    ListPat,
-    /// Module pattern
-    ModulePat,
-}
+    impl<'de> Deserialize<'de> for FnCaptures {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let captures = FnCaptures::default();
 
-impl Display for ParamOrigin {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ParamOrigin::Unknown => write!(f, "unknown"),
-            ParamOrigin::Tuple => write!(f, "tuple"),
-            ParamOrigin::Struct => write!(f, "struct"),
-            ParamOrigin::Fn => write!(f, "function"),
-            ParamOrigin::TyFn => write!(f, "type function"),
-            ParamOrigin::EnumVariant => write!(f, "enum variant"),
-            ParamOrigin::ListPat => write!(f, "list pattern"),
-            ParamOrigin::ModulePat => write!(f, "module pattern"),
+            if let Some(resolved) = Option::<Vec<Capture>>::deserialize(deserializer)? {
+                captures.set(resolved);
+            }
+
+            Ok(captures)
         }
     }
-}
 
-/// A function definition parameter.
-#[derive(Debug, PartialEq, Clone)]
-pub struct Param {
-    /// The name of the argument.
-    pub name: AstNode<Name>,
-    /// The type of the argument, if any.
-    pub ty: Option<AstNode<Ty>>,
-    /// Default value of the argument if provided.
-    ///
-    /// If the value is provided, this makes it a named argument
-    /// which means that they can be specified by putting the name of the
-    /// argument.
-    pub default: Option<AstNode<Expr>>,
-    /// The origin of the parameter, whether it is from a struct field, function
-    /// def, type function def, etc.
-    pub origin: ParamOrigin,
-}
+    impl FnCaptures {
+        /// Get the captures of the function, returns [None] if the captures have
+        /// not been resolved yet.
+        pub fn get(&self) -> Option<&[Capture]> {
+            self.0.get().map(|captures| captures.as_slice())
+        }
 
-/// A function definition.
-#[derive(Debug, PartialEq, Clone)]
-pub struct FnDef {
-    /// The parameters of the function definition.
-    pub params: AstNodes<Param>,
-    /// The return type of the function definition.
-    ///
-    /// Will be inferred if [None].
-    pub return_ty: Option<AstNode<Ty>>,
-    /// The body/contents of the function, in the form of an expression.
-    pub fn_body: AstNode<Expr>,
-}
+        /// Record the resolved captures of the function. If the captures have
+        /// already been resolved, the previous set is kept.
+        pub fn set(&self, captures: Vec<Capture>) {
+            let _ = self.0.set(captures);
+        }
+    }
 
-/// Function call argument.
-#[derive(Debug, PartialEq, Clone)]
-pub struct ConstructorCallArg {
-    /// Optional name for the function argument, e.g `f(x = 3);`.
-    pub name: Option<AstNode<Name>>,
-    /// Each argument of the function call, as an expression.
-    pub value: AstNode<Expr>,
-}
+    /// Function call argument.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct ConstructorCallArg {
+        /// Optional name for the function argument, e.g `f(x = 3);`.
+        pub name: Option<AstNode<Name>>,
+        /// Each argument of the function call, as an expression.
+        pub value: AstNode<Expr>,
+    }
 
-/// Function call arguments.
-#[derive(Debug, PartialEq, Clone)]
-pub struct ConstructorCallArgs {
-    pub entries: AstNodes<ConstructorCallArg>,
-}
+    /// Function call arguments.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct ConstructorCallArgs {
+        pub entries: AstNodes<ConstructorCallArg>,
+    }
 
-/// A constructor call expression. This can either be a function
-/// call, a struct instantiation or a enum variant instantiation.
-#[derive(Debug, PartialEq, Clone)]
-pub struct ConstructorCallExpr {
-    /// An expression which evaluates to a function value.
-    pub subject: AstNode<Expr>,
-    /// Arguments to the function, in the form of [ConstructorCallArgs].
-    pub args: AstNode<ConstructorCallArgs>,
-}
+    /// A constructor call expression. This can either be a function
+    /// call, a struct instantiation or a enum variant instantiation.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct ConstructorCallExpr {
+        /// An expression which evaluates to a function value.
+        pub subject: AstNode<Expr>,
+        /// Arguments to the function, in the form of [ConstructorCallArgs].
+        pub args: AstNode<ConstructorCallArgs>,
+    }
 
-/// An directive expression.
-#[derive(PartialEq, Debug, Clone)]
-pub struct DirectiveExpr {
-    /// The name of the directive (without the "#").
-    pub name: AstNode<Name>,
-    /// An expression which is referenced in the directive
-    pub subject: AstNode<Expr>,
-}
+    /// An directive expression.
+    #[node]
+    #[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+    pub struct DirectiveExpr {
+        /// The name of the directive (without the "#").
+        pub name: AstNode<Name>,
+        /// An expression which is referenced in the directive
+        pub subject: AstNode<Expr>,
+    }
 
-/// A the kind of access an [AccessExpr] has
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub enum AccessKind {
-    /// A namespace access, i.e. `a::b`
-    Namespace,
-    /// A property access, i.e. `a.b`
-    Property,
-}
+    /// A the kind of access an [AccessExpr] has
+    #[node(by_value)]
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+    pub enum AccessKind {
+        /// A namespace access, i.e. `a::b`
+        Namespace,
+        /// A property access, i.e. `a.b`
+        Property,
+    }
 
-impl Display for AccessKind {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            AccessKind::Namespace => write!(f, "namespace"),
-            AccessKind::Property => write!(f, "property"),
+    impl Display for AccessKind {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                AccessKind::Namespace => write!(f, "namespace"),
+                AccessKind::Property => write!(f, "property"),
+            }
         }
     }
-}
-
-/// A property access expression.
-#[derive(Debug, PartialEq, Clone)]
-pub struct AccessExpr {
-    /// An expression which evaluates to a struct or tuple value.
-    pub subject: AstNode<Expr>,
-    /// The property of the subject to access.
-    pub property: AstNode<Name>,
-    /// The kind of access, either namespacing or property
-    pub kind: AccessKind,
-}
 
-/// A typed expression, e.g. `foo as int`.
-#[derive(Debug, PartialEq, Clone)]
-pub struct CastExpr {
-    /// The annotated type of the expression.
-    pub ty: AstNode<Ty>,
-    /// The expression being typed.
-    pub expr: AstNode<Expr>,
-}
+    /// A property access expression.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct AccessExpr {
+        /// An expression which evaluates to a struct or tuple value.
+        pub subject: AstNode<Expr>,
+        /// The property of the subject to access.
+        pub property: AstNode<Name>,
+        /// The kind of access, either namespacing or property
+        pub kind: AccessKind,
+    }
 
-/// Represents a path to a module, given as a string literal to an `import`
-/// call.
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub struct Import {
-    pub path: Str,
-    pub resolved_path: PathBuf,
-}
+    /// A typed expression, e.g. `foo as int`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct CastExpr {
+        /// The annotated type of the expression.
+        pub ty: AstNode<Ty>,
+        /// The expression being typed.
+        pub expr: AstNode<Expr>,
+    }
 
-/// A variable expression.
-#[derive(Debug, PartialEq, Clone)]
-pub struct VariableExpr {
-    /// The name of the variable.
-    pub name: AstNode<Name>,
-}
+    /// Represents a path to a module, given as a string literal to an `import`
+    /// call.
+    #[node]
+    #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+    pub struct Import {
+        pub path: Str,
+        pub resolved_path: PathBuf,
+    }
 
-/// A reference expression with a flag denoting whether it is a raw ref or not
-#[derive(Debug, PartialEq, Clone)]
-pub struct RefExpr {
-    pub inner_expr: AstNode<Expr>,
-    /// The kind of reference, either being a normal reference or a `raw`
-    /// reference
-    pub kind: RefKind,
-    /// Mutability modifier on the expression.
-    pub mutability: Option<AstNode<Mutability>>,
-}
+    /// A variable expression.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct VariableExpr {
+        /// The name of the variable.
+        pub name: AstNode<Name>,
+    }
 
-/// A dereference expression.
-#[derive(Debug, PartialEq, Clone)]
-pub struct TyExpr(pub AstNode<Ty>);
-
-/// A dereference expression.
-#[derive(Debug, PartialEq, Clone)]
-pub struct DerefExpr(pub AstNode<Expr>);
-
-/// An unsafe expression.
-#[derive(Debug, PartialEq, Clone)]
-pub struct UnsafeExpr(pub AstNode<Expr>);
-
-/// A literal.
-#[derive(Debug, PartialEq, Clone)]
-pub struct LitExpr(pub AstNode<Lit>);
-
-/// A block.
-#[derive(Debug, PartialEq, Clone)]
-pub struct BlockExpr(pub AstNode<Block>);
-
-/// An `import` call.
-#[derive(Debug, PartialEq, Clone)]
-pub struct ImportExpr(pub AstNode<Import>);
-
-/// A trait implementation.
-#[derive(Debug, PartialEq, Clone)]
-pub struct TraitImpl {
-    /// The referenced name to the trait
-    pub ty: AstNode<Ty>,
-    /// The implementation of the trait.
-    pub implementation: AstNodes<Expr>,
-}
+    /// A reference expression with a flag denoting whether it is a raw ref or not
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct RefExpr {
+        pub inner_expr: AstNode<Expr>,
+        /// The kind of reference, either being a normal reference or a `raw`
+        /// reference
+        pub kind: RefKind,
+        /// Mutability modifier on the expression.
+        pub mutability: Option<AstNode<Mutability>>,
+    }
 
-/// A binary expression `2 + 2`.
-#[derive(Debug, PartialEq, Clone)]
-pub struct BinaryExpr {
-    pub lhs: AstNode<Expr>,
-    pub rhs: AstNode<Expr>,
-    pub operator: AstNode<BinOp>,
-}
+    /// A dereference expression.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct TyExpr(pub AstNode<Ty>);
+
+    /// A dereference expression.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct DerefExpr(pub AstNode<Expr>);
+
+    /// An unsafe expression.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct UnsafeExpr(pub AstNode<Expr>);
+
+    /// A literal.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct LitExpr(pub AstNode<Lit>);
+
+    /// A block.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct BlockExpr(pub AstNode<Block>);
+
+    /// An `import` call.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct ImportExpr(pub AstNode<Import>);
+
+    /// A trait implementation.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct TraitImpl {
+        /// The referenced name to the trait
+        pub ty: AstNode<Ty>,
+        /// The implementation of the trait.
+        pub implementation: AstNodes<Expr>,
+    }
 
-/// A unary expression `!a`.
-#[derive(Debug, PartialEq, Clone)]
-pub struct UnaryExpr {
-    pub expr: AstNode<Expr>,
-    pub operator: AstNode<UnOp>,
-}
+    /// A binary expression `2 + 2`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct BinaryExpr {
+        pub lhs: AstNode<Expr>,
+        pub rhs: AstNode<Expr>,
+        pub operator: AstNode<BinOp>,
+    }
 
-/// An index expression `arr[x]`.
-#[derive(Debug, PartialEq, Clone)]
-pub struct IndexExpr {
-    /// The subject that is being indexed.
-    pub subject: AstNode<Expr>,
-    /// The expression that is the index.
-    pub index_expr: AstNode<Expr>,
-}
+    /// A unary expression `!a`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct UnaryExpr {
+        pub expr: AstNode<Expr>,
+        pub operator: AstNode<UnOp>,
+    }
 
-/// The kind of an expression.
-#[derive(Debug, PartialEq, Clone)]
-pub enum ExprKind {
-    ConstructorCall(ConstructorCallExpr),
-    Directive(DirectiveExpr),
-    Declaration(Declaration),
-    Variable(VariableExpr),
-    /// Either a property access or a namespace access
-    Access(AccessExpr),
-    Ref(RefExpr),
-    Deref(DerefExpr),
-    Unsafe(UnsafeExpr),
-    LitExpr(LitExpr),
-    Cast(CastExpr),
-    Block(BlockExpr),
-    Import(ImportExpr),
-    StructDef(StructDef),
-    EnumDef(EnumDef),
-    TyFnDef(TyFnDef),
-    TraitDef(TraitDef),
-    FnDef(FnDef),
-    Ty(TyExpr),
-    Return(ReturnStatement),
-    Break(BreakStatement),
-    Continue(ContinueStatement),
-    /// Expression to index a subject e.g. `arr[x]`
-    Index(IndexExpr),
-    /// An expression that captures a variable or a pattern being assigned
-    /// to a right hand-side expression such as `x = 3`.
-    Assign(AssignExpr),
-    /// An expression that captures a variable or a pattern being assigned with
-    /// the application of a binary operator, such as `x += 3`.
-    AssignOp(AssignOpExpr),
-    /// A merge declaration is one that adds an implementation for a particular
-    /// trait/struct to an already declared item, such as `x ~= impl { ... }`
-    MergeDeclaration(MergeDeclaration),
-    TraitImpl(TraitImpl),
-    /// Binary Expression composed of a left and right hand-side with a binary
-    /// operator
-    BinaryExpr(BinaryExpr),
-    /// Unary Expression composed of a unary operator and an expression
-    UnaryExpr(UnaryExpr),
-}
+    /// A range expression `a..b` or `a..=b`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct RangeExpr {
+        /// The lower bound of the range.
+        pub lo: AstNode<Expr>,
+        /// The upper bound of the range.
+        pub hi: AstNode<Expr>,
+        /// Whether the upper bound is included within the range.
+        pub end: RangeEnd,
+    }
 
-/// An expression.
-#[derive(Debug, PartialEq, Clone)]
-pub struct Expr {
-    /// The kind of the expression
-    pub kind: ExprKind,
-}
+    /// An error propagation expression `a?`, which evaluates to the value within
+    /// `Ok(...)`, or returns the error from the current function.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct PropagateExpr {
+        /// The expression whose error is propagated.
+        pub subject: AstNode<Expr>,
+    }
 
-impl Expr {
-    /// Create a new [Expr] with a specific [ExprKind].
-    pub fn new(kind: ExprKind) -> Self {
-        Self { kind }
+    /// An expression that could not be parsed, the parser inserts this in place
+    /// of the malformed expression when it recovers from a syntax error.
+    #[node]
+    #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+    pub struct ErrExpr;
+
+    /// An index expression `arr[x]`.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct IndexExpr {
+        /// The subject that is being indexed.
+        pub subject: AstNode<Expr>,
+        /// The expression that is the index.
+        pub index_expr: AstNode<Expr>,
     }
 
-    /// Convert the [Expr] into an [ExprKind]
-    pub fn into_kind(self) -> ExprKind {
-        self.kind
+    /// The kind of an expression.
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub enum ExprKind {
+        ConstructorCall(ConstructorCallExpr),
+        Directive(DirectiveExpr),
+        Declaration(Declaration),
+        Variable(VariableExpr),
+        /// Either a property access or a namespace access
+        Access(AccessExpr),
+        Ref(RefExpr),
+        Deref(DerefExpr),
+        Unsafe(UnsafeExpr),
+        LitExpr(LitExpr),
+        Cast(CastExpr),
+        Block(BlockExpr),
+        Import(ImportExpr),
+        StructDef(StructDef),
+        EnumDef(EnumDef),
+        TyFnDef(TyFnDef),
+        TraitDef(TraitDef),
+        FnDef(FnDef),
+        Ty(TyExpr),
+        Return(ReturnStatement),
+        Break(BreakStatement),
+        Continue(ContinueStatement),
+        /// Expression to index a subject e.g. `arr[x]`
+        Index(IndexExpr),
+        /// An expression that captures a variable or a pattern being assigned
+        /// to a right hand-side expression such as `x = 3`.
+        Assign(AssignExpr),
+        /// An expression that captures a variable or a pattern being assigned with
+        /// the application of a binary operator, such as `x += 3`.
+        AssignOp(AssignOpExpr),
+        /// A merge declaration is one that adds an implementation for a particular
+        /// trait/struct to an already declared item, such as `x ~= impl { ... }`
+        MergeDeclaration(MergeDeclaration),
+        TraitImpl(TraitImpl),
+        /// Binary Expression composed of a left and right hand-side with a binary
+        /// operator
+        BinaryExpr(BinaryExpr),
+        /// Unary Expression composed of a unary operator and an expression
+        UnaryExpr(UnaryExpr),
+        /// Range expression composed of a lower and upper bound, e.g. `0..10`
+        Range(RangeExpr),
+        /// Error propagation expression, e.g. `read_file(path)?`
+        Propagate(PropagateExpr),
+        /// An expression that failed to parse
+        Err(ErrExpr),
     }
 
-    /// Get the [ExprKind] of the expression
-    pub fn kind(&self) -> &ExprKind {
-        &self.kind
+    /// An expression.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct Expr {
+        /// The kind of the expression, which is visited as the expression itself
+        #[node_kind]
+        pub kind: ExprKind,
     }
 
-    /// Get the [ExprKind] of the expression
-    pub fn kind_mut(&mut self) -> &mut ExprKind {
-        &mut self.kind
+    impl Expr {
+        /// Create a new [Expr] with a specific [ExprKind].
+        pub fn new(kind: ExprKind) -> Self {
+            Self { kind }
+        }
+
+        /// Convert the [Expr] into an [ExprKind]
+        pub fn into_kind(self) -> ExprKind {
+            self.kind
+        }
+
+        /// Get the [ExprKind] of the expression
+        pub fn kind(&self) -> &ExprKind {
+            &self.kind
+        }
+
+        /// Get the [ExprKind] of the expression
+        pub fn kind_mut(&mut self) -> &mut ExprKind {
+            &mut self.kind
+        }
     }
-}
 
-/// A module.
-///
-/// Represents a parsed `.hash` file.
-#[derive(Debug, PartialEq, Clone)]
-pub struct Module {
-    /// The contents of the module, as a list of expressions terminated with a
-    /// semi-colon.
-    pub contents: AstNodes<Expr>,
+    /// A module.
+    ///
+    /// Represents a parsed `.hash` file.
+    #[node]
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    pub struct Module {
+        /// The contents of the module, as a list of expressions terminated with a
+        /// semi-colon.
+        pub contents: AstNodes<Expr>,
+    }
 }
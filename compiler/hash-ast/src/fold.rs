@@ -0,0 +1,21 @@
+//! Fold implementation for [crate::ast] nodes.
+//!
+//! A fold consumes a tree and produces a new tree, which makes it a better fit
+//! than [crate::visitor::AstVisitorMut] for passes that replace nodes with
+//! entirely different nodes, such as de-sugaring.
+//!
+//! The fold trait and the [walk] functions are generated from the definitions
+//! of the nodes in [crate::ast] by the `define_tree` macro, along with the
+//! visitors, and are re-exported here.
+
+use replace_with::replace_with_or_abort;
+
+use crate::ast::AstNode;
+pub use crate::ast::{walk_fold as walk, AstFolder};
+
+/// Fold a node in place, this is useful when a node is only available by a
+/// mutable reference, for example when it is stored within a
+/// [crate::ast::OwnsAstNode].
+pub fn fold_in_place<T>(node: &mut AstNode<T>, f: impl FnOnce(AstNode<T>) -> AstNode<T>) {
+    replace_with_or_abort(node, f)
+}
@@ -0,0 +1,320 @@
+//! An index over the nodes of an AST, which can be used to find the node that
+//! covers a position within the source, and the parents of a node.
+use std::{collections::HashMap, convert::Infallible};
+
+use hash_source::location::Span;
+
+use crate::{
+    ast::{self, AstNodeId},
+    visitor::{walk, AstVisitor},
+};
+
+/// The information that is recorded about each node in the [AstIndex].
+#[derive(Debug)]
+struct IndexedNode {
+    /// The [Span] of the node.
+    span: Span,
+    /// The name of the type of the node, such as `VariableExpr`.
+    kind: &'static str,
+    /// The node that this node is a child of, this is only [None] for the root
+    /// of the index.
+    parent: Option<AstNodeId>,
+    /// The children of the node, in the order that they are visited.
+    children: Vec<AstNodeId>,
+}
+
+/// An index over all of the nodes within a [ast::Module] or [ast::BodyBlock],
+/// which maps the [AstNodeId] of each node to its [Span], its kind and its
+/// parent.
+///
+/// @@Note: nodes that wrap another node, such as an [ast::Expr] which wraps an
+/// [ast::VariableExpr], share the same [AstNodeId] with the inner node, and so
+/// they are recorded as a single node with the kind of the innermost node.
+#[derive(Debug)]
+pub struct AstIndex {
+    /// The id of the node that the index was built from.
+    root: AstNodeId,
+    /// All of the nodes that are within the index.
+    nodes: HashMap<AstNodeId, IndexedNode>,
+}
+
+impl AstIndex {
+    /// Build an index over all of the nodes within the given [ast::Module].
+    pub fn for_module(node: ast::AstNodeRef<ast::Module>) -> Self {
+        let mut builder = IndexBuilder::default();
+        builder.visit_module(&(), node).unwrap();
+        builder.finish(node.id())
+    }
+
+    /// Build an index over all of the nodes within the given
+    /// [ast::BodyBlock].
+    pub fn for_body_block(node: ast::AstNodeRef<ast::BodyBlock>) -> Self {
+        let mut builder = IndexBuilder::default();
+        builder.visit_body_block(&(), node).unwrap();
+        builder.finish(node.id())
+    }
+
+    /// Get the [AstNodeId] of the node that the index was built from.
+    pub fn root(&self) -> AstNodeId {
+        self.root
+    }
+
+    /// Check whether the node with the given [AstNodeId] is within the index.
+    pub fn contains(&self, id: AstNodeId) -> bool {
+        self.nodes.contains_key(&id)
+    }
+
+    /// Get the [Span] of the node with the given [AstNodeId].
+    pub fn span_of(&self, id: AstNodeId) -> Option<Span> {
+        self.nodes.get(&id).map(|node| node.span)
+    }
+
+    /// Get the name of the type of the node with the given [AstNodeId], such
+    /// as `VariableExpr` or `BindingPat`.
+    pub fn kind_of(&self, id: AstNodeId) -> Option<&'static str> {
+        self.nodes.get(&id).map(|node| node.kind)
+    }
+
+    /// Get the parent of the node with the given [AstNodeId], this is [None]
+    /// for the root of the index and for nodes which aren't in the index.
+    pub fn parent_of(&self, id: AstNodeId) -> Option<AstNodeId> {
+        self.nodes.get(&id)?.parent
+    }
+
+    /// Get the children of the node with the given [AstNodeId], in the order
+    /// that they appear in the tree.
+    pub fn children_of(&self, id: AstNodeId) -> &[AstNodeId] {
+        self.nodes.get(&id).map_or(&[], |node| node.children.as_slice())
+    }
+
+    /// Iterate over the ancestors of the node with the given [AstNodeId],
+    /// starting with its parent and ending with the root of the index.
+    pub fn ancestors(&self, id: AstNodeId) -> impl Iterator<Item = AstNodeId> + '_ {
+        std::iter::successors(self.parent_of(id), |&ancestor| self.parent_of(ancestor))
+    }
+
+    /// Find the innermost node whose [Span] covers the given [Span]. For a
+    /// single byte offset, a [Span] with a size of one should be used. If
+    /// none of the nodes cover the [Span], then [None] is returned.
+    pub fn node_at_offset(&self, span: Span) -> Option<AstNodeId> {
+        let covers = |id: &AstNodeId| {
+            self.span_of(*id)
+                .map_or(false, |node| node.start() <= span.start() && span.end() <= node.end())
+        };
+
+        let mut current = Some(self.root).filter(covers)?;
+
+        // The spans of children are within the span of their parent, so the
+        // innermost node can be found by descending into the child that covers
+        // the span until there are no more children that do.
+        while let Some(child) = self.children_of(current).iter().copied().find(covers) {
+            current = child;
+        }
+
+        Some(current)
+    }
+}
+
+/// Visitor which records all of the nodes of a tree into an [AstIndex].
+#[derive(Default)]
+struct IndexBuilder {
+    /// The nodes that have been recorded.
+    nodes: HashMap<AstNodeId, IndexedNode>,
+    /// The nodes that are currently being visited, the last node is the parent
+    /// of any nodes that are entered.
+    stack: Vec<AstNodeId>,
+}
+
+impl IndexBuilder {
+    /// Record a node, and make it the parent of any nodes that are entered
+    /// until [IndexBuilder::exit] is called. Returns whether the node was
+    /// pushed onto the stack, which isn't the case for an inner node which
+    /// shares the [AstNodeId] of the node that is being visited.
+    fn enter(&mut self, id: AstNodeId, span: Span, kind: &'static str) -> bool {
+        if self.stack.last() == Some(&id) {
+            self.nodes.get_mut(&id).unwrap().kind = kind;
+            return false;
+        }
+
+        let parent = self.stack.last().copied();
+
+        // @@Note: cloned nodes share an id, so only the first occurrence of a
+        // node is recorded.
+        if !self.nodes.contains_key(&id) {
+            if let Some(parent) = parent {
+                self.nodes.get_mut(&parent).unwrap().children.push(id);
+            }
+
+            self.nodes.insert(id, IndexedNode { span, kind, parent, children: vec![] });
+        }
+
+        self.stack.push(id);
+        true
+    }
+
+    /// Stop visiting the node that was most recently entered.
+    fn exit(&mut self, entered: bool) {
+        if entered {
+            self.stack.pop();
+        }
+    }
+
+    fn finish(self, root: AstNodeId) -> AstIndex {
+        AstIndex { root, nodes: self.nodes }
+    }
+}
+
+/// Implement the visit method for each of the given nodes, each method records
+/// the node and then walks the children of the node if there is a walk
+/// function for it.
+macro_rules! index_nodes {
+    ($($visit:ident: $ret:ident, $node:ident $(=> $walk:ident)?;)*) => {
+        $(
+            type $ret = ();
+            fn $visit(
+                &mut self,
+                _ctx: &Self::Ctx,
+                node: ast::AstNodeRef<ast::$node>,
+            ) -> Result<Self::$ret, Self::Error> {
+                let entered = self.enter(node.id(), node.span(), stringify!($node));
+                $(walk::$walk(self, _ctx, node)?;)?
+                self.exit(entered);
+                Ok(())
+            }
+        )*
+    };
+}
+
+impl AstVisitor for IndexBuilder {
+    type Ctx = ();
+
+    type CollectionContainer<T> = Vec<T>;
+
+    fn try_collect_items<T, E, I: Iterator<Item = Result<T, E>>>(
+        _: &Self::Ctx,
+        items: I,
+    ) -> Result<Self::CollectionContainer<T>, E> {
+        items.collect()
+    }
+
+    type Error = Infallible;
+
+    type AccessKindRet = ();
+    fn visit_access_kind(
+        &mut self,
+        _: &Self::Ctx,
+        _: ast::AccessKind,
+    ) -> Result<Self::AccessKindRet, Self::Error> {
+        Ok(())
+    }
+
+    index_nodes! {
+            visit_name: NameRet, Name;
+            visit_lit: LitRet, Lit => walk_lit;
+            visit_map_lit: MapLitRet, MapLit => walk_map_lit;
+            visit_map_lit_entry: MapLitEntryRet, MapLitEntry => walk_map_lit_entry;
+            visit_list_lit: ListLitRet, ListLit => walk_list_lit;
+            visit_set_lit: SetLitRet, SetLit => walk_set_lit;
+            visit_tuple_lit_entry: TupleLitEntryRet, TupleLitEntry => walk_tuple_lit_entry;
+            visit_tuple_lit: TupleLitRet, TupleLit => walk_tuple_lit;
+            visit_str_lit: StrLitRet, StrLit;
+            visit_raw_str_lit: RawStrLitRet, RawStrLit;
+            visit_byte_str_lit: ByteStrLitRet, ByteStrLit;
+            visit_multi_line_str_lit: MultiLineStrLitRet, MultiLineStrLit;
+            visit_interpolated_str_lit: InterpolatedStrLitRet, InterpolatedStrLit => walk_interpolated_str_lit;
+            visit_char_lit: CharLitRet, CharLit;
+            visit_float_lit: FloatLitRet, FloatLit;
+            visit_bool_lit: BoolLitRet, BoolLit;
+            visit_int_lit: IntLitRet, IntLit;
+            visit_binary_operator: BinaryOperatorRet, BinOp;
+            visit_unary_operator: UnaryOperatorRet, UnOp;
+            visit_expr: ExprRet, Expr => walk_expr;
+            visit_variable_expr: VariableExprRet, VariableExpr => walk_variable_expr;
+            visit_directive_expr: DirectiveExprRet, DirectiveExpr => walk_directive_expr;
+            visit_constructor_call_arg: ConstructorCallArgRet, ConstructorCallArg => walk_constructor_call_arg;
+            visit_constructor_call_args: ConstructorCallArgsRet, ConstructorCallArgs => walk_constructor_call_args;
+            visit_constructor_call_expr: ConstructorCallExprRet, ConstructorCallExpr => walk_constructor_call_expr;
+            visit_access_expr: AccessExprRet, AccessExpr => walk_access_expr;
+            visit_ref_expr: RefExprRet, RefExpr => walk_ref_expr;
+            visit_deref_expr: DerefExprRet, DerefExpr => walk_deref_expr;
+            visit_unsafe_expr: UnsafeExprRet, UnsafeExpr => walk_unsafe_expr;
+            visit_lit_expr: LitExprRet, LitExpr => walk_lit_expr;
+            visit_cast_expr: CastExprRet, CastExpr => walk_cast_expr;
+            visit_ty_expr: TyExprRet, TyExpr => walk_ty_expr;
+            visit_block_expr: BlockExprRet, BlockExpr => walk_block_expr;
+            visit_import: ImportRet, Import;
+            visit_import_expr: ImportExprRet, ImportExpr => walk_import_expr;
+            visit_ty: TyRet, Ty => walk_ty;
+            visit_tuple_ty: TupleTyRet, TupleTy => walk_tuple_ty;
+            visit_list_ty: ListTyRet, ListTy => walk_list_ty;
+            visit_set_ty: SetTyRet, SetTy => walk_set_ty;
+            visit_map_ty: MapTyRet, MapTy => walk_map_ty;
+            visit_ty_arg: TyArgRet, TyArg => walk_ty_arg;
+            visit_fn_ty: FnTyRet, FnTy => walk_fn_ty;
+            visit_ty_fn_ty: TyFnRet, TyFn => walk_ty_fn;
+            visit_ty_fn_call: TyFnCallRet, TyFnCall => walk_ty_fn_call;
+            visit_named_ty: NamedTyRet, NamedTy => walk_named_ty;
+            visit_access_ty: AccessTyRet, AccessTy => walk_access_ty;
+            visit_ref_ty: RefTyRet, RefTy => walk_ref_ty;
+            visit_merge_ty: MergeTyRet, MergeTy => walk_merge_ty;
+            visit_union_ty: UnionTyRet, UnionTy => walk_union_ty;
+            visit_ty_fn_def: TyFnDefRet, TyFnDef => walk_ty_fn_def;
+            visit_fn_def: FnDefRet, FnDef => walk_fn_def;
+            visit_param: ParamRet, Param => walk_param;
+            visit_block: BlockRet, Block => walk_block;
+            visit_match_case: MatchCaseRet, MatchCase => walk_match_case;
+            visit_match_block: MatchBlockRet, MatchBlock => walk_match_block;
+            visit_loop_block: LoopBlockRet, LoopBlock => walk_loop_block;
+            visit_for_loop_block: ForLoopBlockRet, ForLoopBlock => walk_for_loop_block;
+            visit_while_loop_block: WhileLoopBlockRet, WhileLoopBlock => walk_while_loop_block;
+            visit_mod_block: ModBlockRet, ModBlock => walk_mod_block;
+            visit_impl_block: ImplBlockRet, ImplBlock => walk_impl_block;
+            visit_if_clause: IfClauseRet, IfClause => walk_if_clause;
+            visit_if_block: IfBlockRet, IfBlock => walk_if_block;
+            visit_body_block: BodyBlockRet, BodyBlock => walk_body_block;
+            visit_return_statement: ReturnStatementRet, ReturnStatement => walk_return_statement;
+            visit_break_statement: BreakStatementRet, BreakStatement;
+            visit_continue_statement: ContinueStatementRet, ContinueStatement;
+            visit_visibility_modifier: VisibilityRet, Visibility;
+            visit_mutability_modifier: MutabilityRet, Mutability;
+            visit_ref_kind: RefKindRet, RefKind;
+            visit_attribute: AttributeRet, Attribute => walk_attribute;
+            visit_declaration: DeclarationRet, Declaration => walk_declaration;
+            visit_merge_declaration: MergeDeclarationRet, MergeDeclaration => walk_merge_declaration;
+            visit_assign_expr: AssignExprRet, AssignExpr => walk_assign_expr;
+            visit_assign_op_expr: AssignOpExprRet, AssignOpExpr => walk_assign_op_statement;
+            visit_binary_expr: BinaryExprRet, BinaryExpr => walk_binary_expr;
+            visit_unary_expr: UnaryExprRet, UnaryExpr => walk_unary_expr;
+            visit_index_expr: IndexExprRet, IndexExpr => walk_index_expr;
+            visit_range_expr: RangeExprRet, RangeExpr => walk_range_expr;
+            visit_propagate_expr: PropagateExprRet, PropagateExpr => walk_propagate_expr;
+            visit_err_expr: ErrExprRet, ErrExpr;
+            visit_struct_def: StructDefRet, StructDef => walk_struct_def;
+            visit_enum_def_entry: EnumDefEntryRet, EnumDefEntry => walk_enum_def_entry;
+            visit_enum_def: EnumDefRet, EnumDef => walk_enum_def;
+            visit_trait_def: TraitDefRet, TraitDef => walk_trait_def;
+            visit_trait_impl: TraitImplRet, TraitImpl => walk_trait_impl;
+            visit_pat: PatRet, Pat => walk_pat;
+            visit_access_pat: AccessPatRet, AccessPat => walk_access_pat;
+            visit_constructor_pat: ConstructorPatRet, ConstructorPat => walk_constructor_pat;
+            visit_tuple_pat_entry: TuplePatEntryRet, TuplePatEntry => walk_tuple_pat_entry;
+            visit_tuple_pat: TuplePatRet, TuplePat => walk_tuple_pat;
+            visit_list_pat: ListPatRet, ListPat => walk_list_pat;
+            visit_spread_pat: SpreadPatRet, SpreadPat => walk_spread_pat;
+            visit_range_pat: RangePatRet, RangePat => walk_range_pat;
+            visit_err_pat: ErrPatRet, ErrPat;
+            visit_str_lit_pat: StrLitPatRet, StrLitPat;
+            visit_char_lit_pat: CharLitPatRet, CharLitPat;
+            visit_int_lit_pat: IntLitPatRet, IntLitPat;
+            visit_float_lit_pat: FloatLitPatRet, FloatLitPat;
+            visit_bool_lit_pat: BoolLitPatRet, BoolLitPat;
+            visit_lit_pat: LitPatRet, LitPat => walk_lit_pat;
+            visit_or_pat: OrPatRet, OrPat => walk_or_pat;
+            visit_if_pat: IfPatRet, IfPat => walk_if_pat;
+            visit_binding_pat: BindingPatRet, BindingPat => walk_binding_pat;
+            visit_ignore_pat: IgnorePatRet, IgnorePat;
+            visit_module_pat_entry: ModulePatEntryRet, ModulePatEntry => walk_module_pat_entry;
+            visit_module_pat: ModulePatRet, ModulePat => walk_module_pat;
+            visit_module: ModuleRet, Module => walk_module;
+    }
+}
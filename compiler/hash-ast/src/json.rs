@@ -0,0 +1,43 @@
+//! JSON representation of the AST, this allows tools that aren't written in
+//! Rust to consume and produce the trees that the compiler works with.
+//!
+//! Each [AstNode] is represented by an object with the `id` and the `span` of
+//! the node, and its `body`. Enums are represented by an object with a single
+//! field that is named after the variant, for example the expression `x` is
+//! represented as:
+//!
+//! ```json
+//! {
+//!   "id": 2,
+//!   "span": { "start": 0, "end": 1 },
+//!   "body": {
+//!     "kind": {
+//!       "Variable": {
+//!         "name": { "id": 1, "span": { "start": 0, "end": 1 }, "body": { "ident": "x" } }
+//!       }
+//!     }
+//!   }
+//! }
+//! ```
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::ast::AstNode;
+
+/// Convert a node and all of its children into JSON, this is intended to be
+/// used with an [crate::ast::Module] or a [crate::ast::BodyBlock].
+pub fn to_json<T: Serialize>(node: &AstNode<T>) -> Value {
+    serde_json::to_value(node).expect("AST nodes are always representable as JSON")
+}
+
+/// Rebuild a node and all of its children from the JSON that was produced by
+/// [to_json]. The rebuilt nodes keep the spans that are specified in the
+/// JSON, but they are given new ids, so that they can't clash with nodes that
+/// already exist.
+///
+/// The JSON is read with the default recursion limit of [serde_json], so a
+/// tree that is nested too deeply is rejected with an error instead of
+/// overflowing the stack.
+pub fn from_json<T: DeserializeOwned>(json: &str) -> Result<AstNode<T>, serde_json::Error> {
+    serde_json::from_str(json)
+}
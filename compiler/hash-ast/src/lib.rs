@@ -1,7 +1,11 @@
 //! Hash Compiler AST library file
 
-#![feature(generic_associated_types, box_into_inner, iter_intersperse)]
+#![feature(generic_associated_types, box_into_inner, iter_intersperse, once_cell)]
 
 pub mod ast;
+pub mod fold;
+pub mod index;
+pub mod json;
+pub mod printer;
 pub mod tree;
 pub mod visitor;
@@ -0,0 +1,2462 @@
+//! Hash source pretty-printer. The [AstPrinter] converts an AST back into
+//! source code with a canonical layout, breaking lines that don't fit within
+//! the configured width. If the original source of the AST is provided, then
+//! the comments and the exact spelling of literals are preserved.
+
+use std::{collections::BTreeMap, convert::Infallible, str::FromStr};
+
+use hash_source::location::Span;
+use hash_utils::doc_writing::{Doc, DocWriter, DocWriterConfig};
+
+use crate::{
+    ast,
+    visitor::{walk, AstVisitor},
+};
+
+/// When trailing commas are added to comma separated lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrailingComma {
+    /// Always add a trailing comma.
+    Always,
+    /// Never add a trailing comma, unless the syntax requires it, e.g. `(a,)`.
+    Never,
+    /// Only add a trailing comma when the list is broken over multiple lines.
+    Vertical,
+}
+
+impl FromStr for TrailingComma {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Always" => Ok(TrailingComma::Always),
+            "Never" => Ok(TrailingComma::Never),
+            "Vertical" => Ok(TrailingComma::Vertical),
+            _ => Err(format!(
+                "unknown trailing comma style `{}`, expected one of `Always`, `Never` or `Vertical`",
+                s
+            )),
+        }
+    }
+}
+
+/// Options for the [AstPrinter], these follow the naming of the `rustfmt`
+/// options that they correspond to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrinterConfig {
+    /// The maximum width of a line.
+    pub max_width: usize,
+    /// The number of spaces that a single level of indentation takes.
+    pub tab_spaces: usize,
+    /// Whether to indent using tabs rather than spaces.
+    pub hard_tabs: bool,
+    /// When trailing commas are added to comma separated lists.
+    pub trailing_comma: TrailingComma,
+}
+
+impl Default for PrinterConfig {
+    fn default() -> Self {
+        Self {
+            max_width: 100,
+            tab_spaces: 4,
+            hard_tabs: false,
+            trailing_comma: TrailingComma::Vertical,
+        }
+    }
+}
+
+impl PrinterConfig {
+    fn doc_writer_config(&self) -> DocWriterConfig {
+        DocWriterConfig {
+            max_width: self.max_width,
+            indent_width: self.tab_spaces,
+            hard_tabs: self.hard_tabs,
+        }
+    }
+}
+
+/// Whether a particular comma separated list can have a trailing comma.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Trailing {
+    /// The trailing comma follows [PrinterConfig::trailing_comma].
+    Config,
+    /// The list must have a trailing comma, e.g. the singleton tuple `(a,)`.
+    Required,
+    /// The list can't have a trailing comma, e.g. type arguments.
+    Forbidden,
+}
+
+/// The position that an expression is printed in, which decides whether the
+/// expression has to be wrapped in parentheses in order to be parsed back into
+/// the same tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Position {
+    /// A statement of a module or a block.
+    Statement,
+    /// An expression that is parsed with a minimum precedence of `min_prec`,
+    /// and if `follower` is set, that is followed by a binary operator with
+    /// the given left binding power.
+    Operand { min_prec: u8, follower: Option<u8> },
+    /// An element of a comma separated list, e.g. the argument of a call.
+    Element,
+    /// An expression that is followed by a block, e.g. the condition of an
+    /// `if` block.
+    Condition,
+    /// The operand of a unary operator, which must be a singular expression.
+    Unary,
+    /// The subject of a call, access, index or propagation, which must be a
+    /// singular expression.
+    Postfix,
+}
+
+/// The position of an expression that is parsed on its own, e.g. the value of
+/// a declaration.
+const FREE: Position = Position::Operand { min_prec: 0, follower: None };
+
+/// The position that a type is printed in, see [Position].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TyPosition {
+    Free,
+    Operand {
+        min_prec: u8,
+        follower: Option<u8>,
+    },
+    /// The subject of a type function call or access.
+    Postfix,
+    /// The type of a closure parameter, which can't contain a union or merge
+    /// type since the `|` would end the parameter list.
+    ClosureParam,
+}
+
+/// The position that a pattern is printed in, see [Position].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PatPosition {
+    /// A full pattern, e.g. the pattern of a match case.
+    Free,
+    /// An element of a comma separated list of patterns.
+    Element,
+    /// A singular pattern, e.g. the pattern of a `for` loop, or the subject of
+    /// a constructor pattern.
+    Singular,
+    /// The pattern of a declaration, which must begin like a binding.
+    Declaration,
+    /// A variant of an `or` pattern which isn't the last one.
+    Variant,
+    /// The last variant of an `or` pattern.
+    LastVariant,
+}
+
+/// Extract the value of a result that can't fail.
+fn infallible<T>(result: Result<T, Infallible>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(err) => match err {},
+    }
+}
+
+fn parens(doc: Doc) -> Doc {
+    Doc::concat(["(".into(), doc, ")".into()])
+}
+
+/// Get the expression that is the operand of a prefix operator or modifier.
+fn prefix_operand(expr: &ast::Expr) -> Option<&ast::Expr> {
+    match expr.kind() {
+        ast::ExprKind::UnaryExpr(unary) => Some(unary.expr.body()),
+        ast::ExprKind::Ref(reference) => Some(reference.inner_expr.body()),
+        ast::ExprKind::Deref(ast::DerefExpr(inner)) => Some(inner.body()),
+        ast::ExprKind::Unsafe(ast::UnsafeExpr(inner)) => Some(inner.body()),
+        ast::ExprKind::Directive(directive) => Some(directive.subject.body()),
+        _ => None,
+    }
+}
+
+/// Check whether a type function call can be written as a postfix of an
+/// expression, e.g. `foo<T>`, rather than in the `type Foo<T>` form.
+fn is_postfix_ty_fn_call(ty: &ast::Ty) -> bool {
+    match ty {
+        ast::Ty::TyFnCall(call) => match call.subject.kind() {
+            ast::ExprKind::Ty(ast::TyExpr(inner)) => is_postfix_ty_fn_call(inner.body()),
+            _ => true,
+        },
+        _ => false,
+    }
+}
+
+fn needs_parens(expr: &ast::Expr, position: Position) -> bool {
+    use ast::ExprKind;
+
+    match expr.kind() {
+        ExprKind::Declaration(_)
+        | ExprKind::Assign(_)
+        | ExprKind::AssignOp(_)
+        | ExprKind::MergeDeclaration(_) => position != Position::Statement,
+        // These greedily consume all of the following tokens.
+        ExprKind::FnDef(_) | ExprKind::TyFnDef(_) | ExprKind::Return(_) => {
+            !matches!(position, Position::Statement | Position::Element) && position != FREE
+        }
+        ExprKind::Range(_) => {
+            !matches!(position, Position::Statement | Position::Element | Position::Condition)
+                && position != FREE
+        }
+        ExprKind::Block(_) => position == Position::Condition,
+        ExprKind::BinaryExpr(binary) => match position {
+            Position::Operand { min_prec, follower } => {
+                let (l_prec, _) = binary.operator.infix_binding_power();
+                l_prec < min_prec || follower.map_or(false, |f| absorbs_operator(expr, f))
+            }
+            Position::Element => ends_ambiguously(expr),
+            Position::Unary | Position::Postfix => true,
+            Position::Statement | Position::Condition => false,
+        },
+        // @@Note: after a cast, the minimum precedence is raised above the precedence
+        // of `as`, and any binary operator that follows would be parsed as part of the
+        // type.
+        ExprKind::Cast(_) => match position {
+            Position::Operand { min_prec, follower } => min_prec > 21 || follower.is_some(),
+            Position::Unary | Position::Postfix => true,
+            _ => false,
+        },
+        ExprKind::Ty(ty) if !is_postfix_ty_fn_call(ty.0.body()) => {
+            matches!(position, Position::Operand { follower: Some(_), .. } | Position::Postfix)
+        }
+        ExprKind::UnaryExpr(_)
+        | ExprKind::Ref(_)
+        | ExprKind::Deref(_)
+        | ExprKind::Unsafe(_)
+        | ExprKind::Directive(_) => match position {
+            Position::Postfix => true,
+            Position::Operand { follower: Some(follower), .. } => absorbs_operator(expr, follower),
+            Position::Element => ends_ambiguously(expr),
+            _ => false,
+        },
+        ExprKind::LitExpr(lit) => {
+            // `1.foo` would be lexed as a float
+            position == Position::Postfix
+                && matches!(lit.0.body(), ast::Lit::Int(_) | ast::Lit::Float(_))
+        }
+        _ => position == Position::Element && ends_ambiguously(expr),
+    }
+}
+
+/// Whether the right hand side of the binary expression has to be wrapped in
+/// parentheses, beyond what [needs_parens] requires.
+fn binary_rhs_needs_extra_parens(binary: &ast::BinaryExpr) -> bool {
+    match binary.operator.body() {
+        // `a & &b` would be lexed as `a && b`
+        ast::BinOp::BitAnd => leftmost_is_ref(binary.rhs.body()),
+        // `a < b >> c` would be parsed as the type function call `a<b>`
+        ast::BinOp::Lt => contains_gt(binary.rhs.body()),
+        _ => false,
+    }
+}
+
+fn binary_rhs_needs_parens(binary: &ast::BinaryExpr, follower: Option<u8>) -> bool {
+    let (_, r_prec) = binary.operator.infix_binding_power();
+
+    needs_parens(binary.rhs.body(), Position::Operand { min_prec: r_prec, follower })
+        || binary_rhs_needs_extra_parens(binary)
+}
+
+/// Whether the expression would absorb a binary operator with the left binding
+/// power `follower` that is printed after it.
+fn absorbs_operator(expr: &ast::Expr, follower: u8) -> bool {
+    use ast::ExprKind;
+
+    match expr.kind() {
+        ExprKind::BinaryExpr(binary) => {
+            let operator = binary.operator.body();
+            let (_, r_prec) = operator.infix_binding_power();
+
+            // @@Note: `a < b > c` is parsed as a type function call.
+            r_prec <= follower
+                || (*operator == ast::BinOp::Lt && follower == 7)
+                || (!binary_rhs_needs_parens(binary, None)
+                    && absorbs_operator(binary.rhs.body(), follower))
+        }
+        ExprKind::Cast(_)
+        | ExprKind::Range(_)
+        | ExprKind::FnDef(_)
+        | ExprKind::TyFnDef(_)
+        | ExprKind::Return(_) => true,
+        ExprKind::Ty(ty) => !is_postfix_ty_fn_call(ty.0.body()),
+        _ => match prefix_operand(expr) {
+            Some(operand) => {
+                !needs_parens(operand, Position::Unary) && absorbs_operator(operand, follower)
+            }
+            None => false,
+        },
+    }
+}
+
+/// Whether the expression can't be followed by a comma, because it ends with a
+/// `<` comparison that would be parsed as the beginning of type arguments, as
+/// in `f(a < b, c > d)`, or because it ends with a `return` that has no value.
+fn ends_ambiguously(expr: &ast::Expr) -> bool {
+    use ast::ExprKind;
+
+    match expr.kind() {
+        ExprKind::BinaryExpr(binary) => {
+            *binary.operator.body() == ast::BinOp::Lt
+                || (!binary_rhs_needs_parens(binary, None) && ends_ambiguously(binary.rhs.body()))
+        }
+        ExprKind::FnDef(def) => ends_ambiguously(def.fn_body.body()),
+        ExprKind::TyFnDef(def) => ends_ambiguously(def.body.body()),
+        ExprKind::Return(ast::ReturnStatement(value)) => {
+            value.as_ref().map_or(true, |value| ends_ambiguously(value.body()))
+        }
+        ExprKind::Range(range) => {
+            let position = Position::Operand { min_prec: 1, follower: None };
+            !needs_parens(range.hi.body(), position) && ends_ambiguously(range.hi.body())
+        }
+        _ => match prefix_operand(expr) {
+            Some(operand) => !needs_parens(operand, Position::Unary) && ends_ambiguously(operand),
+            None => false,
+        },
+    }
+}
+
+/// Whether the first token of the expression is a `&`.
+fn leftmost_is_ref(expr: &ast::Expr) -> bool {
+    use ast::ExprKind;
+
+    match expr.kind() {
+        ExprKind::Ref(_) => true,
+        ExprKind::BinaryExpr(binary) => leftmost_is_ref(binary.lhs.body()),
+        ExprKind::Cast(cast) => leftmost_is_ref(cast.expr.body()),
+        ExprKind::Range(range) => leftmost_is_ref(range.lo.body()),
+        ExprKind::Access(access) => leftmost_is_ref(access.subject.body()),
+        ExprKind::ConstructorCall(call) => leftmost_is_ref(call.subject.body()),
+        ExprKind::Index(index) => leftmost_is_ref(index.subject.body()),
+        ExprKind::Propagate(propagate) => leftmost_is_ref(propagate.subject.body()),
+        _ => false,
+    }
+}
+
+/// Whether the expression contains a `>`, `>=` or `>>` operator that isn't
+/// enclosed within brackets.
+fn contains_gt(expr: &ast::Expr) -> bool {
+    use ast::ExprKind;
+
+    match expr.kind() {
+        ExprKind::BinaryExpr(binary) => {
+            matches!(binary.operator.body(), ast::BinOp::Gt | ast::BinOp::GtEq | ast::BinOp::Shr)
+                || contains_gt(binary.lhs.body())
+                || contains_gt(binary.rhs.body())
+        }
+        ExprKind::Cast(cast) => contains_gt(cast.expr.body()),
+        ExprKind::Range(range) => contains_gt(range.lo.body()) || contains_gt(range.hi.body()),
+        ExprKind::Access(access) => contains_gt(access.subject.body()),
+        ExprKind::ConstructorCall(call) => contains_gt(call.subject.body()),
+        ExprKind::Index(index) => contains_gt(index.subject.body()),
+        ExprKind::Propagate(propagate) => contains_gt(propagate.subject.body()),
+        _ => prefix_operand(expr).map_or(false, contains_gt),
+    }
+}
+
+fn ty_operator(ty: &ast::Ty) -> Option<(ast::BinTyOp, &ast::AstNode<ast::Ty>)> {
+    match ty {
+        ast::Ty::Union(union) => Some((ast::BinTyOp::Union, &union.rhs)),
+        ast::Ty::Merge(merge) => Some((ast::BinTyOp::Merge, &merge.rhs)),
+        _ => None,
+    }
+}
+
+fn ty_needs_parens(ty: &ast::Ty, position: TyPosition) -> bool {
+    match ty {
+        ast::Ty::Union(_) | ast::Ty::Merge(_) => match position {
+            TyPosition::Free => false,
+            TyPosition::Operand { min_prec, follower } => {
+                let (operator, _) = ty_operator(ty).unwrap();
+                let (l_prec, _) = operator.infix_binding_power();
+
+                l_prec < min_prec || follower.map_or(false, |f| ty_absorbs_operator(ty, f))
+            }
+            TyPosition::Postfix | TyPosition::ClosureParam => true,
+        },
+        // These end with a type that is parsed with the lowest precedence.
+        ast::Ty::Ref(_) | ast::Ty::Fn(_) | ast::Ty::TyFn(_) => matches!(
+            position,
+            TyPosition::Operand { follower: Some(_), .. }
+                | TyPosition::Postfix
+                | TyPosition::ClosureParam
+        ),
+        _ => false,
+    }
+}
+
+/// Whether the type would absorb a type operator with the left binding power
+/// `follower` that is printed after it, see [absorbs_operator].
+fn ty_absorbs_operator(ty: &ast::Ty, follower: u8) -> bool {
+    match ty {
+        ast::Ty::Ref(_) | ast::Ty::Fn(_) | ast::Ty::TyFn(_) => true,
+        _ => match ty_operator(ty) {
+            Some((operator, rhs)) => {
+                let (_, r_prec) = operator.infix_binding_power();
+                let position = TyPosition::Operand { min_prec: r_prec, follower: None };
+
+                r_prec <= follower
+                    || (!ty_needs_parens(rhs.body(), position)
+                        && ty_absorbs_operator(rhs.body(), follower))
+            }
+            None => false,
+        },
+    }
+}
+
+fn pat_needs_parens(pat: &ast::Pat, position: PatPosition) -> bool {
+    match pat {
+        ast::Pat::Or(_) => !matches!(position, PatPosition::Free | PatPosition::Element),
+        // The condition of the guard is parsed greedily, so it would consume any
+        // following `|`.
+        ast::Pat::If(if_pat) => match position {
+            PatPosition::Free | PatPosition::LastVariant => false,
+            PatPosition::Element => ends_ambiguously(if_pat.condition.body()),
+            _ => true,
+        },
+        // A declaration is only detected if it begins like a binding
+        ast::Pat::Spread(_) | ast::Pat::Range(_) => position == PatPosition::Declaration,
+        _ => false,
+    }
+}
+
+/// Escape a character so that it can be printed within a string or character
+/// literal delimited by `quote`.
+fn escape_char(ch: char, quote: char, out: &mut String) {
+    match ch {
+        '\\' => out.push_str("\\\\"),
+        '\n' => out.push_str("\\n"),
+        '\t' => out.push_str("\\t"),
+        '\r' => out.push_str("\\r"),
+        '\0' => out.push_str("\\0"),
+        ch if ch == quote => {
+            out.push('\\');
+            out.push(ch);
+        }
+        ch if ch.is_control() => out.push_str(&format!("\\u{{{:x}}}", ch as u32)),
+        ch => out.push(ch),
+    }
+}
+
+/// Escape the contents of a string literal. If the literal is `interpolated`,
+/// then the braces that would otherwise begin an interpolation are escaped.
+fn escape_str(contents: &str, interpolated: bool) -> String {
+    let mut out = String::with_capacity(contents.len());
+
+    for ch in contents.chars() {
+        match ch {
+            '{' | '}' if interpolated => {
+                out.push('\\');
+                out.push(ch);
+            }
+            ch => escape_char(ch, '"', &mut out),
+        }
+    }
+
+    out
+}
+
+/// Struct implementing [crate::visitor::AstVisitor], for the purpose of
+/// printing the AST as formatted source code.
+pub struct AstPrinter<'s> {
+    config: PrinterConfig,
+    /// The source that the AST was parsed from, if any.
+    source: Option<&'s str>,
+    /// The spans of the comments within the source that haven't been printed
+    /// yet, keyed by their start.
+    comments: BTreeMap<usize, Span>,
+    /// The position that the expression which is currently visited is printed
+    /// in.
+    position: Position,
+    /// The position that the type which is currently visited is printed in.
+    ty_position: TyPosition,
+}
+
+impl<'s> AstPrinter<'s> {
+    /// Create a new [AstPrinter] that prints the AST without any comments.
+    pub fn new(config: PrinterConfig) -> Self {
+        Self {
+            config,
+            source: None,
+            comments: BTreeMap::new(),
+            position: Position::Statement,
+            ty_position: TyPosition::Free,
+        }
+    }
+
+    /// Provide the source that the AST was parsed from, along with the spans of
+    /// the comments within it. Comments are attached to the closest
+    /// statement or list item, and literals are printed as they were
+    /// written.
+    pub fn with_source(
+        mut self,
+        source: &'s str,
+        comments: impl IntoIterator<Item = Span>,
+    ) -> Self {
+        self.source = Some(source);
+        self.comments = comments.into_iter().map(|span| (span.start(), span)).collect();
+        self
+    }
+
+    /// Print the given [ast::Module] as formatted source.
+    pub fn print_module(&mut self, node: ast::AstNodeRef<ast::Module>) -> String {
+        let doc = infallible(self.visit_module(&(), node));
+        DocWriter::new_with_config(&doc, self.config.doc_writer_config()).to_string()
+    }
+
+    /// Print an expression, wrapping it in parentheses if the position that it
+    /// is printed in requires it.
+    fn expr(&mut self, node: ast::AstNodeRef<ast::Expr>, position: Position) -> Doc {
+        let wrap = needs_parens(node.body(), position);
+        self.position = if wrap { FREE } else { position };
+
+        let doc = infallible(self.visit_expr(&(), node));
+        if wrap {
+            parens(doc)
+        } else {
+            doc
+        }
+    }
+
+    /// Print a type, wrapping it in parentheses if the position that it is
+    /// printed in requires it. The trailing comma is needed since `(T)` is a
+    /// tuple type.
+    fn ty(&mut self, node: ast::AstNodeRef<ast::Ty>, position: TyPosition) -> Doc {
+        let wrap = ty_needs_parens(node.body(), position);
+        self.ty_position = if wrap { TyPosition::Free } else { position };
+
+        let doc = infallible(self.visit_ty(&(), node));
+        if wrap {
+            Doc::concat(["(".into(), doc, ",)".into()])
+        } else {
+            doc
+        }
+    }
+
+    /// Print a pattern, wrapping it in parentheses if the position that it is
+    /// printed in requires it.
+    fn pat(&mut self, node: ast::AstNodeRef<ast::Pat>, position: PatPosition) -> Doc {
+        let doc = infallible(self.visit_pat(&(), node));
+
+        if pat_needs_parens(node.body(), position) {
+            parens(doc)
+        } else {
+            doc
+        }
+    }
+
+    fn name(&mut self, node: &ast::AstNode<ast::Name>) -> Doc {
+        infallible(self.visit_name(&(), node.ast_ref()))
+    }
+
+    /// Print the statements of the given block, enclosed in braces.
+    fn body_block(&mut self, node: ast::AstNodeRef<ast::BodyBlock>) -> Doc {
+        let span = node.span();
+
+        if node.statements.is_empty() && !self.has_comments(span.start(), span.end()) {
+            return match &node.expr {
+                Some(expr) => Doc::concat([
+                    "{".into(),
+                    Doc::concat([Doc::Line, self.expr(expr.ast_ref(), Position::Statement)]).nest(),
+                    Doc::Line,
+                    "}".into(),
+                ])
+                .group(),
+                None => "{}".into(),
+            };
+        }
+
+        let mut items = vec![];
+
+        for statement in node.statements.iter() {
+            let doc = self.expr(statement.ast_ref(), Position::Statement);
+            items.push((statement.span(), doc.append(";".into())));
+        }
+
+        if let Some(expr) = &node.expr {
+            items.push((expr.span(), self.expr(expr.ast_ref(), Position::Statement)));
+        }
+
+        self.braced(items, span.start(), span.end())
+    }
+
+    /// Print a sequence of statements, each terminated by a `;`.
+    fn statements(&mut self, statements: &ast::AstNodes<ast::Expr>) -> Vec<(Span, Doc)> {
+        let mut items = vec![];
+
+        for statement in statements.iter() {
+            let doc = self.expr(statement.ast_ref(), Position::Statement);
+            items.push((statement.span(), doc.append(";".into())));
+        }
+
+        items
+    }
+
+    /// Print the given lines within braces, along with any comments between
+    /// `lo` and `hi`.
+    fn braced(&mut self, items: Vec<(Span, Doc)>, lo: usize, hi: usize) -> Doc {
+        let lines = self.lines(items, lo, hi);
+
+        if lines.is_nil() {
+            return "{}".into();
+        }
+
+        Doc::concat([
+            "{".into(),
+            Doc::concat([Doc::HardLine, lines]).nest(),
+            Doc::HardLine,
+            "}".into(),
+        ])
+    }
+
+    /// Print each of the items on a separate line. Comments between `lo` and
+    /// `hi` are printed on the line above the item that they precede or are
+    /// contained within, or after the item if they are on the same line, and
+    /// single blank lines between the items are preserved.
+    fn lines(&mut self, items: Vec<(Span, Doc)>, lo: usize, hi: usize) -> Doc {
+        let mut lines = vec![];
+        let mut cursor = lo;
+
+        for (span, doc) in items {
+            let mut elements: Vec<_> = self
+                .take_comments(cursor, span.end())
+                .into_iter()
+                .map(|comment| (comment, self.comment(comment)))
+                .collect();
+            elements.push((span, doc));
+
+            for (span, doc) in elements {
+                if !lines.is_empty()
+                    && cursor <= span.start()
+                    && self.has_blank_line(cursor, span.start())
+                {
+                    lines.push(Doc::nil());
+                }
+
+                lines.push(doc);
+                cursor = cursor.max(span.end());
+            }
+
+            if let Some(comment) = self.take_trailing_comment(cursor, hi) {
+                let line = lines.pop().unwrap();
+                lines.push(Doc::concat([line, " ".into(), self.comment(comment)]));
+                cursor = comment.end();
+            }
+        }
+
+        for comment in self.take_comments(cursor, hi) {
+            if !lines.is_empty() && self.has_blank_line(cursor, comment.start()) {
+                lines.push(Doc::nil());
+            }
+
+            lines.push(self.comment(comment));
+            cursor = comment.end();
+        }
+
+        Doc::join(lines, Doc::HardLine)
+    }
+
+    /// Print a comma separated list of items enclosed within the `open` and
+    /// `close` delimiters. The list is printed on a single line if it fits,
+    /// otherwise each item is printed on a separate line. Comments between
+    /// `lo` and `hi` are printed alongside the item that they are closest to.
+    fn comma_list(
+        &mut self,
+        (open, close): (&'static str, &'static str),
+        items: Vec<(Span, Doc)>,
+        (lo, hi): (usize, usize),
+        trailing: Trailing,
+        padded: bool,
+    ) -> Doc {
+        let mut contents = vec![];
+        let mut cursor = lo;
+        let mut force_break = false;
+        let count = items.len();
+
+        for (index, (span, doc)) in items.into_iter().enumerate() {
+            for comment in self.take_comments(cursor, span.end()) {
+                contents.push(self.comment(comment));
+                contents.push(Doc::HardLine);
+            }
+
+            contents.push(doc);
+            cursor = cursor.max(span.end());
+
+            let is_last = index + 1 == count;
+            contents.push(if is_last { self.trailing_comma(trailing) } else { ",".into() });
+
+            match self.take_trailing_comment(cursor, hi) {
+                Some(comment) => {
+                    contents.push(" ".into());
+                    contents.push(self.comment(comment));
+                    cursor = comment.end();
+
+                    if is_last {
+                        force_break = true;
+                    } else {
+                        contents.push(Doc::HardLine);
+                    }
+                }
+                None if !is_last => contents.push(Doc::Line),
+                None => {}
+            }
+        }
+
+        for comment in self.take_comments(cursor, hi) {
+            if !contents.is_empty() {
+                contents.push(Doc::HardLine);
+            }
+
+            contents.push(self.comment(comment));
+            force_break = true;
+        }
+
+        if contents.is_empty() {
+            return Doc::concat([open.into(), close.into()]);
+        }
+
+        let line = if padded { Doc::Line } else { Doc::SoftLine };
+        let closing_line = if force_break { Doc::HardLine } else { line.clone() };
+
+        Doc::concat([
+            open.into(),
+            Doc::concat([line, Doc::concat(contents)]).nest(),
+            closing_line,
+            close.into(),
+        ])
+        .group()
+    }
+
+    fn trailing_comma(&self, trailing: Trailing) -> Doc {
+        match (trailing, self.config.trailing_comma) {
+            (Trailing::Required, _) | (Trailing::Config, TrailingComma::Always) => ",".into(),
+            (Trailing::Forbidden, _) | (Trailing::Config, TrailingComma::Never) => Doc::nil(),
+            (Trailing::Config, TrailingComma::Vertical) => Doc::if_break(","),
+        }
+    }
+
+    /// Remove and return the comments that start between `lo` and `hi`.
+    fn take_comments(&mut self, lo: usize, hi: usize) -> Vec<Span> {
+        if lo >= hi {
+            return vec![];
+        }
+
+        let mut taken = self.comments.split_off(&lo);
+        self.comments.append(&mut taken.split_off(&hi));
+
+        taken.into_values().collect()
+    }
+
+    /// Remove and return the first comment after `lo` if it is on the same
+    /// line, only separated by whitespace, commas or semi-colons.
+    fn take_trailing_comment(&mut self, lo: usize, hi: usize) -> Option<Span> {
+        let source = self.source?;
+        let (&start, &span) = self.comments.range(lo..hi).next()?;
+
+        let gap = source.get(lo..start)?;
+        if !gap.chars().all(|ch| matches!(ch, ' ' | '\t' | ',' | ';')) {
+            return None;
+        }
+
+        self.comments.remove(&start);
+        Some(span)
+    }
+
+    fn has_comments(&self, lo: usize, hi: usize) -> bool {
+        lo < hi && self.comments.range(lo..hi).next().is_some()
+    }
+
+    /// Whether the source between `lo` and `hi` contains an empty line.
+    fn has_blank_line(&self, lo: usize, hi: usize) -> bool {
+        self.source
+            .and_then(|source| source.get(lo..hi))
+            .map_or(false, |gap| gap.matches('\n').count() >= 2)
+    }
+
+    fn comment(&self, span: Span) -> Doc {
+        let source = self.source.unwrap_or_default();
+        Doc::text(source[span.start()..span.end()].trim_end().to_owned())
+    }
+
+    /// Print the source of the given span as is, any comments within the span
+    /// are considered to be a part of it.
+    fn verbatim(&mut self, span: Span) -> Option<Doc> {
+        let text = self.source?.get(span.start()..span.end())?;
+        self.take_comments(span.start(), span.end());
+
+        Some(Doc::text(text.to_owned()))
+    }
+
+    /// Print the name, type and default value of a parameter.
+    fn param(&mut self, node: ast::AstNodeRef<ast::Param>, ty_position: TyPosition) -> Doc {
+        let mut doc = vec![self.name(&node.name)];
+
+        if let Some(ty) = &node.ty {
+            doc.push(": ".into());
+            doc.push(self.ty(ty.ast_ref(), ty_position));
+        }
+
+        if let Some(default) = &node.default {
+            doc.push(" = ".into());
+
+            // The defaults of type function parameters are types
+            doc.push(match (node.origin, default.kind()) {
+                (ast::ParamOrigin::TyFn, ast::ExprKind::Ty(ty)) => {
+                    self.ty(ty.0.ast_ref(), TyPosition::Free)
+                }
+                _ => self.expr(default.ast_ref(), Position::Element),
+            });
+        }
+
+        Doc::concat(doc)
+    }
+
+    /// Print the parameters of a function or a type function.
+    fn params(
+        &mut self,
+        delimiters: (&'static str, &'static str),
+        params: &ast::AstNodes<ast::Param>,
+        bounds: (usize, usize),
+        trailing: Trailing,
+        ty_position: TyPosition,
+    ) -> Doc {
+        let items = params
+            .iter()
+            .map(|param| (param.span(), self.param(param.ast_ref(), ty_position)))
+            .collect();
+
+        self.comma_list(delimiters, items, bounds, trailing, false)
+    }
+
+    /// Print the entries of a tuple or function type.
+    fn ty_entries(&mut self, entries: &ast::AstNodes<ast::TyArg>, bounds: (usize, usize)) -> Doc {
+        // `(T,)` is parsed as the type `T`
+        let trailing = match entries.nodes.as_slice() {
+            [entry] if entry.name.is_none() => Trailing::Forbidden,
+            _ => Trailing::Config,
+        };
+
+        let items = entries
+            .iter()
+            .map(|entry| {
+                let ty = self.ty(entry.ty.ast_ref(), TyPosition::Free);
+
+                let doc = match &entry.name {
+                    Some(name) => Doc::concat([self.name(name), ": ".into(), ty]),
+                    None => ty,
+                };
+
+                (entry.span(), doc)
+            })
+            .collect();
+
+        self.comma_list(("(", ")"), items, bounds, trailing, false)
+    }
+
+    /// Print the type arguments of a type function call.
+    fn ty_args(&mut self, args: &ast::AstNodes<ast::TyArg>, bounds: (usize, usize)) -> Doc {
+        let items = args
+            .iter()
+            .map(|arg| (arg.span(), infallible(self.visit_ty_arg(&(), arg.ast_ref()))))
+            .collect();
+
+        self.comma_list(("<", ">"), items, bounds, Trailing::Forbidden, false)
+    }
+
+    /// Print a list of expressions, e.g. the elements of a list literal.
+    fn exprs(
+        &mut self,
+        delimiters: (&'static str, &'static str),
+        exprs: &ast::AstNodes<ast::Expr>,
+        bounds: (usize, usize),
+        padded: bool,
+    ) -> Doc {
+        let items = exprs
+            .iter()
+            .map(|expr| (expr.span(), self.expr(expr.ast_ref(), Position::Element)))
+            .collect();
+
+        self.comma_list(delimiters, items, bounds, Trailing::Config, padded)
+    }
+
+    /// Print the fields of a tuple or constructor pattern.
+    fn tuple_pat_entries(
+        &mut self,
+        fields: &ast::AstNodes<ast::TuplePatEntry>,
+        bounds: (usize, usize),
+        trailing: Trailing,
+    ) -> Doc {
+        let items = fields
+            .iter()
+            .map(|field| {
+                (field.span(), infallible(self.visit_tuple_pat_entry(&(), field.ast_ref())))
+            })
+            .collect();
+
+        self.comma_list(("(", ")"), items, bounds, trailing, false)
+    }
+
+    /// Print a literal as it was written in the source, or using the given
+    /// function if there is no source.
+    fn lit(&mut self, span: Span, fallback: impl FnOnce() -> String) -> Doc {
+        match self.verbatim(span) {
+            Some(doc) => doc,
+            None => Doc::text(fallback()),
+        }
+    }
+}
+
+impl<'s> AstVisitor for AstPrinter<'s> {
+    type Ctx = ();
+
+    type CollectionContainer<T> = Vec<T>;
+
+    fn try_collect_items<T, E, I: Iterator<Item = Result<T, E>>>(
+        _: &Self::Ctx,
+        items: I,
+    ) -> Result<Self::CollectionContainer<T>, E> {
+        items.collect()
+    }
+
+    type Error = Infallible;
+
+    type NameRet = Doc;
+    fn visit_name(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::Name>,
+    ) -> Result<Self::NameRet, Self::Error> {
+        Ok(Doc::text(node.ident.to_string()))
+    }
+
+    type LitRet = Doc;
+    fn visit_lit(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::Lit>,
+    ) -> Result<Self::LitRet, Self::Error> {
+        walk::walk_lit_same_children(self, ctx, node)
+    }
+
+    type MapLitRet = Doc;
+    fn visit_map_lit(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::MapLit>,
+    ) -> Result<Self::MapLitRet, Self::Error> {
+        let mut items = vec![];
+
+        for entry in node.elements.iter() {
+            items.push((entry.span(), self.visit_map_lit_entry(ctx, entry.ast_ref())?));
+        }
+
+        let span = node.span();
+        Ok(self.comma_list(
+            ("map!{", "}"),
+            items,
+            (span.start(), span.end()),
+            Trailing::Config,
+            true,
+        ))
+    }
+
+    type MapLitEntryRet = Doc;
+    fn visit_map_lit_entry(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::MapLitEntry>,
+    ) -> Result<Self::MapLitEntryRet, Self::Error> {
+        Ok(Doc::concat([
+            self.expr(node.key.ast_ref(), Position::Element),
+            ": ".into(),
+            self.expr(node.value.ast_ref(), Position::Element),
+        ]))
+    }
+
+    type ListLitRet = Doc;
+    fn visit_list_lit(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::ListLit>,
+    ) -> Result<Self::ListLitRet, Self::Error> {
+        let span = node.span();
+        Ok(self.exprs(("[", "]"), &node.elements, (span.start(), span.end()), false))
+    }
+
+    type SetLitRet = Doc;
+    fn visit_set_lit(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::SetLit>,
+    ) -> Result<Self::SetLitRet, Self::Error> {
+        let span = node.span();
+        Ok(self.exprs(("set!{", "}"), &node.elements, (span.start(), span.end()), true))
+    }
+
+    type TupleLitEntryRet = Doc;
+    fn visit_tuple_lit_entry(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::TupleLitEntry>,
+    ) -> Result<Self::TupleLitEntryRet, Self::Error> {
+        let mut doc = vec![];
+
+        if let Some(name) = &node.name {
+            doc.push(self.name(name));
+
+            match &node.ty {
+                Some(ty) => {
+                    doc.push(": ".into());
+                    doc.push(self.ty(ty.ast_ref(), TyPosition::Free));
+                    doc.push(" = ".into());
+                }
+                None => doc.push(" = ".into()),
+            }
+        }
+
+        doc.push(self.expr(node.value.ast_ref(), Position::Element));
+        Ok(Doc::concat(doc))
+    }
+
+    type TupleLitRet = Doc;
+    fn visit_tuple_lit(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::TupleLit>,
+    ) -> Result<Self::TupleLitRet, Self::Error> {
+        // `(a)` is parsed as a parenthesised expression
+        let trailing = match node.elements.nodes.as_slice() {
+            [entry] if entry.name.is_none() && entry.ty.is_none() => Trailing::Required,
+            _ => Trailing::Config,
+        };
+
+        let mut items = vec![];
+
+        for entry in node.elements.iter() {
+            items.push((entry.span(), self.visit_tuple_lit_entry(ctx, entry.ast_ref())?));
+        }
+
+        let span = node.span();
+        Ok(self.comma_list(("(", ")"), items, (span.start(), span.end()), trailing, false))
+    }
+
+    type StrLitRet = Doc;
+    fn visit_str_lit(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::StrLit>,
+    ) -> Result<Self::StrLitRet, Self::Error> {
+        Ok(self.lit(node.span(), || format!("\"{}\"", escape_str(node.0.into(), false))))
+    }
+
+    type RawStrLitRet = Doc;
+    fn visit_raw_str_lit(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::RawStrLit>,
+    ) -> Result<Self::RawStrLitRet, Self::Error> {
+        Ok(self.lit(node.span(), || {
+            let contents: &str = node.0.into();
+
+            // Use one more `#` than the longest sequence of them that follows a quote
+            let hashes = contents
+                .split('"')
+                .skip(1)
+                .map(|part| part.chars().take_while(|ch| *ch == '#').count())
+                .max()
+                .unwrap_or(0)
+                + 1;
+
+            let hashes = "#".repeat(hashes);
+            format!("r{}\"{}\"{}", hashes, contents, hashes)
+        }))
+    }
+
+    type ByteStrLitRet = Doc;
+    fn visit_byte_str_lit(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::ByteStrLit>,
+    ) -> Result<Self::ByteStrLitRet, Self::Error> {
+        Ok(self.lit(node.span(), || format!("b\"{}\"", node.0.bytes().escape_ascii())))
+    }
+
+    type MultiLineStrLitRet = Doc;
+    fn visit_multi_line_str_lit(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::MultiLineStrLit>,
+    ) -> Result<Self::MultiLineStrLitRet, Self::Error> {
+        Ok(self.lit(node.span(), || {
+            let contents: &str = node.0.into();
+            let mut out = String::from("\"\"\"");
+
+            // The literal is printed on a single line, so any leading whitespace has to
+            // be escaped to not be stripped as indentation.
+            let mut leading = true;
+
+            for ch in contents.chars() {
+                leading = leading && ch.is_whitespace();
+
+                match ch {
+                    ' ' if leading => out.push_str("\\x20"),
+                    ch => escape_char(ch, '"', &mut out),
+                }
+            }
+
+            out.push_str("\"\"\"");
+            out
+        }))
+    }
+
+    type InterpolatedStrLitRet = Doc;
+    fn visit_interpolated_str_lit(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::InterpolatedStrLit>,
+    ) -> Result<Self::InterpolatedStrLitRet, Self::Error> {
+        if let Some(doc) = self.verbatim(node.span()) {
+            return Ok(doc);
+        }
+
+        let mut doc = vec!["f\"".into()];
+
+        for segment in node.segments.iter() {
+            match segment.kind() {
+                ast::ExprKind::LitExpr(ast::LitExpr(lit)) => match lit.body() {
+                    ast::Lit::Str(str) => doc.push(Doc::text(escape_str(str.0.into(), true))),
+                    _ => doc.push(Doc::concat([
+                        "{".into(),
+                        self.expr(segment.ast_ref(), FREE),
+                        "}".into(),
+                    ])),
+                },
+                _ => doc.push(Doc::concat([
+                    "{".into(),
+                    self.expr(segment.ast_ref(), FREE),
+                    "}".into(),
+                ])),
+            }
+        }
+
+        doc.push("\"".into());
+        Ok(Doc::concat(doc))
+    }
+
+    type CharLitRet = Doc;
+    fn visit_char_lit(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::CharLit>,
+    ) -> Result<Self::CharLitRet, Self::Error> {
+        Ok(self.lit(node.span(), || {
+            let mut out = String::from("'");
+            escape_char(node.0, '\'', &mut out);
+            out.push('\'');
+            out
+        }))
+    }
+
+    type FloatLitRet = Doc;
+    fn visit_float_lit(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::FloatLit>,
+    ) -> Result<Self::FloatLitRet, Self::Error> {
+        Ok(self.lit(node.span(), || node.0.to_string()))
+    }
+
+    type BoolLitRet = Doc;
+    fn visit_bool_lit(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::BoolLit>,
+    ) -> Result<Self::BoolLitRet, Self::Error> {
+        Ok(Doc::text(node.0.to_string()))
+    }
+
+    type IntLitRet = Doc;
+    fn visit_int_lit(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::IntLit>,
+    ) -> Result<Self::IntLitRet, Self::Error> {
+        Ok(self.lit(node.span(), || node.0.to_string()))
+    }
+
+    type BinaryOperatorRet = Doc;
+    fn visit_binary_operator(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::BinOp>,
+    ) -> Result<Self::BinaryOperatorRet, Self::Error> {
+        Ok(Doc::text(node.body().to_string()))
+    }
+
+    type UnaryOperatorRet = Doc;
+    fn visit_unary_operator(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::UnOp>,
+    ) -> Result<Self::UnaryOperatorRet, Self::Error> {
+        Ok(match node.body() {
+            ast::UnOp::BitNot => "~".into(),
+            ast::UnOp::Not => "!".into(),
+            ast::UnOp::Neg => "-".into(),
+        })
+    }
+
+    type ExprRet = Doc;
+    fn visit_expr(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::Expr>,
+    ) -> Result<Self::ExprRet, Self::Error> {
+        walk::walk_expr_same_children(self, ctx, node)
+    }
+
+    type VariableExprRet = Doc;
+    fn visit_variable_expr(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::VariableExpr>,
+    ) -> Result<Self::VariableExprRet, Self::Error> {
+        Ok(self.name(&node.name))
+    }
+
+    type DirectiveExprRet = Doc;
+    fn visit_directive_expr(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::DirectiveExpr>,
+    ) -> Result<Self::DirectiveExprRet, Self::Error> {
+        Ok(Doc::concat([
+            "#".into(),
+            self.name(&node.name),
+            " ".into(),
+            self.expr(node.subject.ast_ref(), Position::Unary),
+        ]))
+    }
+
+    type ConstructorCallArgRet = Doc;
+    fn visit_constructor_call_arg(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::ConstructorCallArg>,
+    ) -> Result<Self::ConstructorCallArgRet, Self::Error> {
+        let value = self.expr(node.value.ast_ref(), Position::Element);
+
+        Ok(match &node.name {
+            Some(name) => Doc::concat([self.name(name), " = ".into(), value]),
+            None => value,
+        })
+    }
+
+    type ConstructorCallArgsRet = Doc;
+    fn visit_constructor_call_args(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::ConstructorCallArgs>,
+    ) -> Result<Self::ConstructorCallArgsRet, Self::Error> {
+        let mut items = vec![];
+
+        for arg in node.entries.iter() {
+            items.push((arg.span(), self.visit_constructor_call_arg(ctx, arg.ast_ref())?));
+        }
+
+        let span = node.span();
+        Ok(self.comma_list(("(", ")"), items, (span.start(), span.end()), Trailing::Config, false))
+    }
+
+    type ConstructorCallExprRet = Doc;
+    fn visit_constructor_call_expr(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::ConstructorCallExpr>,
+    ) -> Result<Self::ConstructorCallExprRet, Self::Error> {
+        let subject = self.expr(node.subject.ast_ref(), Position::Postfix);
+        let args = self.visit_constructor_call_args(ctx, node.args.ast_ref())?;
+
+        Ok(subject.append(args))
+    }
+
+    type AccessExprRet = Doc;
+    fn visit_access_expr(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::AccessExpr>,
+    ) -> Result<Self::AccessExprRet, Self::Error> {
+        Ok(Doc::concat([
+            self.expr(node.subject.ast_ref(), Position::Postfix),
+            self.visit_access_kind(ctx, node.kind)?,
+            self.name(&node.property),
+        ]))
+    }
+
+    type AccessKindRet = Doc;
+    fn visit_access_kind(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AccessKind,
+    ) -> Result<Self::AccessKindRet, Self::Error> {
+        Ok(match node {
+            ast::AccessKind::Namespace => "::".into(),
+            ast::AccessKind::Property => ".".into(),
+        })
+    }
+
+    type RefExprRet = Doc;
+    fn visit_ref_expr(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::RefExpr>,
+    ) -> Result<Self::RefExprRet, Self::Error> {
+        let mut doc = vec!["&".into()];
+
+        if node.kind == ast::RefKind::Raw {
+            doc.push("raw ".into());
+        }
+
+        if let Some(mutability) = &node.mutability {
+            doc.push(self.visit_mutability_modifier(ctx, mutability.ast_ref())?);
+        }
+
+        doc.push(self.expr(node.inner_expr.ast_ref(), Position::Unary));
+        Ok(Doc::concat(doc))
+    }
+
+    type DerefExprRet = Doc;
+    fn visit_deref_expr(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::DerefExpr>,
+    ) -> Result<Self::DerefExprRet, Self::Error> {
+        Ok(Doc::concat(["*".into(), self.expr(node.0.ast_ref(), Position::Unary)]))
+    }
+
+    type UnsafeExprRet = Doc;
+    fn visit_unsafe_expr(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::UnsafeExpr>,
+    ) -> Result<Self::UnsafeExprRet, Self::Error> {
+        Ok(Doc::concat(["unsafe ".into(), self.expr(node.0.ast_ref(), Position::Unary)]))
+    }
+
+    type LitExprRet = Doc;
+    fn visit_lit_expr(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::LitExpr>,
+    ) -> Result<Self::LitExprRet, Self::Error> {
+        self.visit_lit(ctx, node.0.ast_ref())
+    }
+
+    type CastExprRet = Doc;
+    fn visit_cast_expr(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::CastExpr>,
+    ) -> Result<Self::CastExprRet, Self::Error> {
+        let min_prec = match self.position {
+            Position::Operand { min_prec, .. } => min_prec,
+            _ => 0,
+        };
+
+        let (l_prec, _) = ast::BinOp::As.infix_binding_power();
+        let expr =
+            self.expr(node.expr.ast_ref(), Position::Operand { min_prec, follower: Some(l_prec) });
+
+        Ok(Doc::concat([expr, " as ".into(), self.ty(node.ty.ast_ref(), TyPosition::Free)]))
+    }
+
+    type TyExprRet = Doc;
+    fn visit_ty_expr(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::TyExpr>,
+    ) -> Result<Self::TyExprRet, Self::Error> {
+        let ty = self.ty(node.0.ast_ref(), TyPosition::Free);
+
+        if is_postfix_ty_fn_call(node.0.body()) {
+            Ok(ty)
+        } else {
+            Ok(Doc::concat(["type ".into(), ty]))
+        }
+    }
+
+    type BlockExprRet = Doc;
+    fn visit_block_expr(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::BlockExpr>,
+    ) -> Result<Self::BlockExprRet, Self::Error> {
+        self.visit_block(ctx, node.0.ast_ref())
+    }
+
+    type ImportRet = Doc;
+    fn visit_import(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::Import>,
+    ) -> Result<Self::ImportRet, Self::Error> {
+        Ok(Doc::text(format!("import(\"{}\")", escape_str(node.path.into(), false))))
+    }
+
+    type ImportExprRet = Doc;
+    fn visit_import_expr(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::ImportExpr>,
+    ) -> Result<Self::ImportExprRet, Self::Error> {
+        self.visit_import(ctx, node.0.ast_ref())
+    }
+
+    type TyRet = Doc;
+    fn visit_ty(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::Ty>,
+    ) -> Result<Self::TyRet, Self::Error> {
+        walk::walk_ty_same_children(self, ctx, node)
+    }
+
+    type TupleTyRet = Doc;
+    fn visit_tuple_ty(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::TupleTy>,
+    ) -> Result<Self::TupleTyRet, Self::Error> {
+        let span = node.span();
+        Ok(self.ty_entries(&node.entries, (span.start(), span.end())))
+    }
+
+    type ListTyRet = Doc;
+    fn visit_list_ty(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::ListTy>,
+    ) -> Result<Self::ListTyRet, Self::Error> {
+        Ok(Doc::concat(["[".into(), self.ty(node.inner.ast_ref(), TyPosition::Free), "]".into()]))
+    }
+
+    type SetTyRet = Doc;
+    fn visit_set_ty(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::SetTy>,
+    ) -> Result<Self::SetTyRet, Self::Error> {
+        Ok(Doc::concat(["{".into(), self.ty(node.inner.ast_ref(), TyPosition::Free), "}".into()]))
+    }
+
+    type MapTyRet = Doc;
+    fn visit_map_ty(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::MapTy>,
+    ) -> Result<Self::MapTyRet, Self::Error> {
+        Ok(Doc::concat([
+            "{".into(),
+            self.ty(node.key.ast_ref(), TyPosition::Free),
+            ": ".into(),
+            self.ty(node.value.ast_ref(), TyPosition::Free),
+            "}".into(),
+        ]))
+    }
+
+    type TyArgRet = Doc;
+    fn visit_ty_arg(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::TyArg>,
+    ) -> Result<Self::TyArgRet, Self::Error> {
+        let ty = self.ty(node.ty.ast_ref(), TyPosition::Free);
+
+        Ok(match &node.name {
+            Some(name) => Doc::concat([self.name(name), " = ".into(), ty]),
+            None => ty,
+        })
+    }
+
+    type FnTyRet = Doc;
+    fn visit_fn_ty(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::FnTy>,
+    ) -> Result<Self::FnTyRet, Self::Error> {
+        let params =
+            self.ty_entries(&node.params, (node.span().start(), node.return_ty.span().start()));
+
+        Ok(Doc::concat([
+            params,
+            " -> ".into(),
+            self.ty(node.return_ty.ast_ref(), TyPosition::Free),
+        ]))
+    }
+
+    type TyFnRet = Doc;
+    fn visit_ty_fn_ty(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::TyFn>,
+    ) -> Result<Self::TyFnRet, Self::Error> {
+        let bounds = (node.span().start(), node.return_ty.span().start());
+        let params =
+            self.params(("<", ">"), &node.params, bounds, Trailing::Forbidden, TyPosition::Free);
+
+        Ok(Doc::concat([
+            params,
+            " -> ".into(),
+            self.ty(node.return_ty.ast_ref(), TyPosition::Free),
+        ]))
+    }
+
+    type TyFnCallRet = Doc;
+    fn visit_ty_fn_call(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::TyFnCall>,
+    ) -> Result<Self::TyFnCallRet, Self::Error> {
+        let subject = match node.subject.kind() {
+            ast::ExprKind::Ty(ast::TyExpr(inner)) if !is_postfix_ty_fn_call(inner.body()) => {
+                self.ty(inner.ast_ref(), TyPosition::Postfix)
+            }
+            _ => self.expr(node.subject.ast_ref(), Position::Postfix),
+        };
+
+        let args = self.ty_args(&node.args, (node.subject.span().end(), node.span().end()));
+        Ok(subject.append(args))
+    }
+
+    type NamedTyRet = Doc;
+    fn visit_named_ty(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::NamedTy>,
+    ) -> Result<Self::NamedTyRet, Self::Error> {
+        Ok(self.name(&node.name))
+    }
+
+    type AccessTyRet = Doc;
+    fn visit_access_ty(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::AccessTy>,
+    ) -> Result<Self::AccessTyRet, Self::Error> {
+        Ok(Doc::concat([
+            self.ty(node.subject.ast_ref(), TyPosition::Postfix),
+            "::".into(),
+            self.name(&node.property),
+        ]))
+    }
+
+    type RefTyRet = Doc;
+    fn visit_ref_ty(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::RefTy>,
+    ) -> Result<Self::RefTyRet, Self::Error> {
+        let mut doc = vec!["&".into()];
+
+        if let Some(kind) = &node.kind {
+            doc.push(self.visit_ref_kind(ctx, kind.ast_ref())?);
+        }
+
+        if let Some(mutability) = &node.mutability {
+            doc.push(self.visit_mutability_modifier(ctx, mutability.ast_ref())?);
+        }
+
+        doc.push(self.ty(node.inner.ast_ref(), TyPosition::Free));
+        Ok(Doc::concat(doc))
+    }
+
+    type MergeTyRet = Doc;
+    fn visit_merge_ty(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::MergeTy>,
+    ) -> Result<Self::MergeTyRet, Self::Error> {
+        Ok(self.binary_ty(ast::BinTyOp::Merge, &node.lhs, &node.rhs))
+    }
+
+    type UnionTyRet = Doc;
+    fn visit_union_ty(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::UnionTy>,
+    ) -> Result<Self::UnionTyRet, Self::Error> {
+        Ok(self.binary_ty(ast::BinTyOp::Union, &node.lhs, &node.rhs))
+    }
+
+    type TyFnDefRet = Doc;
+    fn visit_ty_fn_def(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::TyFnDef>,
+    ) -> Result<Self::TyFnDefRet, Self::Error> {
+        let end = node.return_ty.as_ref().map_or(node.body.span(), |ty| ty.span()).start();
+        let params = self.params(
+            ("<", ">"),
+            &node.params,
+            (node.span().start(), end),
+            Trailing::Forbidden,
+            TyPosition::Free,
+        );
+
+        let mut doc = vec![params];
+
+        if let Some(return_ty) = &node.return_ty {
+            doc.push(" -> ".into());
+            doc.push(self.ty(return_ty.ast_ref(), TyPosition::Free));
+        }
+
+        doc.push(" => ".into());
+        doc.push(self.expr(node.body.ast_ref(), FREE));
+        Ok(Doc::concat(doc))
+    }
+
+    type FnDefRet = Doc;
+    fn visit_fn_def(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::FnDef>,
+    ) -> Result<Self::FnDefRet, Self::Error> {
+        let span = node.span();
+        let bounds = (
+            span.start(),
+            node.return_ty.as_ref().map_or(node.fn_body.span(), |ty| ty.span()).start(),
+        );
+
+        // Functions that were written as closures are kept as closures, if possible.
+        let is_closure = self
+            .source
+            .and_then(|source| source.get(span.start()..))
+            .map_or(false, |source| source.starts_with('|'))
+            && node.return_ty.is_none()
+            && node.params.iter().all(|param| param.default.is_none());
+
+        let mut doc = vec![];
+
+        if is_closure {
+            doc.push(self.params(
+                ("|", "|"),
+                &node.params,
+                bounds,
+                Trailing::Forbidden,
+                TyPosition::ClosureParam,
+            ));
+        } else {
+            doc.push(self.params(
+                ("(", ")"),
+                &node.params,
+                bounds,
+                Trailing::Config,
+                TyPosition::Free,
+            ));
+
+            if let Some(return_ty) = &node.return_ty {
+                doc.push(" -> ".into());
+                doc.push(self.ty(return_ty.ast_ref(), TyPosition::Free));
+            }
+
+            doc.push(" =>".into());
+        }
+
+        doc.push(" ".into());
+        doc.push(self.expr(node.fn_body.ast_ref(), FREE));
+        Ok(Doc::concat(doc))
+    }
+
+    type ParamRet = Doc;
+    fn visit_param(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::Param>,
+    ) -> Result<Self::ParamRet, Self::Error> {
+        Ok(self.param(node, TyPosition::Free))
+    }
+
+    type BlockRet = Doc;
+    fn visit_block(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::Block>,
+    ) -> Result<Self::BlockRet, Self::Error> {
+        walk::walk_block_same_children(self, ctx, node)
+    }
+
+    type MatchCaseRet = Doc;
+    fn visit_match_case(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::MatchCase>,
+    ) -> Result<Self::MatchCaseRet, Self::Error> {
+        Ok(Doc::concat([
+            self.pat(node.pat.ast_ref(), PatPosition::Free),
+            " => ".into(),
+            self.expr(node.expr.ast_ref(), FREE),
+        ]))
+    }
+
+    type MatchBlockRet = Doc;
+    fn visit_match_block(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::MatchBlock>,
+    ) -> Result<Self::MatchBlockRet, Self::Error> {
+        let subject = self.expr(node.subject.ast_ref(), Position::Condition);
+        let mut items = vec![];
+
+        for case in node.cases.iter() {
+            let doc = self.visit_match_case(ctx, case.ast_ref())?;
+            items.push((case.span(), doc.append(";".into())));
+        }
+
+        let cases = self.braced(items, node.subject.span().end(), node.span().end());
+        Ok(Doc::concat(["match ".into(), subject, " ".into(), cases]))
+    }
+
+    type LoopBlockRet = Doc;
+    fn visit_loop_block(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::LoopBlock>,
+    ) -> Result<Self::LoopBlockRet, Self::Error> {
+        Ok(Doc::concat(["loop ".into(), self.visit_block(ctx, node.0.ast_ref())?]))
+    }
+
+    type ForLoopBlockRet = Doc;
+    fn visit_for_loop_block(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::ForLoopBlock>,
+    ) -> Result<Self::ForLoopBlockRet, Self::Error> {
+        Ok(Doc::concat([
+            "for ".into(),
+            self.pat(node.pat.ast_ref(), PatPosition::Singular),
+            " in ".into(),
+            self.expr(node.iterator.ast_ref(), Position::Condition),
+            " ".into(),
+            self.visit_block(ctx, node.body.ast_ref())?,
+        ]))
+    }
+
+    type WhileLoopBlockRet = Doc;
+    fn visit_while_loop_block(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::WhileLoopBlock>,
+    ) -> Result<Self::WhileLoopBlockRet, Self::Error> {
+        Ok(Doc::concat([
+            "while ".into(),
+            self.expr(node.condition.ast_ref(), Position::Condition),
+            " ".into(),
+            self.visit_block(ctx, node.body.ast_ref())?,
+        ]))
+    }
+
+    type ModBlockRet = Doc;
+    fn visit_mod_block(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::ModBlock>,
+    ) -> Result<Self::ModBlockRet, Self::Error> {
+        Ok(Doc::concat(["mod ".into(), self.visit_body_block(ctx, node.0.ast_ref())?]))
+    }
+
+    type ImplBlockRet = Doc;
+    fn visit_impl_block(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::ImplBlock>,
+    ) -> Result<Self::ImplBlockRet, Self::Error> {
+        Ok(Doc::concat(["impl ".into(), self.visit_body_block(ctx, node.0.ast_ref())?]))
+    }
+
+    type IfClauseRet = Doc;
+    fn visit_if_clause(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::IfClause>,
+    ) -> Result<Self::IfClauseRet, Self::Error> {
+        Ok(Doc::concat([
+            "if ".into(),
+            self.expr(node.condition.ast_ref(), Position::Condition),
+            " ".into(),
+            self.visit_block(ctx, node.body.ast_ref())?,
+        ]))
+    }
+
+    type IfBlockRet = Doc;
+    fn visit_if_block(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::IfBlock>,
+    ) -> Result<Self::IfBlockRet, Self::Error> {
+        let mut clauses = vec![];
+
+        for clause in node.clauses.iter() {
+            clauses.push(self.visit_if_clause(ctx, clause.ast_ref())?);
+        }
+
+        if let Some(otherwise) = &node.otherwise {
+            clauses.push(self.visit_block(ctx, otherwise.ast_ref())?);
+        }
+
+        Ok(Doc::join(clauses, " else ".into()))
+    }
+
+    type BodyBlockRet = Doc;
+    fn visit_body_block(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::BodyBlock>,
+    ) -> Result<Self::BodyBlockRet, Self::Error> {
+        Ok(self.body_block(node))
+    }
+
+    type ReturnStatementRet = Doc;
+    fn visit_return_statement(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::ReturnStatement>,
+    ) -> Result<Self::ReturnStatementRet, Self::Error> {
+        Ok(match &node.0 {
+            Some(value) => Doc::concat(["return ".into(), self.expr(value.ast_ref(), FREE)]),
+            None => "return".into(),
+        })
+    }
+
+    type BreakStatementRet = Doc;
+    fn visit_break_statement(
+        &mut self,
+        _: &Self::Ctx,
+        _: ast::AstNodeRef<ast::BreakStatement>,
+    ) -> Result<Self::BreakStatementRet, Self::Error> {
+        Ok("break".into())
+    }
+
+    type ContinueStatementRet = Doc;
+    fn visit_continue_statement(
+        &mut self,
+        _: &Self::Ctx,
+        _: ast::AstNodeRef<ast::ContinueStatement>,
+    ) -> Result<Self::ContinueStatementRet, Self::Error> {
+        Ok("continue".into())
+    }
+
+    type VisibilityRet = Doc;
+    fn visit_visibility_modifier(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::Visibility>,
+    ) -> Result<Self::VisibilityRet, Self::Error> {
+        Ok(match node.body() {
+            ast::Visibility::Public => "pub ".into(),
+            ast::Visibility::Private => "priv ".into(),
+        })
+    }
+
+    type MutabilityRet = Doc;
+    fn visit_mutability_modifier(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::Mutability>,
+    ) -> Result<Self::MutabilityRet, Self::Error> {
+        Ok(match node.body() {
+            ast::Mutability::Mutable => "mut ".into(),
+            ast::Mutability::Immutable => Doc::nil(),
+        })
+    }
+
+    type RefKindRet = Doc;
+    fn visit_ref_kind(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::RefKind>,
+    ) -> Result<Self::RefKindRet, Self::Error> {
+        Ok(match node.body() {
+            ast::RefKind::Raw => "raw ".into(),
+            ast::RefKind::Normal => Doc::nil(),
+        })
+    }
+
+    type AttributeRet = Doc;
+    fn visit_attribute(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::Attribute>,
+    ) -> Result<Self::AttributeRet, Self::Error> {
+        let name = self.name(&node.name);
+
+        match &node.args {
+            Some(args) => Ok(name.append(self.visit_constructor_call_args(ctx, args.ast_ref())?)),
+            None => Ok(name),
+        }
+    }
+
+    type DeclarationRet = Doc;
+    fn visit_declaration(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::Declaration>,
+    ) -> Result<Self::DeclarationRet, Self::Error> {
+        let mut doc = vec![];
+
+        if !node.attributes.is_empty() {
+            let mut items = vec![];
+
+            for attribute in node.attributes.iter() {
+                items.push((attribute.span(), self.visit_attribute(ctx, attribute.ast_ref())?));
+            }
+
+            let bounds = (node.span().start(), node.pat.span().start());
+            doc.push(self.comma_list(("#[", "]"), items, bounds, Trailing::Config, false));
+            doc.push(Doc::HardLine);
+        }
+
+        doc.push(self.pat(node.pat.ast_ref(), PatPosition::Declaration));
+
+        match (&node.ty, &node.value) {
+            (Some(ty), value) => {
+                doc.push(": ".into());
+                doc.push(self.ty(ty.ast_ref(), TyPosition::Free));
+
+                if let Some(value) = value {
+                    doc.push(" = ".into());
+                    doc.push(self.expr(value.ast_ref(), FREE));
+                }
+            }
+            (None, Some(value)) => {
+                doc.push(" := ".into());
+                doc.push(self.expr(value.ast_ref(), FREE));
+            }
+            (None, None) => {}
+        }
+
+        Ok(Doc::concat(doc))
+    }
+
+    type MergeDeclarationRet = Doc;
+    fn visit_merge_declaration(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::MergeDeclaration>,
+    ) -> Result<Self::MergeDeclarationRet, Self::Error> {
+        Ok(Doc::concat([
+            self.expr(node.decl.ast_ref(), FREE),
+            " ~= ".into(),
+            self.expr(node.value.ast_ref(), FREE),
+        ]))
+    }
+
+    type AssignExprRet = Doc;
+    fn visit_assign_expr(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::AssignExpr>,
+    ) -> Result<Self::AssignExprRet, Self::Error> {
+        Ok(Doc::concat([
+            self.expr(node.lhs.ast_ref(), FREE),
+            " = ".into(),
+            self.expr(node.rhs.ast_ref(), FREE),
+        ]))
+    }
+
+    type AssignOpExprRet = Doc;
+    fn visit_assign_op_expr(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::AssignOpExpr>,
+    ) -> Result<Self::AssignOpExprRet, Self::Error> {
+        Ok(Doc::concat([
+            self.expr(node.lhs.ast_ref(), FREE),
+            " ".into(),
+            self.visit_binary_operator(ctx, node.operator.ast_ref())?,
+            "= ".into(),
+            self.expr(node.rhs.ast_ref(), FREE),
+        ]))
+    }
+
+    type BinaryExprRet = Doc;
+    fn visit_binary_expr(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::BinaryExpr>,
+    ) -> Result<Self::BinaryExprRet, Self::Error> {
+        let (min_prec, follower) = match self.position {
+            Position::Operand { min_prec, follower } => (min_prec, follower),
+            _ => (0, None),
+        };
+
+        let (l_prec, r_prec) = node.operator.infix_binding_power();
+        let lhs =
+            self.expr(node.lhs.ast_ref(), Position::Operand { min_prec, follower: Some(l_prec) });
+        let operator = self.visit_binary_operator(ctx, node.operator.ast_ref())?;
+
+        let rhs = if binary_rhs_needs_extra_parens(node.body()) {
+            parens(self.expr(node.rhs.ast_ref(), FREE))
+        } else {
+            self.expr(node.rhs.ast_ref(), Position::Operand { min_prec: r_prec, follower })
+        };
+
+        Ok(Doc::concat([lhs, Doc::concat([Doc::Line, operator, " ".into(), rhs]).nest()]).group())
+    }
+
+    type UnaryExprRet = Doc;
+    fn visit_unary_expr(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::UnaryExpr>,
+    ) -> Result<Self::UnaryExprRet, Self::Error> {
+        Ok(Doc::concat([
+            self.visit_unary_operator(ctx, node.operator.ast_ref())?,
+            self.expr(node.expr.ast_ref(), Position::Unary),
+        ]))
+    }
+
+    type IndexExprRet = Doc;
+    fn visit_index_expr(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::IndexExpr>,
+    ) -> Result<Self::IndexExprRet, Self::Error> {
+        Ok(Doc::concat([
+            self.expr(node.subject.ast_ref(), Position::Postfix),
+            "[".into(),
+            self.expr(node.index_expr.ast_ref(), FREE),
+            "]".into(),
+        ]))
+    }
+
+    type RangeExprRet = Doc;
+    fn visit_range_expr(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::RangeExpr>,
+    ) -> Result<Self::RangeExprRet, Self::Error> {
+        Ok(Doc::concat([
+            self.expr(node.lo.ast_ref(), Position::Operand { min_prec: 0, follower: Some(1) }),
+            Doc::text(node.end.to_string()),
+            self.expr(node.hi.ast_ref(), Position::Operand { min_prec: 1, follower: None }),
+        ]))
+    }
+
+    type PropagateExprRet = Doc;
+    fn visit_propagate_expr(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::PropagateExpr>,
+    ) -> Result<Self::PropagateExprRet, Self::Error> {
+        Ok(self.expr(node.subject.ast_ref(), Position::Postfix).append("?".into()))
+    }
+
+    type ErrExprRet = Doc;
+    fn visit_err_expr(
+        &mut self,
+        _: &Self::Ctx,
+        _: ast::AstNodeRef<ast::ErrExpr>,
+    ) -> Result<Self::ErrExprRet, Self::Error> {
+        Ok(Doc::nil())
+    }
+
+    type StructDefRet = Doc;
+    fn visit_struct_def(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::StructDef>,
+    ) -> Result<Self::StructDefRet, Self::Error> {
+        let span = node.span();
+        let entries = self.params(
+            ("(", ")"),
+            &node.entries,
+            (span.start(), span.end()),
+            Trailing::Config,
+            TyPosition::Free,
+        );
+
+        Ok(Doc::concat(["struct".into(), entries]))
+    }
+
+    type EnumDefEntryRet = Doc;
+    fn visit_enum_def_entry(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::EnumDefEntry>,
+    ) -> Result<Self::EnumDefEntryRet, Self::Error> {
+        let name = self.name(&node.name);
+
+        if node.args.is_empty() {
+            return Ok(name);
+        }
+
+        let items = node
+            .args
+            .iter()
+            .map(|arg| (arg.span(), self.ty(arg.ast_ref(), TyPosition::Free)))
+            .collect();
+
+        let bounds = (node.name.span().end(), node.span().end());
+        Ok(name.append(self.comma_list(("(", ")"), items, bounds, Trailing::Config, false)))
+    }
+
+    type EnumDefRet = Doc;
+    fn visit_enum_def(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::EnumDef>,
+    ) -> Result<Self::EnumDefRet, Self::Error> {
+        let mut items = vec![];
+
+        for entry in node.entries.iter() {
+            items.push((entry.span(), self.visit_enum_def_entry(ctx, entry.ast_ref())?));
+        }
+
+        let span = node.span();
+        let entries =
+            self.comma_list(("(", ")"), items, (span.start(), span.end()), Trailing::Config, false);
+
+        Ok(Doc::concat(["enum".into(), entries]))
+    }
+
+    type TraitDefRet = Doc;
+    fn visit_trait_def(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::TraitDef>,
+    ) -> Result<Self::TraitDefRet, Self::Error> {
+        let span = node.span();
+        let items = self.statements(&node.members);
+
+        Ok(Doc::concat(["trait ".into(), self.braced(items, span.start(), span.end())]))
+    }
+
+    type TraitImplRet = Doc;
+    fn visit_trait_impl(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::TraitImpl>,
+    ) -> Result<Self::TraitImplRet, Self::Error> {
+        let ty = self.ty(node.ty.ast_ref(), TyPosition::Free);
+        let items = self.statements(&node.implementation);
+        let members = self.braced(items, node.ty.span().end(), node.span().end());
+
+        Ok(Doc::concat(["impl ".into(), ty, " ".into(), members]))
+    }
+
+    type PatRet = Doc;
+    fn visit_pat(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::Pat>,
+    ) -> Result<Self::PatRet, Self::Error> {
+        walk::walk_pat_same_children(self, ctx, node)
+    }
+
+    type AccessPatRet = Doc;
+    fn visit_access_pat(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::AccessPat>,
+    ) -> Result<Self::AccessPatRet, Self::Error> {
+        Ok(Doc::concat([
+            self.pat(node.subject.ast_ref(), PatPosition::Singular),
+            "::".into(),
+            self.name(&node.property),
+        ]))
+    }
+
+    type ConstructorPatRet = Doc;
+    fn visit_constructor_pat(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::ConstructorPat>,
+    ) -> Result<Self::ConstructorPatRet, Self::Error> {
+        let subject = self.pat(node.subject.ast_ref(), PatPosition::Singular);
+        let bounds = (node.subject.span().end(), node.span().end());
+
+        Ok(subject.append(self.tuple_pat_entries(&node.fields, bounds, Trailing::Config)))
+    }
+
+    type TuplePatEntryRet = Doc;
+    fn visit_tuple_pat_entry(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::TuplePatEntry>,
+    ) -> Result<Self::TuplePatEntryRet, Self::Error> {
+        let pat = self.pat(node.pat.ast_ref(), PatPosition::Element);
+
+        Ok(match &node.name {
+            Some(name) => Doc::concat([self.name(name), " = ".into(), pat]),
+            None => pat,
+        })
+    }
+
+    type TuplePatRet = Doc;
+    fn visit_tuple_pat(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::TuplePat>,
+    ) -> Result<Self::TuplePatRet, Self::Error> {
+        // `(a)` is parsed as a parenthesised pattern
+        let trailing = match node.fields.nodes.as_slice() {
+            [field] if field.name.is_none() => Trailing::Required,
+            _ => Trailing::Config,
+        };
+
+        let span = node.span();
+        Ok(self.tuple_pat_entries(&node.fields, (span.start(), span.end()), trailing))
+    }
+
+    type ListPatRet = Doc;
+    fn visit_list_pat(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::ListPat>,
+    ) -> Result<Self::ListPatRet, Self::Error> {
+        let items = node
+            .fields
+            .iter()
+            .map(|field| (field.span(), self.pat(field.ast_ref(), PatPosition::Element)))
+            .collect();
+
+        let span = node.span();
+        Ok(self.comma_list(("[", "]"), items, (span.start(), span.end()), Trailing::Config, false))
+    }
+
+    type SpreadPatRet = Doc;
+    fn visit_spread_pat(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::SpreadPat>,
+    ) -> Result<Self::SpreadPatRet, Self::Error> {
+        Ok(match &node.name {
+            Some(name) => Doc::concat(["...".into(), self.name(name)]),
+            None => "...".into(),
+        })
+    }
+
+    type RangePatRet = Doc;
+    fn visit_range_pat(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::RangePat>,
+    ) -> Result<Self::RangePatRet, Self::Error> {
+        Ok(Doc::concat([
+            self.visit_lit_pat(ctx, node.lo.ast_ref())?,
+            Doc::text(node.end.to_string()),
+            self.visit_lit_pat(ctx, node.hi.ast_ref())?,
+        ]))
+    }
+
+    type ErrPatRet = Doc;
+    fn visit_err_pat(
+        &mut self,
+        _: &Self::Ctx,
+        _: ast::AstNodeRef<ast::ErrPat>,
+    ) -> Result<Self::ErrPatRet, Self::Error> {
+        Ok(Doc::nil())
+    }
+
+    type StrLitPatRet = Doc;
+    fn visit_str_lit_pat(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::StrLitPat>,
+    ) -> Result<Self::StrLitPatRet, Self::Error> {
+        Ok(self.lit(node.span(), || format!("\"{}\"", escape_str(node.0.into(), false))))
+    }
+
+    type CharLitPatRet = Doc;
+    fn visit_char_lit_pat(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::CharLitPat>,
+    ) -> Result<Self::CharLitPatRet, Self::Error> {
+        Ok(self.lit(node.span(), || {
+            let mut out = String::from("'");
+            escape_char(node.0, '\'', &mut out);
+            out.push('\'');
+            out
+        }))
+    }
+
+    type IntLitPatRet = Doc;
+    fn visit_int_lit_pat(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::IntLitPat>,
+    ) -> Result<Self::IntLitPatRet, Self::Error> {
+        Ok(self.lit(node.span(), || node.0.to_string()))
+    }
+
+    type FloatLitPatRet = Doc;
+    fn visit_float_lit_pat(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::FloatLitPat>,
+    ) -> Result<Self::FloatLitPatRet, Self::Error> {
+        Ok(self.lit(node.span(), || node.0.to_string()))
+    }
+
+    type BoolLitPatRet = Doc;
+    fn visit_bool_lit_pat(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::BoolLitPat>,
+    ) -> Result<Self::BoolLitPatRet, Self::Error> {
+        Ok(Doc::text(node.0.to_string()))
+    }
+
+    type LitPatRet = Doc;
+    fn visit_lit_pat(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::LitPat>,
+    ) -> Result<Self::LitPatRet, Self::Error> {
+        walk::walk_lit_pat_same_children(self, ctx, node)
+    }
+
+    type OrPatRet = Doc;
+    fn visit_or_pat(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::OrPat>,
+    ) -> Result<Self::OrPatRet, Self::Error> {
+        let count = node.variants.len();
+        let mut variants = vec![];
+
+        for (index, variant) in node.variants.iter().enumerate() {
+            let position =
+                if index + 1 == count { PatPosition::LastVariant } else { PatPosition::Variant };
+
+            variants.push(self.pat(variant.ast_ref(), position));
+        }
+
+        Ok(Doc::join(variants, " | ".into()))
+    }
+
+    type IfPatRet = Doc;
+    fn visit_if_pat(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::IfPat>,
+    ) -> Result<Self::IfPatRet, Self::Error> {
+        // @@Note: the condition is never wrapped in parentheses, since `(a) => ...`
+        // within a match case would be parsed as a function definition.
+        Ok(Doc::concat([
+            self.pat(node.pat.ast_ref(), PatPosition::Singular),
+            " if ".into(),
+            self.expr(node.condition.ast_ref(), FREE),
+        ]))
+    }
+
+    type BindingPatRet = Doc;
+    fn visit_binding_pat(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::BindingPat>,
+    ) -> Result<Self::BindingPatRet, Self::Error> {
+        let mut doc = vec![];
+
+        if let Some(visibility) = &node.visibility {
+            doc.push(self.visit_visibility_modifier(ctx, visibility.ast_ref())?);
+        }
+
+        if let Some(mutability) = &node.mutability {
+            doc.push(self.visit_mutability_modifier(ctx, mutability.ast_ref())?);
+        }
+
+        doc.push(self.name(&node.name));
+        Ok(Doc::concat(doc))
+    }
+
+    type IgnorePatRet = Doc;
+    fn visit_ignore_pat(
+        &mut self,
+        _: &Self::Ctx,
+        _: ast::AstNodeRef<ast::IgnorePat>,
+    ) -> Result<Self::IgnorePatRet, Self::Error> {
+        Ok("_".into())
+    }
+
+    type ModulePatEntryRet = Doc;
+    fn visit_module_pat_entry(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::ModulePatEntry>,
+    ) -> Result<Self::ModulePatEntryRet, Self::Error> {
+        let name = self.name(&node.name);
+
+        // `{ a }` is a shorthand for `{ a as a }`
+        match node.pat.body() {
+            ast::Pat::Binding(ast::BindingPat {
+                name: binding,
+                visibility: None,
+                mutability: None,
+            }) if binding.ident == node.name.ident => Ok(name),
+            _ => Ok(Doc::concat([
+                name,
+                " as ".into(),
+                self.pat(node.pat.ast_ref(), PatPosition::Element),
+            ])),
+        }
+    }
+
+    type ModulePatRet = Doc;
+    fn visit_module_pat(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::ModulePat>,
+    ) -> Result<Self::ModulePatRet, Self::Error> {
+        let mut items = vec![];
+
+        for entry in node.fields.iter() {
+            items.push((entry.span(), self.visit_module_pat_entry(ctx, entry.ast_ref())?));
+        }
+
+        let span = node.span();
+        Ok(self.comma_list(("{", "}"), items, (span.start(), span.end()), Trailing::Config, true))
+    }
+
+    type ModuleRet = Doc;
+    fn visit_module(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::Module>,
+    ) -> Result<Self::ModuleRet, Self::Error> {
+        let items = self.statements(&node.contents);
+        let lines = self.lines(items, 0, usize::MAX);
+
+        if lines.is_nil() {
+            Ok(lines)
+        } else {
+            Ok(lines.append(Doc::HardLine))
+        }
+    }
+}
+
+impl AstPrinter<'_> {
+    /// Print a binary type, see [AstPrinter::visit_binary_expr].
+    fn binary_ty(
+        &mut self,
+        operator: ast::BinTyOp,
+        lhs: &ast::AstNode<ast::Ty>,
+        rhs: &ast::AstNode<ast::Ty>,
+    ) -> Doc {
+        let (min_prec, follower) = match self.ty_position {
+            TyPosition::Operand { min_prec, follower } => (min_prec, follower),
+            _ => (0, None),
+        };
+
+        let (l_prec, r_prec) = operator.infix_binding_power();
+        let lhs = self.ty(lhs.ast_ref(), TyPosition::Operand { min_prec, follower: Some(l_prec) });
+        let rhs = self.ty(rhs.ast_ref(), TyPosition::Operand { min_prec: r_prec, follower });
+
+        let operator = match operator {
+            ast::BinTyOp::Union => " | ",
+            ast::BinTyOp::Merge => " ~ ",
+        };
+
+        Doc::concat([lhs, operator.into(), rhs])
+    }
+}
@@ -132,6 +132,44 @@ impl AstVisitor for AstTreeGenerator {
         Ok(TreeNode::leaf(labelled("str", node.0, "\"")))
     }
 
+    type RawStrLitRet = TreeNode;
+    fn visit_raw_str_lit(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::RawStrLit>,
+    ) -> Result<Self::RawStrLitRet, Self::Error> {
+        Ok(TreeNode::leaf(labelled("raw_str", node.0, "\"")))
+    }
+
+    type ByteStrLitRet = TreeNode;
+    fn visit_byte_str_lit(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::ByteStrLit>,
+    ) -> Result<Self::ByteStrLitRet, Self::Error> {
+        Ok(TreeNode::leaf(labelled("byte_str", node.0, "\"")))
+    }
+
+    type MultiLineStrLitRet = TreeNode;
+    fn visit_multi_line_str_lit(
+        &mut self,
+        _: &Self::Ctx,
+        node: ast::AstNodeRef<ast::MultiLineStrLit>,
+    ) -> Result<Self::MultiLineStrLitRet, Self::Error> {
+        // The contents are escaped since they span multiple lines
+        Ok(TreeNode::leaf(labelled("multi_line_str", format!("{:?}", String::from(node.0)), "")))
+    }
+
+    type InterpolatedStrLitRet = TreeNode;
+    fn visit_interpolated_str_lit(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::InterpolatedStrLit>,
+    ) -> Result<Self::InterpolatedStrLitRet, Self::Error> {
+        let children = walk::walk_interpolated_str_lit(self, ctx, node)?;
+        Ok(TreeNode::branch("interpolated_str", children.segments))
+    }
+
     type CharLitRet = TreeNode;
     fn visit_char_lit(
         &mut self,
@@ -849,6 +887,22 @@ impl AstVisitor for AstTreeGenerator {
         Ok(())
     }
 
+    type AttributeRet = TreeNode;
+
+    fn visit_attribute(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::Attribute>,
+    ) -> Result<Self::AttributeRet, Self::Error> {
+        let walk::Attribute { args, .. } = walk::walk_attribute(self, ctx, node)?;
+        let label = labelled("attribute", node.name.ident, "\"");
+
+        match args {
+            Some(args) => Ok(TreeNode::branch(label, vec![args])),
+            None => Ok(TreeNode::leaf(label)),
+        }
+    }
+
     type DeclarationRet = TreeNode;
 
     fn visit_declaration(
@@ -856,12 +910,17 @@ impl AstVisitor for AstTreeGenerator {
         ctx: &Self::Ctx,
         node: ast::AstNodeRef<ast::Declaration>,
     ) -> Result<Self::DeclarationRet, Self::Error> {
-        let walk::Declaration { pat: pattern, ty, value } =
+        let walk::Declaration { attributes, pat: pattern, ty, value } =
             walk::walk_declaration(self, ctx, node)?;
 
+        let attributes =
+            (!attributes.is_empty()).then(|| TreeNode::branch("attributes", attributes));
+
         Ok(TreeNode::branch(
             "declaration",
-            iter::once(TreeNode::branch("pattern", vec![pattern]))
+            attributes
+                .into_iter()
+                .chain(iter::once(TreeNode::branch("pattern", vec![pattern])))
                 .chain(ty.map(|t| TreeNode::branch("type", vec![t])))
                 .chain(value.map(|t| TreeNode::branch("value", vec![t])))
                 .collect(),
@@ -950,6 +1009,40 @@ impl AstVisitor for AstTreeGenerator {
         ))
     }
 
+    type RangeExprRet = TreeNode;
+    fn visit_range_expr(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::RangeExpr>,
+    ) -> Result<Self::RangeExprRet, Self::Error> {
+        let end = node.end;
+        let walk::RangeExpr { lo, hi } = walk::walk_range_expr(self, ctx, node)?;
+
+        Ok(TreeNode::branch(
+            labelled("range", end, "\""),
+            vec![TreeNode::branch("lo", vec![lo]), TreeNode::branch("hi", vec![hi])],
+        ))
+    }
+
+    type PropagateExprRet = TreeNode;
+    fn visit_propagate_expr(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::PropagateExpr>,
+    ) -> Result<Self::PropagateExprRet, Self::Error> {
+        let walk::PropagateExpr { subject } = walk::walk_propagate_expr(self, ctx, node)?;
+        Ok(TreeNode::branch("propagate", vec![subject]))
+    }
+
+    type ErrExprRet = TreeNode;
+    fn visit_err_expr(
+        &mut self,
+        _: &Self::Ctx,
+        _: ast::AstNodeRef<ast::ErrExpr>,
+    ) -> Result<Self::ErrExprRet, Self::Error> {
+        Ok(TreeNode::leaf("error"))
+    }
+
     type StructDefRet = TreeNode;
     fn visit_struct_def(
         &mut self,
@@ -1225,6 +1318,32 @@ impl AstVisitor for AstTreeGenerator {
         Ok(TreeNode::leaf("ignore"))
     }
 
+    type RangePatRet = TreeNode;
+
+    fn visit_range_pat(
+        &mut self,
+        ctx: &Self::Ctx,
+        node: ast::AstNodeRef<ast::RangePat>,
+    ) -> Result<Self::RangePatRet, Self::Error> {
+        let end = node.end;
+        let walk::RangePat { lo, hi } = walk::walk_range_pat(self, ctx, node)?;
+
+        Ok(TreeNode::branch(
+            labelled("range", end, "\""),
+            vec![TreeNode::branch("lo", vec![lo]), TreeNode::branch("hi", vec![hi])],
+        ))
+    }
+
+    type ErrPatRet = TreeNode;
+
+    fn visit_err_pat(
+        &mut self,
+        _: &Self::Ctx,
+        _: ast::AstNodeRef<ast::ErrPat>,
+    ) -> Result<Self::ErrPatRet, Self::Error> {
+        Ok(TreeNode::leaf("error"))
+    }
+
     type ModulePatEntryRet = TreeNode;
 
     fn visit_module_pat_entry(
@@ -29,6 +29,7 @@ error_codes! {
     NonRuntimeInstantiable = 25,
     UnsupportedTyFnApplication = 26,
     TypeIsNotTrait = 27,
+    IntLitOutOfRange = 28,
 
     // Errors in regard to parameter lists
     ParameterLengthMismatch = 35,
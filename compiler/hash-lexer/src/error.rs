@@ -1,6 +1,5 @@
 //! Hash Compiler parser error data types.
 
-use derive_more::Constructor;
 use hash_source::{location::Span, SourceId};
 use hash_token::{delimiter::Delimiter, TokenKind};
 use thiserror::Error;
@@ -12,16 +11,86 @@ pub type LexerResult<T> = Result<T, LexerError>;
 /// includes an optional message with the error, the [LexerErrorKind] which
 /// classifies the error, and a [Span] that represents where the tokenisation
 /// error occurred.
-#[derive(Debug, Constructor, Error)]
+#[derive(Debug, Error)]
 #[error("{kind}{}", .message.as_ref().map(|s| format!(". {s}")).unwrap_or_else(|| String::from("")))]
 pub struct LexerError {
     pub(crate) message: Option<String>,
-    kind: LexerErrorKind,
+    pub(crate) kind: LexerErrorKind,
+    pub span: Span,
+    /// Labelled locations that are related to the error. If there are no
+    /// labels, then the error is only reported at the [Span] of the error.
+    pub labels: Vec<(Span, String)>,
+    /// An optional suggestion on how to fix the error, at a particular
+    /// location.
+    pub help: Option<(Span, String)>,
+}
+
+impl LexerError {
+    /// Create a new [LexerError] from an optional message, the kind of the
+    /// error, and the [Span] of the error.
+    pub fn new(message: Option<String>, kind: LexerErrorKind, span: Span) -> Self {
+        Self { message, kind, span, labels: vec![], help: None }
+    }
+
+    /// Add a labelled location to the error.
+    pub fn with_label(mut self, span: Span, label: impl ToString) -> Self {
+        self.labels.push((span, label.to_string()));
+        self
+    }
+
+    /// Add a suggestion on how to fix the error at the given location.
+    pub fn with_help(mut self, span: Span, help: impl ToString) -> Self {
+        self.help = Some((span, help.to_string()));
+        self
+    }
+}
+
+/// A [LexerWarning] represents a non-fatal issue that was encountered during
+/// tokenisation, which includes the [LexerWarningKind] and the [Span] of the
+/// offending source.
+#[derive(Debug, Error)]
+#[error("{kind}")]
+pub struct LexerWarning {
+    pub kind: LexerWarningKind,
     pub span: Span,
 }
 
+impl LexerWarning {
+    /// Create a new [LexerWarning] from the kind and the [Span] of the warning.
+    pub fn new(kind: LexerWarningKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+}
+
+/// A [LexerWarningKind] represents the kind of [LexerWarning].
+#[derive(Debug, Error)]
+pub enum LexerWarningKind {
+    /// An identifier that contains characters from multiple scripts, `ch` is
+    /// the first character that is not from the `script` of the preceding
+    /// characters, and is instead from the `other` script.
+    #[error("identifier `{ident}` mixes characters from the {script} and {other} scripts")]
+    MixedScriptIdent {
+        ident: String,
+        ch: char,
+        script: &'static str,
+        other: &'static str,
+        /// The ASCII character that `ch` looks like, if any.
+        lookalike: Option<String>,
+    },
+    /// An identifier that consists of non-ASCII characters which all look
+    /// like ASCII characters, `ch` is the first non-ASCII character.
+    #[error("identifier `{ident}` could be confused with the identifier `{lookalike}`")]
+    ConfusableIdent { ident: String, ch: char, lookalike: String },
+}
+
+/// This implementation exists since we can't use tuples that are un-named
+/// with foreign module types.
+#[derive(Debug)]
+pub struct LexerWarningWrapper(pub SourceId, pub LexerWarning);
+
 /// This implementation exists since we can't use tuples that are un-named
 /// with foreign module types.
+#[derive(Debug)]
 pub struct LexerErrorWrapper(pub SourceId, pub LexerError);
 
 /// A [LexerErrorKind] represents the kind of [LexerError] which gives
@@ -36,6 +105,9 @@ pub enum LexerErrorKind {
     /// specification, or is too large.
     #[error("Malformed numerical literal")]
     MalformedNumericalLit,
+    /// Occurs when a numerical literal has a suffix that isn't a known type.
+    #[error("Invalid suffix `{0}` for a numerical literal")]
+    InvalidLitSuffix(String),
     /// Occurs when a float literal exponent has no proceeding digits.
     #[error("Expected float exponent to have at least one digit")]
     MissingExponentDigits,
@@ -43,6 +115,24 @@ pub enum LexerErrorKind {
     /// specification, or is too large.
     #[error("Unclosed string literal")]
     UnclosedStringLit,
+    /// Occurs when an interpolated expression within a string literal isn't
+    /// closed by a `}` before the end of the literal.
+    #[error("Unclosed interpolation within a string literal, use `\\{{` for a literal `{{`")]
+    UnclosedInterpolation,
+    /// Occurs when a raw string literal is not closed by a quote followed by
+    /// the same number of `#`s as the literal was opened with.
+    #[error("Unclosed raw string literal, expected it to be closed with `{0}`")]
+    UnclosedRawStrLit(String),
+    /// Occurs when a byte string literal is not closed.
+    #[error("Unclosed byte string literal")]
+    UnclosedByteStrLit,
+    /// Occurs when a multi-line string literal is not closed with `"""`.
+    #[error("Unclosed multi-line string literal, expected it to be closed with `\"\"\"`")]
+    UnclosedMultiLineStrLit,
+    /// Occurs when a byte string literal contains a character that is not
+    /// ASCII, non-ASCII bytes must be written using `\x` escape sequences.
+    #[error("Non-ASCII character `{0}` in a byte string literal, use a `\\x` escape instead")]
+    NonAsciiByteStrChar(char),
     /// Occurs when a character literal is comprised of more than one character
     #[error("Invalid character literal `{0}`, character literals may only contain one codepoint")]
     InvalidCharacterLit(String),
@@ -56,6 +146,13 @@ pub enum LexerErrorKind {
     /// Unclosed tree block
     #[error("Encountered unclosed delimiter `{}`, add a `{0}` after the inner expression", .0.left())]
     Unclosed(Delimiter),
+    /// A tree that is closed by a delimiter that doesn't match the opening
+    /// delimiter of the tree.
+    #[error("Mismatched closing delimiter `{}`, expected `{}` to close the `{}`", .close.right(), .open.right(), .open.left())]
+    MismatchedDelimiter { open: Delimiter, close: Delimiter },
+    /// A closing delimiter that doesn't close any tree.
+    #[error("Unexpected closing delimiter `{}`", .0.right())]
+    UnexpectedClosingDelimiter(Delimiter),
 }
 
 // For now, we don't use the reporting capabilities within the lexer and just
@@ -0,0 +1,471 @@
+//! Hash Compiler incremental lexing. When the contents of a source are edited,
+//! only the smallest token tree that encloses the edit is re-lexed, and the
+//! rest of the previous token stream is re-used.
+use hash_source::location::Span;
+use hash_token::{
+    delimiter::Delimiter,
+    trivia::{Trivia, TriviaMap},
+    Token, TokenKind,
+};
+
+use crate::{
+    error::{LexerError, LexerErrorKind, LexerWarning},
+    Lexer, TreeClose,
+};
+
+/// An edit of the contents of a source, the contents within the `span` of the
+/// previous contents are replaced with the `replacement`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    /// The span of the previous contents that is replaced.
+    pub span: Span,
+    /// The text that replaces the contents within the span.
+    pub replacement: String,
+}
+
+impl TextEdit {
+    /// Create a new [TextEdit] from a [Span] and the replacement text.
+    pub fn new(span: Span, replacement: impl Into<String>) -> Self {
+        Self { span, replacement: replacement.into() }
+    }
+
+    /// Apply the edit to the given contents.
+    pub fn apply(&self, contents: &str) -> String {
+        let mut result = String::with_capacity(contents.len() + self.replacement.len());
+        result.push_str(&contents[..self.span.start()]);
+        result.push_str(&self.replacement);
+        result.push_str(&contents[self.span.end()..]);
+        result
+    }
+
+    /// The change in the length of the contents after the edit is applied.
+    fn delta(&self) -> isize {
+        self.replacement.len() as isize - self.span.size() as isize
+    }
+}
+
+/// The complete result of lexing a source, this is the input and output of
+/// [Lexer::relex].
+#[derive(Debug)]
+pub struct LexedSource {
+    /// The top-level token stream.
+    pub tokens: Vec<Token>,
+    /// The token trees that are referenced by [TokenKind::Tree] and
+    /// [TokenKind::InterpolatedStrLit] tokens.
+    pub token_trees: Vec<Vec<Token>>,
+    /// All of the errors that were encountered whilst lexing.
+    pub errors: Vec<LexerError>,
+    /// All of the warnings that were encountered whilst lexing.
+    pub warnings: Vec<LexerWarning>,
+    /// The trivia of the tokens, see [Lexer::trivia].
+    pub trivia: TriviaMap,
+    /// Trivia at the end of the contents, see [Lexer::eof_trivia].
+    pub eof_trivia: Vec<Trivia>,
+}
+
+impl<'a> Lexer<'a> {
+    /// Tokenise the whole of the contents into a [LexedSource].
+    pub fn lex_source(mut self) -> LexedSource {
+        let (tokens, errors) = self.tokenise();
+
+        LexedSource {
+            tokens,
+            token_trees: self.token_trees,
+            errors,
+            warnings: self.warnings,
+            trivia: self.trivia,
+            eof_trivia: self.dangling_trivia,
+        }
+    }
+
+    /// Re-tokenise the contents after the given [TextEdit] was applied to the
+    /// contents that `previous` was produced from. The contents of the
+    /// [Lexer] must be the contents after the edit.
+    ///
+    /// Only the smallest token tree that encloses the edit is re-lexed, the
+    /// remaining tokens are shifted to account for the edit. If the edit
+    /// isn't enclosed within a token tree, or it changes where the tree is
+    /// closed, then the whole of the contents is lexed again. In either case,
+    /// the result is the same as if the contents were lexed from scratch.
+    pub fn relex(mut self, previous: LexedSource, edit: &TextEdit) -> LexedSource {
+        let path = previous.enclosing_trees(edit.span);
+        let (tree_span, delimiter) = match path.last() {
+            Some(&target) => target,
+            None => return self.lex_source(),
+        };
+
+        let shift = Shift { end: tree_span.end(), delta: edit.delta() };
+
+        // If the previous tree wasn't closed, then we can't be sure where the re-lexed
+        // tree should end
+        let was_unclosed = previous.errors.iter().any(|err| match err.kind {
+            LexerErrorKind::Unclosed(_) => err.span.start() == tree_span.start(),
+            LexerErrorKind::MismatchedDelimiter { .. } => {
+                err.labels.iter().any(|(span, _)| span.start() == tree_span.start())
+            }
+            _ => false,
+        });
+
+        self.offset.set(tree_span.start() + 1);
+        let kind = self.eat_token_tree(delimiter);
+
+        if was_unclosed
+            || self.offset.get() != shift.offset(tree_span.end())
+            || self.tree_close != Some(TreeClose::Closed)
+        {
+            self.offset.set(0);
+            self.tree_close = None;
+            self.token_trees.clear();
+            self.errors.clear();
+            self.warnings.clear();
+            self.trivia = TriviaMap::new();
+            self.dangling_trivia.clear();
+
+            return self.lex_source();
+        }
+
+        let LexedSource { tokens, token_trees, errors, warnings, trivia, eof_trivia } = previous;
+
+        // Splice the errors of the re-lexed tree in place of the previous ones, the
+        // errors that are reported after the tree are either after it, or are
+        // reported for the enclosing trees not being closed.
+        let mut before = vec![];
+        let mut after = vec![];
+
+        for mut err in errors {
+            let span = err.span;
+
+            if span.start() > tree_span.start() && span.end() < tree_span.end() {
+                continue;
+            }
+
+            let is_after = span.start() >= tree_span.end()
+                || matches!(err.kind, LexerErrorKind::Unclosed(_))
+                    && path.iter().any(|(ancestor, _)| ancestor.start() == span.start());
+
+            if is_after || !after.is_empty() {
+                err.span = shift.span(span);
+                after.push(err);
+            } else {
+                before.push(err);
+            }
+        }
+
+        // Warnings are only reported for identifiers, so they are ordered by their
+        // position
+        let (before_warnings, after_warnings): (Vec<_>, Vec<_>) = warnings
+            .into_iter()
+            .filter(|warning| {
+                warning.span.end() <= tree_span.start() || warning.span.start() >= tree_span.end()
+            })
+            .partition(|warning| warning.span.end() <= tree_span.start());
+
+        let warnings = before_warnings
+            .into_iter()
+            .chain(std::mem::take(&mut self.warnings))
+            .chain(after_warnings.into_iter().map(|mut warning| {
+                warning.span = shift.span(warning.span);
+                warning
+            }))
+            .collect();
+
+        let mut rebuilder = Rebuilder {
+            shift,
+            target: tree_span,
+            relexed: Some((kind, std::mem::take(&mut self.dangling_trivia))),
+            previous_trees: token_trees,
+            relexed_trees: std::mem::take(&mut self.token_trees),
+            token_trees: vec![],
+            previous_trivia: trivia,
+            trivia: self.take_trivia(),
+        };
+        let tokens = rebuilder.rebuild(tokens, false);
+
+        before.append(&mut self.errors);
+        before.append(&mut after);
+
+        LexedSource {
+            tokens,
+            token_trees: rebuilder.token_trees,
+            errors: before,
+            warnings,
+            trivia: rebuilder.trivia,
+            eof_trivia: eof_trivia.into_iter().map(|trivia| shift.trivia(trivia)).collect(),
+        }
+    }
+}
+
+impl LexedSource {
+    /// Find the token trees that enclose the given [Span], from the outermost
+    /// to the innermost tree. A tree only encloses the span if the span is
+    /// strictly within the delimiters of the tree.
+    fn enclosing_trees(&self, span: Span) -> Vec<(Span, Delimiter)> {
+        let mut path = vec![];
+        let mut stream = self.tokens.as_slice();
+
+        'search: loop {
+            for token in stream {
+                if token.span.start() >= span.start() || span.end() >= token.span.end() {
+                    continue;
+                }
+
+                match token.kind {
+                    TokenKind::Tree(delimiter, index) => {
+                        path.push((token.span, delimiter));
+                        stream = &self.token_trees[index];
+                    }
+                    TokenKind::InterpolatedStrLit(index) => {
+                        stream = &self.token_trees[index];
+                    }
+                    _ => break 'search,
+                }
+
+                continue 'search;
+            }
+
+            break;
+        }
+
+        path
+    }
+}
+
+/// The shift of offsets that occurs after an edit within a token tree, any
+/// offset at or after the end of the tree is moved by the change in length
+/// of the contents.
+#[derive(Clone, Copy)]
+struct Shift {
+    end: usize,
+    delta: isize,
+}
+
+impl Shift {
+    fn offset(&self, offset: usize) -> usize {
+        if offset >= self.end {
+            (offset as isize + self.delta) as usize
+        } else {
+            offset
+        }
+    }
+
+    fn span(&self, span: Span) -> Span {
+        Span::new(self.offset(span.start()), self.offset(span.end()))
+    }
+
+    fn trivia(&self, trivia: Trivia) -> Trivia {
+        Trivia::new(trivia.kind, self.span(trivia.span))
+    }
+}
+
+/// Re-builds the token stream of a [LexedSource] after a tree has been
+/// re-lexed. The token trees are re-numbered in the same order as the
+/// [Lexer] would produce them, which is that the inner trees of a tree come
+/// before it.
+struct Rebuilder {
+    shift: Shift,
+    /// The span of the tree that was re-lexed, in the previous contents.
+    target: Span,
+    /// The kind of the re-lexed tree and the dangling trivia within it.
+    relexed: Option<(TokenKind, Vec<Trivia>)>,
+    previous_trees: Vec<Vec<Token>>,
+    relexed_trees: Vec<Vec<Token>>,
+    /// The re-numbered token trees.
+    token_trees: Vec<Vec<Token>>,
+    previous_trivia: TriviaMap,
+    /// The trivia of the re-built tokens, this starts with the trivia of the
+    /// re-lexed tokens.
+    trivia: TriviaMap,
+}
+
+impl Rebuilder {
+    /// Re-build the given tokens, which are either from the previous token
+    /// stream or from the re-lexed tree.
+    fn rebuild(&mut self, tokens: Vec<Token>, relexed: bool) -> Vec<Token> {
+        tokens.into_iter().map(|token| self.rebuild_token(token, relexed)).collect()
+    }
+
+    fn rebuild_token(&mut self, mut token: Token, relexed: bool) -> Token {
+        // Tokens that were re-lexed don't need to be shifted
+        if !relexed {
+            let shift = self.shift;
+            let is_target =
+                token.span == self.target && matches!(token.kind, TokenKind::Tree(_, _));
+            let trivia = self.previous_trivia.remove(&token);
+            token.span = shift.span(token.span);
+
+            if is_target {
+                let (kind, dangling) = self.relexed.take().unwrap();
+                token.kind = kind;
+
+                // Only the trailing trivia of the tree is after the tree
+                if let Some(mut trivia) = trivia {
+                    for item in &mut trivia.trailing {
+                        *item = shift.trivia(*item);
+                    }
+
+                    trivia.dangling = dangling;
+                    self.trivia.insert(&token, trivia);
+                }
+
+                return self.rebuild_tree(token, true);
+            }
+
+            if let Some(mut trivia) = trivia {
+                for item in trivia
+                    .leading
+                    .iter_mut()
+                    .chain(&mut trivia.trailing)
+                    .chain(&mut trivia.dangling)
+                {
+                    *item = shift.trivia(*item);
+                }
+
+                self.trivia.insert(&token, trivia);
+            }
+        }
+
+        self.rebuild_tree(token, relexed)
+    }
+
+    /// Re-build the tree that the token refers to, if the token is a tree,
+    /// and re-number the index of the tree within the token.
+    fn rebuild_tree(&mut self, mut token: Token, relexed: bool) -> Token {
+        let index = match &mut token.kind {
+            TokenKind::Tree(_, index) | TokenKind::InterpolatedStrLit(index) => index,
+            _ => return token,
+        };
+
+        let trees = if relexed { &mut self.relexed_trees } else { &mut self.previous_trees };
+        let children = std::mem::take(&mut trees[*index]);
+        let children = self.rebuild(children, relexed);
+
+        self.token_trees.push(children);
+        *index = self.token_trees.len() - 1;
+
+        token
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hash_source::{InteractiveId, SourceId};
+
+    fn source_id() -> SourceId {
+        SourceId::Interactive(InteractiveId::default())
+    }
+
+    /// Check that re-lexing the contents after an edit yields the same result
+    /// as lexing the edited contents from scratch.
+    fn assert_relex_matches(contents: &str, edit: TextEdit, trivia: bool) {
+        let lexer = |contents| {
+            let lexer = Lexer::new(contents, source_id());
+            if trivia {
+                lexer.with_trivia()
+            } else {
+                lexer
+            }
+        };
+
+        let edited = edit.apply(contents);
+        let previous = lexer(contents).lex_source();
+        let expected = lexer(&edited).lex_source();
+        let actual = lexer(&edited).relex(previous, &edit);
+
+        // Literals are interned on each lex, so they are compared by their value
+        let tokens = |tokens: &[Token], trivia: &TriviaMap| {
+            tokens
+                .iter()
+                .map(|token| {
+                    let kind = match token.kind {
+                        TokenKind::Tree(_, _) | TokenKind::InterpolatedStrLit(_) => {
+                            format!("{:?}", token.kind)
+                        }
+                        kind => kind.to_string(),
+                    };
+
+                    (kind, token.span, trivia.get(token).cloned())
+                })
+                .collect::<Vec<_>>()
+        };
+        let trees = |source: &LexedSource| {
+            source.token_trees.iter().map(|tree| tokens(tree, &source.trivia)).collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            tokens(&actual.tokens, &actual.trivia),
+            tokens(&expected.tokens, &expected.trivia)
+        );
+        assert_eq!(trees(&actual), trees(&expected));
+        assert_eq!(actual.trivia, expected.trivia);
+        assert_eq!(actual.eof_trivia, expected.eof_trivia);
+
+        let errors = |source: &LexedSource| {
+            source.errors.iter().map(|err| (err.span, err.to_string())).collect::<Vec<_>>()
+        };
+        assert_eq!(errors(&actual), errors(&expected));
+
+        let warnings = |source: &LexedSource| {
+            source
+                .warnings
+                .iter()
+                .map(|warning| (warning.span, warning.to_string()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(warnings(&actual), warnings(&expected));
+    }
+
+    /// Create an edit that replaces the first occurrence of `from` in the
+    /// contents with `to`.
+    fn replace(contents: &str, from: &str, to: &str) -> TextEdit {
+        let start = contents.find(from).unwrap();
+        TextEdit::new(Span::new(start, start + from.len()), to)
+    }
+
+    #[test]
+    fn relex_within_tree() {
+        let contents = "a := (1, [2, 3]);\nb := { c := 4; (d) };\ne := [5];";
+
+        for (from, to) in [("2", "22 + x"), ("c := 4", "c"), (", 3", ""), ("d", "f\"{d}\"")] {
+            assert_relex_matches(contents, replace(contents, from, to), false);
+            assert_relex_matches(contents, replace(contents, from, to), true);
+        }
+    }
+
+    #[test]
+    fn relex_changing_tree_structure() {
+        let contents = "a := (1, [2, 3]);\nb := { c };";
+
+        // Closing the tree early or an unclosed string change the rest of the stream
+        for (from, to) in [("2", "2)"), ("2", "\"2"), ("c", "{ c"), (":=", "=")] {
+            assert_relex_matches(contents, replace(contents, from, to), false);
+        }
+    }
+
+    #[test]
+    fn relex_with_errors() {
+        let contents = "a := (1x, [2, 'ab']);\nb := { 0b; @ };\nc := (1.";
+
+        for (from, to) in [("'ab'", "'a'"), ("2", "2u1"), ("@", "1"), ("0b", "0b1")] {
+            assert_relex_matches(contents, replace(contents, from, to), false);
+        }
+    }
+
+    #[test]
+    fn relex_with_warnings() {
+        let contents = "pаss := (fооd, [nаme]);\nb := (а);";
+
+        for (from, to) in [("fооd", "food"), ("[", "[fооd, "), ("nаme", "name")] {
+            assert_relex_matches(contents, replace(contents, from, to), false);
+        }
+    }
+
+    #[test]
+    fn relex_interpolated_string() {
+        let contents = "a := (f\"value {x + 1} and {(y)}\");";
+
+        for (from, to) in [("x + 1", "x"), ("y", "y, z"), ("and", "or \\{")] {
+            assert_relex_matches(contents, replace(contents, from, to), true);
+        }
+    }
+}
@@ -2,21 +2,47 @@
 
 #![feature(cell_update)]
 
-use error::{LexerError, LexerErrorKind, LexerErrorWrapper, LexerResult};
-use hash_source::{identifier::CORE_IDENTIFIERS, location::Span, SourceId};
-use hash_token::{delimiter::Delimiter, keyword::Keyword, Token, TokenKind};
+use error::{LexerError, LexerErrorKind, LexerResult, LexerWarning, LexerWarningKind};
+use hash_source::{
+    constant::{FloatConstantValue, FloatTy, IntConstantValue, IntTy},
+    identifier::CORE_IDENTIFIERS,
+    location::Span,
+    SourceId,
+};
+use hash_token::{
+    delimiter::Delimiter,
+    keyword::Keyword,
+    trivia::{DocCommentKind, TokenTrivia, Trivia, TriviaKind, TriviaMap},
+    Token, TokenKind,
+};
+use num_bigint::BigInt;
 use std::{cell::Cell, iter};
-use utils::is_id_start;
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+use utils::{ascii_lookalike, find_mixed_script_char, is_id_start, strip_indentation};
 
 use crate::utils::is_id_continue;
 
 pub mod error;
+pub mod incremental;
 mod utils;
 
 /// Representing the end of stream, or the initial character that is set as
 /// 'prev' in a [Lexer] since there is no character before the start.
 const EOF_CHAR: char = '\0';
 
+/// The way that a token tree was ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TreeClose {
+    /// The tree was closed with the matching delimiter.
+    Closed,
+    /// The tree was ended by a closing delimiter that doesn't match the
+    /// opening delimiter of the tree, this includes the [Span] of the closing
+    /// delimiter.
+    Mismatched(Delimiter, Span),
+    /// The end of the contents was reached before the tree was closed.
+    Unclosed,
+}
+
 /// The [Lexer] is a representation of the source in tokens that can be turned
 /// into the AST. The [Lexer] has methods that can parse various token types
 /// from the source, transform the entire contents into a vector of tokens and
@@ -31,13 +57,45 @@ pub struct Lexer<'a> {
     /// Representative module index of the current source.
     source_id: SourceId,
 
-    /// Representing the last character the lexer encountered. This is only set
-    /// by [Lexer::advance_token] so that [Lexer::eat_token_tree] can perform a
-    /// check on if the token tree was closed up.
-    // @@Cleanup: We could use a delimiter stack to automatically guarantee this?
-    previous_delimiter: Cell<Option<char>>,
+    /// The delimiters of the token trees that are currently being lexed, along
+    /// with the [Span] of the opening delimiter.
+    delimiter_stack: Vec<(Delimiter, Span)>,
+
+    /// How the innermost token tree that is being lexed was ended. This is set
+    /// by [Lexer::advance_token] when it encounters a closing delimiter, and
+    /// after [Lexer::eat_token_tree] returns, it represents how that tree was
+    /// ended.
+    tree_close: Option<TreeClose>,
 
     token_trees: Vec<Vec<Token>>,
+
+    /// Any errors that were encountered whilst lexing the contents. The lexer
+    /// doesn't stop at the first error, instead it produces a
+    /// [TokenKind::Err] in place of the malformed token, records the error
+    /// here and continues lexing.
+    errors: Vec<LexerError>,
+
+    /// Whether a literal reached the end of the contents without being
+    /// terminated. Any token trees that enclose the literal are then also
+    /// unclosed, but this isn't reported since the literal is already reported
+    /// as unterminated.
+    unterminated_literal: bool,
+
+    /// Any warnings that were encountered whilst lexing the contents, such as
+    /// identifiers that could be confused with other identifiers.
+    warnings: Vec<LexerWarning>,
+
+    /// Whether comments and whitespace should be preserved and recorded as the
+    /// [TokenTrivia] of the produced tokens.
+    preserve_trivia: bool,
+
+    /// The [TokenTrivia] of the produced tokens, this is only populated when
+    /// the lexer is preserving trivia.
+    trivia: TriviaMap,
+
+    /// Trivia that was consumed before reaching the end of a token tree or the
+    /// end of the contents, and hence isn't attached to any token.
+    dangling_trivia: Vec<Trivia>,
 }
 
 impl<'a> Lexer<'a> {
@@ -46,25 +104,83 @@ impl<'a> Lexer<'a> {
         Lexer {
             offset: Cell::new(0),
             source_id,
-            previous_delimiter: Cell::new(None),
+            delimiter_stack: vec![],
+            tree_close: None,
             contents,
             token_trees: vec![],
+            errors: vec![],
+            unterminated_literal: false,
+            warnings: vec![],
+            preserve_trivia: false,
+            trivia: TriviaMap::new(),
+            dangling_trivia: vec![],
         }
     }
 
+    /// Make the [Lexer] preserve comments and whitespace, recording them as
+    /// the [TokenTrivia] of the produced tokens. Trivia on the same line after
+    /// a token is attached to it as trailing trivia, any other trivia is
+    /// attached to the following token as leading trivia.
+    pub fn with_trivia(mut self) -> Self {
+        self.preserve_trivia = true;
+        self
+    }
+
+    /// Returns the trivia at the end of the contents that isn't followed by
+    /// any token. This is only populated once the lexer has reached the end
+    /// of the contents, and when the lexer is preserving trivia.
+    pub fn eof_trivia(&self) -> &[Trivia] {
+        &self.dangling_trivia
+    }
+
+    /// Returns the [TokenTrivia] of the tokens that have been produced so far,
+    /// see [Lexer::with_trivia].
+    pub fn trivia(&self) -> &TriviaMap {
+        &self.trivia
+    }
+
+    /// Take the [TokenTrivia] of the tokens that have been produced so far.
+    pub fn take_trivia(&mut self) -> TriviaMap {
+        std::mem::take(&mut self.trivia)
+    }
+
     /// Returns a reference to the stored token trees for the current job
     pub fn into_token_trees(self) -> Vec<Vec<Token>> {
         self.token_trees
     }
 
-    /// Tokenise the given input stream
-    pub fn tokenise(&mut self) -> Result<Vec<Token>, LexerErrorWrapper> {
-        let iter = std::iter::from_fn(|| self.advance_token().transpose());
+    /// Take the warnings that have been encountered by the lexer so far.
+    pub fn take_warnings(&mut self) -> Vec<LexerWarning> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// Returns the errors that have been encountered by the lexer so far.
+    pub fn errors(&self) -> &[LexerError] {
+        &self.errors
+    }
 
-        iter.collect::<Result<_, _>>().map_err(|err| LexerErrorWrapper(self.source_id, err))
+    /// Tokenise the given input stream. Lexing errors do not stop the
+    /// tokenisation of the input, the malformed token is replaced with
+    /// a [TokenKind::Err] token and the lexer carries on. All of the errors
+    /// that were encountered are returned alongside the token stream.
+    pub fn tokenise(&mut self) -> (Vec<Token>, Vec<LexerError>) {
+        let tokens = std::iter::from_fn(|| self.advance_token()).collect();
+
+        (tokens, std::mem::take(&mut self.errors))
+    }
+
+    /// Get the [SourceId] of the contents that are being lexed.
+    pub fn source_id(&self) -> SourceId {
+        self.source_id
+    }
 
-        // Row::try_from_iter(iter, wall).map_err(|err|
-        // LexerErrorWrapper(self.source_id, err))
+    /// Record the given [LexerError] and yield a [TokenKind::Err] in its place
+    /// so that lexing can continue.
+    fn recover(&mut self, result: LexerResult<TokenKind>) -> TokenKind {
+        result.unwrap_or_else(|err| {
+            self.errors.push(err);
+            TokenKind::Err
+        })
     }
 
     /// Returns amount of already consumed symbols.
@@ -120,43 +236,20 @@ impl<'a> Lexer<'a> {
         self.offset.update(|x| x + ch.len_utf8());
     }
 
-    /// Checks if there is nothing more to consume.
-    fn is_eof(&self) -> bool {
-        self.contents.len() == self.len_consumed()
-    }
-
-    /// Parses a token from the input string.
-    pub fn advance_token(&mut self) -> LexerResult<Option<Token>> {
+    /// Parses a token from the input string. If the token is malformed, the
+    /// error is recorded and a [TokenKind::Err] token is produced instead.
+    pub fn advance_token(&mut self) -> Option<Token> {
         // Eat any comments or whitespace before processing the token...
-        loop {
-            match self.peek() {
-                c if c.is_whitespace() => self.eat_while_and_discard(char::is_whitespace),
-                '/' => match self.peek_second() {
-                    '*' => self.block_comment(),
-                    '/' => self.line_comment(),
-                    _ => {
-                        self.skip();
-
-                        let offset = self.offset.get();
-
-                        // @@Hack: since we already compare if the first item is a slash, we'll just
-                        // return here the slash and advance it by one.
-                        return Ok(Some(Token::new(
-                            TokenKind::Slash,
-                            Span::new(offset, offset + 1),
-                        )));
-                    }
-                },
-                _ => break,
-            }
-        }
+        let leading = self.eat_trivia(false);
 
         let offset = self.offset.get();
-        let next_token = self.next();
-
-        if next_token.is_none() {
-            return Ok(None);
-        }
+        let next_token = match self.next() {
+            Some(ch) => ch,
+            None => {
+                self.dangling_trivia = leading;
+                return None;
+            }
+        };
 
         // We avoid checking if the tokens are compound here because we don't really
         // want to deal with comments and spaces in an awkward way... Once the
@@ -175,7 +268,7 @@ impl<'a> Lexer<'a> {
         // between the colons, this might be problematic. Essentially, we pass
         // the responsibility of forming more compound tokens to AST gen rather than
         // here.
-        let token_kind = match next_token.unwrap() {
+        let token_kind = match next_token {
             // One-symbol tokens
             '~' => TokenKind::Tilde,
             '=' => TokenKind::Eq,
@@ -196,78 +289,274 @@ impl<'a> Lexer<'a> {
             '#' => TokenKind::Hash,
             '$' => TokenKind::Dollar,
             '?' => TokenKind::Question,
+            '/' => TokenKind::Slash,
 
             // Consume a token tree, which is a starting delimiter, followed by a an arbitrary
             // number of tokens and closed by a following delimiter...
-            ch @ ('(' | '{' | '[') => self.eat_token_tree(Delimiter::from_left(ch).unwrap())?,
+            ch @ ('(' | '{' | '[') => self.eat_token_tree(Delimiter::from_left(ch).unwrap()),
+
+            // Raw string literal, e.g. `r"..."` or `r#"..."#`
+            'r' if self.is_raw_str_start() => self.raw_string(),
+            // Byte string literal, e.g. `b"..."`
+            'b' if self.peek() == '"' => {
+                self.skip(); // skip the opening quote
+                self.byte_string()
+            }
+            // Interpolated string literal, e.g. `f"..."`
+            'f' if self.peek() == '"' => {
+                self.skip(); // skip the opening quote
+                self.string(true)
+            }
 
             // Identifier (this should be checked after other variant that can
             // start as identifier).
             ch if is_id_start(ch) => self.ident(ch),
             // Numeric literal.
-            ch @ '0'..='9' => self.number(ch)?,
+            ch @ '0'..='9' => {
+                let result = self.number(ch);
+                self.recover(result)
+            }
             // character literal.
-            '\'' => self.char()?,
+            '\'' => {
+                let result = self.char();
+                self.recover(result)
+            }
+            // Multi-line string literal.
+            '"' if self.peek() == '"' && self.peek_second() == '"' => {
+                self.skip();
+                self.skip();
+                self.multi_line_string()
+            }
             // String literal.
-            '"' => self.string()?,
+            '"' => self.string(false),
 
             // We have to exit the current tree if we encounter a closing delimiter...
             ch @ (')' | '}' | ']') => {
-                self.previous_delimiter.set(Some(ch));
-
-                return Ok(None);
+                let delimiter = Delimiter::from_right(ch).unwrap();
+                let span = Span::new(offset, self.len_consumed());
+
+                match self.delimiter_stack.last() {
+                    // A closing delimiter that doesn't belong to any tree
+                    None => {
+                        self.errors.push(LexerError::new(
+                            None,
+                            LexerErrorKind::UnexpectedClosingDelimiter(delimiter),
+                            span,
+                        ));
+
+                        TokenKind::Delimiter(delimiter, false)
+                    }
+                    Some((open, _)) => {
+                        self.tree_close = Some(if *open == delimiter {
+                            TreeClose::Closed
+                        } else {
+                            // If the delimiter closes an enclosing tree, then it is left for
+                            // that tree to consume
+                            if self.delimiter_stack.iter().any(|(open, _)| *open == delimiter) {
+                                self.offset.set(offset);
+                            }
+
+                            TreeClose::Mismatched(delimiter, span)
+                        });
+
+                        self.dangling_trivia = leading;
+                        return None;
+                    }
+                }
             }
 
-            // We didn't get a hit on the right token...
-            ch => TokenKind::Unexpected(ch),
+            // We didn't get a hit on the right token, we still produce the token so
+            // that it can be used in the token stream, but also report it.
+            ch => {
+                self.errors.push(LexerError::new(
+                    None,
+                    LexerErrorKind::Unexpected(ch),
+                    Span::new(offset, self.len_consumed()),
+                ));
+
+                TokenKind::Unexpected(ch)
+            }
         };
 
         let location = Span::new(offset, self.len_consumed());
-        Ok(Some(Token::new(token_kind, location)))
+        let token = Token::new(token_kind, location);
+
+        if !self.preserve_trivia {
+            return Some(token);
+        }
+
+        // The trivia before the closing delimiter of a token tree is left over
+        // from lexing the inside of the tree
+        let dangling = match token_kind {
+            TokenKind::Tree(_, _) => std::mem::take(&mut self.dangling_trivia),
+            _ => vec![],
+        };
+        let trailing = self.eat_trivia(true);
+
+        self.trivia.insert(&token, TokenTrivia { leading, trailing, dangling });
+        Some(token)
+    }
+
+    /// Consume any comments and whitespace, yielding the consumed [Trivia] if
+    /// the lexer is preserving trivia. If the trivia is `trailing`, then only
+    /// trivia up to the end of the current line is consumed.
+    fn eat_trivia(&self, trailing: bool) -> Vec<Trivia> {
+        let mut trivia = vec![];
+
+        loop {
+            let start = self.offset.get();
+
+            let kind = match self.peek() {
+                '\n' if trailing => break,
+                c if c.is_whitespace() => {
+                    if trailing {
+                        self.eat_while_and_discard(|c| c.is_whitespace() && c != '\n');
+                    } else {
+                        self.eat_while_and_discard(char::is_whitespace);
+                    }
+
+                    TriviaKind::Whitespace
+                }
+                '/' => match self.peek_second() {
+                    '*' => {
+                        self.block_comment();
+                        TriviaKind::BlockComment
+                    }
+                    '/' => self.line_comment(),
+                    _ => break,
+                },
+                _ => break,
+            };
+
+            if self.preserve_trivia {
+                trivia.push(Trivia::new(kind, Span::new(start, self.offset.get())));
+            }
+        }
+
+        trivia
     }
 
     /// This will essentially recursively consume tokens until it reaches the
     /// right hand-side variant of the provided delimiter. If no delimiter
     /// is reached, but the stream has reached EOF, this is reported
-    /// as an error because it is essentially an un-closed block. This kind of
-    /// behaviour is desired and avoids performing complex delimiter depth
-    /// analysis later on.
-    fn eat_token_tree(&mut self, delimiter: Delimiter) -> LexerResult<TokenKind> {
+    /// as an error because it is essentially an un-closed block. If the tree
+    /// is ended by a different closing delimiter, then this is reported as a
+    /// mismatched delimiter with the locations of both delimiters. Even if the
+    /// tree isn't closed, the tree is still produced so that the rest of the
+    /// stream can be used.
+    fn eat_token_tree(&mut self, delimiter: Delimiter) -> TokenKind {
         let mut children_tokens = vec![];
         let start = self.offset.get() - 1; // we need to ge the previous location to accurately denote the error...
+        let open = Span::new(start, start + 1);
 
-        // we need to reset self.prev here as it might be polluted with previous token
-        // trees
-        self.previous_delimiter.set(None);
+        self.delimiter_stack.push((delimiter, open));
 
-        while !self.is_eof() {
-            // @@ErrorReporting: Option here doesn't just mean EOF, it could also be that
-            // the next token failed to be parsed.
-            match self.advance_token()? {
+        let close = loop {
+            // Reset the state since it might be polluted by inner trees
+            self.tree_close = None;
+
+            match self.advance_token() {
                 Some(token) => children_tokens.push(token),
-                None => break,
+                None => break self.tree_close.unwrap_or(TreeClose::Unclosed),
             };
+        };
+
+        self.delimiter_stack.pop();
+        self.tree_close = Some(close);
+
+        match close {
+            TreeClose::Closed => {}
+            TreeClose::Mismatched(closing, span) => {
+                let err = LexerError::new(
+                    None,
+                    LexerErrorKind::MismatchedDelimiter { open: delimiter, close: closing },
+                    span,
+                )
+                .with_label(open, "unclosed delimiter")
+                .with_label(span, "mismatched closing delimiter");
+
+                self.errors.push(self.with_closing_suggestion(err, delimiter, open, span.start()));
+            }
+            TreeClose::Unclosed if self.unterminated_literal => {}
+            TreeClose::Unclosed => {
+                let err = LexerError::new(None, LexerErrorKind::Unclosed(delimiter), open);
+                self.errors.push(self.with_closing_suggestion(
+                    err,
+                    delimiter,
+                    open,
+                    self.contents.len(),
+                ));
+            }
+        }
+
+        // push this to the token_trees and get the current index to use instead...
+        self.token_trees.push(children_tokens);
+        TokenKind::Tree(delimiter, self.token_trees.len() - 1)
+    }
+
+    /// Suggest where the closing delimiter of a tree that was opened at `open`
+    /// is likely missing, based on the indentation of the lines up until `end`.
+    /// The first line that is indented at most as much as the line of the
+    /// opening delimiter is likely no longer within the tree, so the
+    /// closing delimiter is suggested at the end of the line before it.
+    fn with_closing_suggestion(
+        &self,
+        err: LexerError,
+        delimiter: Delimiter,
+        open: Span,
+        end: usize,
+    ) -> LexerError {
+        let indentation = |line: &str| line.len() - line.trim_start().len();
+
+        let line_start = self.contents[..open.start()].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = self.contents[open.start()..]
+            .find('\n')
+            .map_or(self.contents.len(), |idx| idx + open.start());
+
+        if line_end >= end {
+            return err;
         }
 
-        match self.previous_delimiter.get() {
-            Some(delim) if delim == delimiter.right() => {
-                // push this to the token_trees and get the current index to use instead...
-                self.token_trees.push(children_tokens);
+        let open_indentation = indentation(&self.contents[line_start..line_end]);
+
+        // The end of the last line that is within the tree
+        let mut last_end = line_end;
+        let mut offset = line_end + 1;
+
+        for line in self.contents[line_end + 1..end].split_inclusive('\n') {
+            let content = line.trim_end();
+
+            if !content.is_empty() {
+                if indentation(content) <= open_indentation {
+                    let last = self.contents[..last_end].trim_end();
+                    let last_char = last.chars().next_back().map_or(1, char::len_utf8);
 
-                Ok(TokenKind::Tree(delimiter, self.token_trees.len() - 1))
+                    return err.with_help(
+                        Span::new(last.len() - last_char, last.len()),
+                        format!(
+                            "`{}` might be missing here, the next line is not indented within the `{}`",
+                            delimiter.right(),
+                            delimiter.left()
+                        ),
+                    );
+                }
+
+                last_end = offset + content.len();
             }
-            _ => Err(LexerError::new(
-                None,
-                LexerErrorKind::Unclosed(delimiter),
-                Span::new(start, start + 1),
-            )),
+
+            offset += line.len();
         }
+
+        err
     }
 
     /// Consume an identifier, at this stage keywords are also considered to be
     /// identifiers. The function expects that the first character of the
-    /// identifier is consumed when the function is called.
-    fn ident(&self, first: char) -> TokenKind {
+    /// identifier is consumed when the function is called. Identifiers that
+    /// contain non-ASCII characters are NFC normalised, and are checked for
+    /// characters that are mixed from multiple scripts or look like ASCII
+    /// characters.
+    fn ident(&mut self, first: char) -> TokenKind {
         debug_assert!(is_id_start(first));
 
         let start = self.offset.get() - first.len_utf8();
@@ -313,7 +602,43 @@ impl<'a> Lexer<'a> {
                 TokenKind::Keyword(Keyword::Set)
             }
             "_" => TokenKind::Ident(CORE_IDENTIFIERS.underscore),
-            _ => TokenKind::Ident(name.into()),
+            _ if name.is_ascii() => TokenKind::Ident(name.into()),
+            _ => {
+                self.check_unicode_ident(name, start);
+
+                if is_nfc(name) {
+                    TokenKind::Ident(name.into())
+                } else {
+                    TokenKind::Ident(name.nfc().collect::<String>().into())
+                }
+            }
+        }
+    }
+
+    /// Check if an identifier that contains non-ASCII characters mixes
+    /// characters from multiple scripts, or if it could be confused with an
+    /// ASCII identifier, reporting a [LexerWarning] at the offending
+    /// character.
+    fn check_unicode_ident(&mut self, name: &str, start: usize) {
+        let char_span =
+            |offset: usize, ch: char| Span::new(start + offset, start + offset + ch.len_utf8());
+
+        if let Some((offset, ch, script, other)) = find_mixed_script_char(name) {
+            let kind = LexerWarningKind::MixedScriptIdent {
+                ident: name.to_string(),
+                ch,
+                script,
+                other,
+                lookalike: ascii_lookalike(ch.encode_utf8(&mut [0; 4])),
+            };
+
+            self.warnings.push(LexerWarning::new(kind, char_span(offset, ch)));
+        } else if let Some(lookalike) = ascii_lookalike(name) {
+            // ##Safety: the identifier contains at least one non-ASCII character
+            let (offset, ch) = name.char_indices().find(|(_, ch)| !ch.is_ascii()).unwrap();
+            let kind = LexerWarningKind::ConfusableIdent { ident: name.to_string(), ch, lookalike };
+
+            self.warnings.push(LexerWarning::new(kind, char_span(offset, ch)));
         }
     }
 
@@ -339,20 +664,29 @@ impl<'a> Lexer<'a> {
             if let Some(radix) = maybe_radix {
                 self.skip(); // accounting for the radix
 
-                let chars = self.eat_decimal_digits(radix);
-                let value = u64::from_str_radix(chars, radix);
+                let digits = self
+                    .eat_decimal_digits(radix)
+                    .chars()
+                    .filter(|c| *c != '_')
+                    .collect::<String>();
 
-                // @@ErrorHandling: We shouldn't error here, this should be handled by the
-                // SmallVec<..> change to integers
-                if value.is_err() {
+                if digits.is_empty() {
                     return Err(LexerError::new(
-                        Some("Integer literal too large".to_string()),
+                        Some("Expected digits after the integer base prefix".to_string()),
                         LexerErrorKind::MalformedNumericalLit,
                         Span::new(start, self.offset.get()),
                     ));
                 }
 
-                return Ok(TokenKind::IntLit(value.unwrap()));
+                if radix == 16 {
+                    if let Some(float) = self.eat_hex_float(start)? {
+                        return Ok(float);
+                    }
+                }
+
+                // ##Safety: all of the digits have been checked to be valid for the radix.
+                let value = BigInt::parse_bytes(digits.as_bytes(), radix).unwrap();
+                return self.eat_int_suffix(value);
             }
         }
 
@@ -367,83 +701,166 @@ impl<'a> Lexer<'a> {
             // the form of '2.pow(...)' If we don't check this here, it leads to the
             // tokeniser being too greedy and eating the 'dot' without reason.
             // Admittedly, this is a slight ambiguity in the language syntax, but
-            // there isn't currently a clear way to resolve this ambiguity.
-            '.' if !is_id_start(self.peek_second()) => {
+            // there isn't currently a clear way to resolve this ambiguity. Similarly, the
+            // 'dot' isn't eaten if it begins a range such as '0..10'.
+            '.' if !is_id_start(self.peek_second()) && self.peek_second() != '.' => {
                 self.skip();
+                self.eat_decimal_digits(10);
+                self.eat_float_exponent(start, ['e', 'E'])?;
 
-                let after_digits = self.eat_decimal_digits(10);
-
-                let num = pre_digits
-                    .chain(std::iter::once('.'))
-                    .chain(after_digits.chars().filter(|c| *c != '_'));
-
-                self.eat_float_lit(num, start)
+                self.eat_float_suffix(start)
             }
             // Immediate exponent
-            'e' | 'E' => self.eat_float_lit(pre_digits, start),
+            'e' | 'E' => {
+                self.eat_float_exponent(start, ['e', 'E'])?;
+                self.eat_float_suffix(start)
+            }
+            // An integer literal with a float suffix, e.g. `1f32`, is a float literal
+            _ if self.peek_lit_suffix().and_then(FloatTy::from_suffix).is_some() => {
+                self.eat_float_suffix(start)
+            }
             _ => {
                 let digits = pre_digits.collect::<String>();
 
-                // @@TODO: Use our own parser for integers and floats instead of relying on
-                // rust's default one.
-                match digits.parse::<u64>() {
+                match digits.parse::<BigInt>() {
                     Err(err) => Err(LexerError::new(
                         Some(format!("{}.", err)),
                         LexerErrorKind::MalformedNumericalLit,
                         Span::new(start, self.offset.get()),
                     )),
-                    Ok(value) => Ok(TokenKind::IntLit(value)),
+                    Ok(value) => self.eat_int_suffix(value),
                 }
             }
         }
     }
 
-    /// Function to apply an exponent to a floating point literal.
-    fn eat_float_lit(
-        &self,
-        num: impl Iterator<Item = char>,
-        start: usize,
-    ) -> LexerResult<TokenKind> {
-        let num = num.collect::<String>().parse::<f64>();
-
-        match num {
-            Err(err) => Err(LexerError::new(
-                Some(format!("{}.", err)),
-                LexerErrorKind::MalformedNumericalLit,
-                Span::new(start, self.offset.get()),
-            )),
-            Ok(value) => {
-                let exp = self.eat_exponent(start)?;
+    /// Peek the suffix of a numerical literal if one is present, e.g. the `u8`
+    /// in `255u8`, without consuming it.
+    fn peek_lit_suffix(&self) -> Option<&str> {
+        let slice = unsafe { self.as_slice() };
+
+        if !is_id_start(self.peek()) {
+            return None;
+        }
+
+        let end = slice.find(|c| !is_id_continue(c)).unwrap_or(slice.len());
+        Some(&slice[..end])
+    }
 
-                // if an exponent was specified, as in it is non-zero, we need to apply the
-                // exponent to the float literal.
-                let value = if exp != 0 { value * 10f64.powi(exp) } else { value };
+    /// Consume the suffix of a numerical literal if one is present, yielding
+    /// the suffix and its [Span].
+    fn eat_lit_suffix(&self) -> Option<(&str, Span)> {
+        if !is_id_start(self.peek()) {
+            return None;
+        }
 
-                Ok(TokenKind::FloatLit(value))
+        let start = self.offset.get();
+        let suffix = self.eat_while_and_slice(is_id_continue);
+
+        Some((suffix, Span::new(start, self.offset.get())))
+    }
+
+    /// Consume an optional type suffix after an integer literal, for example
+    /// the `u8` in `255u8`, and produce the [TokenKind::IntLit]. If the suffix
+    /// isn't a known integer type, an error is reported.
+    fn eat_int_suffix(&self, value: BigInt) -> LexerResult<TokenKind> {
+        let suffix = match self.eat_lit_suffix() {
+            Some((suffix, span)) => match IntTy::from_suffix(suffix) {
+                Some(ty) => Some(ty),
+                None => {
+                    return Err(LexerError::new(
+                        None,
+                        LexerErrorKind::InvalidLitSuffix(suffix.to_string()),
+                        span,
+                    ))
+                }
+            },
+            None => None,
+        };
+
+        Ok(TokenKind::IntLit(IntConstantValue { value, suffix }.into()))
+    }
+
+    /// Consume an optional type suffix after a float literal, for example the
+    /// `f64` in `1.5f64`, and produce the [TokenKind::FloatLit]. The text of
+    /// the literal is taken to be everything from `start` up to the suffix.
+    /// If the suffix isn't a known float type, an error is reported.
+    fn eat_float_suffix(&self, start: usize) -> LexerResult<TokenKind> {
+        let text = self.contents[start..self.offset.get()].to_string();
+
+        let suffix = match self.eat_lit_suffix() {
+            Some((suffix, span)) => match FloatTy::from_suffix(suffix) {
+                Some(ty) => Some(ty),
+                None => {
+                    return Err(LexerError::new(
+                        None,
+                        LexerErrorKind::InvalidLitSuffix(suffix.to_string()),
+                        span,
+                    ))
+                }
+            },
+            None => None,
+        };
+
+        Ok(TokenKind::FloatLit(FloatConstantValue { text, suffix }.into()))
+    }
+
+    /// Attempt to continue lexing a hexadecimal integer literal as a float
+    /// literal, e.g. `0x1.8p3`. Hexadecimal float literals must have a binary
+    /// exponent, which disambiguates them from property accesses on integer
+    /// literals such as `0xff.foo()`. If the literal isn't a float, the lexer
+    /// is left at the end of the integer digits and [None] is returned.
+    fn eat_hex_float(&self, start: usize) -> LexerResult<Option<TokenKind>> {
+        let checkpoint = self.offset.get();
+
+        if self.peek() == '.' && self.peek_second().is_ascii_hexdigit() {
+            let is_property_access = is_id_start(self.peek_second());
+
+            self.skip(); // skip the `.`
+            self.eat_decimal_digits(16);
+
+            if !matches!(self.peek(), 'p' | 'P') {
+                if is_property_access {
+                    self.offset.set(checkpoint);
+                    return Ok(None);
+                }
+
+                return Err(LexerError::new(
+                    Some(
+                        "Hexadecimal float literals must have a binary exponent, e.g. `0x1.8p0`"
+                            .to_string(),
+                    ),
+                    LexerErrorKind::MalformedNumericalLit,
+                    Span::new(start, self.offset.get()),
+                ));
             }
         }
+
+        if !matches!(self.peek(), 'p' | 'P') {
+            return Ok(None);
+        }
+
+        self.eat_float_exponent(start, ['p', 'P'])?;
+        self.eat_float_suffix(start).map(Some)
     }
 
-    /// Consume an exponent for a float literal.
-    fn eat_exponent(&self, start: usize) -> LexerResult<i32> {
-        if !matches!(self.peek(), 'e' | 'E') {
-            // @@Hack: we return a zero to signal that there was no exponent and therefore
-            // avoid applying it later
-            return Ok(0);
+    /// Consume an exponent for a float literal, if one is present. The exponent
+    /// begins with one of the given markers, followed by an optional sign and
+    /// at least one decimal digit.
+    fn eat_float_exponent(&self, start: usize, markers: [char; 2]) -> LexerResult<()> {
+        if !markers.contains(&self.peek()) {
+            return Ok(());
         }
 
         self.skip(); // consume the exponent
 
         // Check if there is a sign before the digits start in the exponent...
-        let negated = if self.peek() == '-' {
+        if matches!(self.peek(), '-' | '+') {
             self.skip();
-            true
-        } else {
-            false
-        };
+        }
 
         // Check that there is at least on digit in the exponent
-        if self.peek() == EOF_CHAR {
+        if self.eat_decimal_digits(10).chars().all(|c| c == '_') {
             return Err(LexerError::new(
                 None,
                 LexerErrorKind::MissingExponentDigits,
@@ -451,15 +868,7 @@ impl<'a> Lexer<'a> {
             ));
         }
 
-        match self.eat_decimal_digits(10).parse::<i32>() {
-            Err(_) => Err(LexerError::new(
-                Some("Invalid float exponent.".to_string()),
-                LexerErrorKind::MalformedNumericalLit,
-                Span::new(start, self.offset.get() + 1),
-            )),
-            Ok(num) if negated => Ok(-num),
-            Ok(num) => Ok(num),
-        }
+        Ok(())
     }
 
     /// Consume only decimal digits up to encountering a non-decimal digit
@@ -473,7 +882,18 @@ impl<'a> Lexer<'a> {
     /// specified by the escape literal rules. More information about the
     /// escape sequences can be found at [escape sequences](https://hash-org.github.io/lang/basics/intro.html)
     fn char_from_escape_seq(&self) -> LexerResult<char> {
-        let c = self.next().unwrap();
+        let c = match self.next() {
+            Some(c) => c,
+            None => {
+                let offset = self.offset.get();
+
+                return Err(LexerError::new(
+                    Some("Expected an escape sequence after `\\`".to_string()),
+                    LexerErrorKind::BadEscapeSequence,
+                    Span::new(offset - 1, offset),
+                ));
+            }
+        };
 
         // we need to compute the old byte offset by accounting for both the 'u'
         // character and the '\\' character, but since this is known to be 2
@@ -481,6 +901,8 @@ impl<'a> Lexer<'a> {
         let start = self.offset.get() - 1;
         match c {
             '0' => Ok('\0'),
+            '{' => Ok('{'),
+            '}' => Ok('}'),
             'n' => Ok('\n'),
             't' => Ok('\t'),
             'u' => {
@@ -598,7 +1020,10 @@ impl<'a> Lexer<'a> {
             // char as the actual char by escaping it
             self.skip();
 
-            let ch = self.char_from_escape_seq()?;
+            let ch = self.char_from_escape_seq().map_err(|err| {
+                self.skip_char_lit_remainder();
+                err
+            })?;
             let next = self.peek();
 
             // eat the single quote after the character
@@ -615,6 +1040,8 @@ impl<'a> Lexer<'a> {
                     ));
                 }
 
+                self.skip_char_lit_remainder();
+
                 return Err(LexerError::new(
                     Some("Character literal can only contain one codepoint".to_string()),
                     LexerErrorKind::BadEscapeSequence,
@@ -631,33 +1058,91 @@ impl<'a> Lexer<'a> {
         // the reporting aspect, we want to eat up until the next `'` in order
         // to highlight the entire literal
         let lit = self.eat_while_and_slice(move |c| c != '\'' && !c.is_whitespace());
+        let end = self.offset.get() + 1;
+
+        // Eat the closing quote so that it doesn't begin another character literal
+        if self.peek() == '\'' {
+            self.skip();
+        }
 
         Err(LexerError::new(
             None,
             LexerErrorKind::InvalidCharacterLit(lit.to_string()),
-            Span::new(start, self.offset.get() + 1),
+            Span::new(start, end),
         ))
     }
 
+    /// Skip the rest of a malformed character literal up to and including the
+    /// closing quote, if it is present on the same line. This is so that the
+    /// remainder of the literal isn't lexed as separate tokens when the lexer
+    /// recovers from the error.
+    fn skip_char_lit_remainder(&self) {
+        self.eat_while_and_discard(|c| c != '\'' && c != '\n');
+
+        if self.peek() == '\'' {
+            self.skip();
+        }
+    }
+
     /// Consume a string literal provided that the current previous token is a
     /// double quote, this will produce a [TokenKind::StrLit] provided
     /// that the literal is correctly formed and is ended before the end of
-    /// file is reached.
-    fn string(&self) -> LexerResult<TokenKind> {
+    /// file is reached. Malformed escape sequences are reported, but the rest
+    /// of the literal is still consumed, producing a [TokenKind::Err].
+    ///
+    /// If the literal is `interpolated`, i.e. it is prefixed with an `f`, then
+    /// the contents after each un-escaped `{` up to the matching `}` are lexed
+    /// as a token tree and the literal is produced as a
+    /// [TokenKind::InterpolatedStrLit]. The segments of the literal are stored
+    /// in a token tree, where the string segments are [TokenKind::StrLit]s,
+    /// and the interpolated expressions are brace [TokenKind::Tree]s.
+    fn string(&mut self, mut interpolated: bool) -> TokenKind {
         let mut value = String::from("");
         let mut closed = false;
+        let mut malformed = false;
 
         let start = self.offset.get();
 
+        // The segments of the literal, only populated if the literal is interpolated
+        let mut segments = vec![];
+        let trees_start = self.token_trees.len();
+        let mut segment_start = if interpolated { start - 2 } else { start - 1 };
+
         while let Some(c) = self.next() {
             match c {
                 '"' => {
                     closed = true;
                     break;
                 }
-                '\\' => {
-                    let ch = self.char_from_escape_seq()?;
-                    value.push(ch);
+                '\\' => match self.char_from_escape_seq() {
+                    Ok(ch) => value.push(ch),
+                    Err(err) => {
+                        self.errors.push(err);
+                        malformed = true;
+                    }
+                },
+                '{' if interpolated => {
+                    let tree_start = self.offset.get() - 1;
+
+                    let tree = match self.interpolation() {
+                        Some(tree) => tree,
+                        None => {
+                            // The rest of the literal is taken verbatim, so that the
+                            // error is only reported once
+                            interpolated = false;
+                            malformed = true;
+                            continue;
+                        }
+                    };
+
+                    // Save the string segment that precedes the interpolated expression
+                    if !value.is_empty() {
+                        let kind = TokenKind::StrLit(std::mem::take(&mut value).into());
+                        segments.push(Token::new(kind, Span::new(segment_start, tree_start)));
+                    }
+
+                    segments.push(Token::new(tree, Span::new(tree_start, self.offset.get())));
+                    segment_start = self.offset.get();
                 }
                 ch => value.push(ch),
             }
@@ -665,35 +1150,270 @@ impl<'a> Lexer<'a> {
 
         // Report that the literal is unclosed
         if !closed {
-            return Err(LexerError::new(
+            self.unterminated_literal = true;
+            self.errors.push(LexerError::new(
                 None,
                 LexerErrorKind::UnclosedStringLit,
                 Span::new(start, self.offset.get()),
             ));
         }
 
+        // The trees of the interpolated expressions are not reachable from an
+        // erroneous literal, so they are discarded
+        if !closed || malformed {
+            self.token_trees.truncate(trees_start);
+            return TokenKind::Err;
+        }
+
+        if !segments.is_empty() {
+            if !value.is_empty() {
+                let kind = TokenKind::StrLit(value.into());
+                segments.push(Token::new(kind, Span::new(segment_start, self.offset.get())));
+            }
+
+            self.token_trees.push(segments);
+            return TokenKind::InterpolatedStrLit(self.token_trees.len() - 1);
+        }
+
         // Essentially we put the string into the literal map and get an id out which we
         // use for the actual representation in the token
-        Ok(TokenKind::StrLit(value.into()))
+        TokenKind::StrLit(value.into())
+    }
+
+    /// Consume an interpolated expression within a string literal, this
+    /// expects that the opening `{` has been consumed. If the expression isn't
+    /// closed before the end of the literal, then the expression is discarded
+    /// and the lexer is reset to the start of it, since it is likely that the
+    /// `{` was meant to be part of the string. This is reported once as an
+    /// unclosed interpolation, rather than reporting the errors of the
+    /// remaining contents that are lexed as the expression.
+    fn interpolation(&mut self) -> Option<TokenKind> {
+        let offset = self.offset.get();
+        let errors = self.errors.len();
+        let warnings = self.warnings.len();
+        let trees = self.token_trees.len();
+        let unterminated_literal = self.unterminated_literal;
+        let dangling_trivia = std::mem::take(&mut self.dangling_trivia);
+
+        let tree = self.eat_token_tree(Delimiter::Brace);
+        let close = self.tree_close.take();
+        self.dangling_trivia = dangling_trivia;
+
+        if close == Some(TreeClose::Closed) {
+            return Some(tree);
+        }
+
+        self.offset.set(offset);
+        self.errors.truncate(errors);
+        self.warnings.truncate(warnings);
+        self.token_trees.truncate(trees);
+        self.trivia.truncate(offset);
+        self.unterminated_literal = unterminated_literal;
+
+        self.errors.push(LexerError::new(
+            None,
+            LexerErrorKind::UnclosedInterpolation,
+            Span::new(offset - 1, offset),
+        ));
+
+        None
+    }
+
+    /// Check if the lexer is at the start of a raw string literal, this
+    /// expects that the leading `r` has been consumed. A raw string starts
+    /// with any number of `#`s, followed by a `"`.
+    fn is_raw_str_start(&self) -> bool {
+        let slice = unsafe { self.as_slice() };
+        slice.trim_start_matches('#').starts_with('"')
+    }
+
+    /// Consume a raw string literal, e.g. `r#"contains "quotes""#`. This
+    /// expects that the leading `r` has been consumed. The contents of a raw
+    /// string are taken verbatim, and the literal is closed by a `"` followed
+    /// by as many `#`s as the literal was opened with.
+    fn raw_string(&mut self) -> TokenKind {
+        let start = self.offset.get() - 1;
+        let hashes = self.eat_while_and_slice(|c| c == '#').len();
+        self.skip(); // skip the opening quote
+
+        let contents_start = self.offset.get();
+        let terminator = format!("\"{}", "#".repeat(hashes));
+
+        loop {
+            let slice = unsafe { self.as_slice() };
+
+            if slice.starts_with(&terminator) {
+                let value = &self.contents[contents_start..self.offset.get()];
+                self.offset.update(|x| x + terminator.len());
+
+                return TokenKind::RawStrLit(value.into());
+            }
+
+            if self.next().is_none() {
+                self.unterminated_literal = true;
+                self.errors.push(LexerError::new(
+                    None,
+                    LexerErrorKind::UnclosedRawStrLit(terminator),
+                    Span::new(start, self.offset.get()),
+                ));
+
+                return TokenKind::Err;
+            }
+        }
+    }
+
+    /// Consume a byte string literal, e.g. `b"bytes\xff"`, this expects that
+    /// the leading `b"` has been consumed. Byte strings may only contain ASCII
+    /// characters, any other bytes must be written using `\x` escape
+    /// sequences. Unicode escape sequences aren't allowed within byte strings.
+    fn byte_string(&mut self) -> TokenKind {
+        let mut value = vec![];
+        let mut closed = false;
+        let mut malformed = false;
+
+        let start = self.offset.get() - 2;
+
+        while let Some(c) = self.next() {
+            match c {
+                '"' => {
+                    closed = true;
+                    break;
+                }
+                '\\' if self.peek() == 'u' => {
+                    let offset = self.offset.get();
+                    self.skip();
+
+                    self.errors.push(LexerError::new(
+                        Some("Unicode escape sequences cannot be used in byte strings".to_string()),
+                        LexerErrorKind::BadEscapeSequence,
+                        Span::new(offset - 1, offset + 1),
+                    ));
+                    malformed = true;
+                }
+                '\\' => match self.char_from_escape_seq() {
+                    // `\x` escapes produce characters up to `\xff`, which are the bytes
+                    Ok(ch) => value.push(ch as u8),
+                    Err(err) => {
+                        self.errors.push(err);
+                        malformed = true;
+                    }
+                },
+                ch if ch.is_ascii() => value.push(ch as u8),
+                ch => {
+                    let offset = self.offset.get();
+
+                    self.errors.push(LexerError::new(
+                        None,
+                        LexerErrorKind::NonAsciiByteStrChar(ch),
+                        Span::new(offset - ch.len_utf8(), offset),
+                    ));
+                    malformed = true;
+                }
+            }
+        }
+
+        if !closed {
+            self.unterminated_literal = true;
+            self.errors.push(LexerError::new(
+                None,
+                LexerErrorKind::UnclosedByteStrLit,
+                Span::new(start, self.offset.get()),
+            ));
+
+            return TokenKind::Err;
+        }
+
+        if malformed {
+            return TokenKind::Err;
+        }
+
+        TokenKind::ByteStrLit(value.as_slice().into())
+    }
+
+    /// Consume a multi-line string literal, this expects that the opening
+    /// `"""` has been consumed. Escape sequences are processed as in ordinary
+    /// strings, but the common indentation of the lines is stripped from the
+    /// literal. If the opening `"""` is followed by a line break, the first
+    /// line is omitted, and if the closing `"""` is on its own line, the last
+    /// line is omitted and its indentation also counts towards the common
+    /// indentation.
+    fn multi_line_string(&mut self) -> TokenKind {
+        // Each character is stored with whether it was produced by an escape
+        // sequence, since escaped whitespace isn't considered as indentation.
+        let mut lines: Vec<Vec<(char, bool)>> = vec![vec![]];
+        let mut closed = false;
+        let mut malformed = false;
+
+        let start = self.offset.get() - 3;
+
+        while let Some(c) = self.next() {
+            let line = lines.last_mut().unwrap();
+
+            match c {
+                '"' if self.peek() == '"' && self.peek_second() == '"' => {
+                    self.skip();
+                    self.skip();
+
+                    closed = true;
+                    break;
+                }
+                '\\' => match self.char_from_escape_seq() {
+                    Ok(ch) => line.push((ch, true)),
+                    Err(err) => {
+                        self.errors.push(err);
+                        malformed = true;
+                    }
+                },
+                '\r' if self.peek() == '\n' => {}
+                '\n' => lines.push(vec![]),
+                ch => line.push((ch, false)),
+            }
+        }
+
+        if !closed {
+            self.unterminated_literal = true;
+            self.errors.push(LexerError::new(
+                None,
+                LexerErrorKind::UnclosedMultiLineStrLit,
+                Span::new(start, self.offset.get()),
+            ));
+
+            return TokenKind::Err;
+        }
+
+        if malformed {
+            return TokenKind::Err;
+        }
+
+        TokenKind::MultiLineStrLit(strip_indentation(lines).into())
     }
 
     /// Consume a line comment after the first following slash, essentially
     /// eating characters up to the next '\n' encountered. If we reach EOF
-    /// before a newline, then we stop eating there.
-    //@@DocSupport: These could return a TokenKind so that we can feed it into some
-    //@@DocSupport: kind of documentation generator tool
-    fn line_comment(&self) {
+    /// before a newline, then we stop eating there. The kind of the comment is
+    /// returned, which distinguishes between ordinary and documentation
+    /// comments.
+    fn line_comment(&self) -> TriviaKind {
         debug_assert!(self.peek() == '/' && self.peek_second() == '/');
+
+        // `///` and `//!` are documentation comments, but `////...` isn't
+        let kind = match (self.nth_char(2), self.nth_char(3)) {
+            ('/', '/') => TriviaKind::LineComment,
+            ('/', _) => TriviaKind::DocComment(DocCommentKind::Outer),
+            ('!', _) => TriviaKind::DocComment(DocCommentKind::Inner),
+            _ => TriviaKind::LineComment,
+        };
+
         self.skip();
         self.eat_while_and_discard(|c| c != '\n');
+
+        kind
     }
 
     /// Consume a block comment after the first following '/*' sequence of
     /// characters. If the iterator encounters the start of another block
     /// comment, we increment a nested comment counter to ensure that nested
     /// block comments are accounted for and handled gracefully.
-    //@@DocSupport: These could return a TokenKind so that we can feed it into some
-    //@@DocSupport: kind of documentation generator tool
     fn block_comment(&self) {
         debug_assert!(self.peek() == '/' && self.peek_second() == '*');
         self.skip();
@@ -753,3 +1473,245 @@ impl<'a> Lexer<'a> {
         &self.contents[start..end]
     }
 }
+
+/// Collect the [Span]s of all of the comments within the given contents, in
+/// the order that they appear in. Lexing errors are ignored, since the
+/// comments are only needed to preserve them when the contents are printed
+/// again.
+pub fn comment_spans(contents: &str, source_id: SourceId) -> Vec<Span> {
+    let mut lexer = Lexer::new(contents, source_id).with_trivia();
+    lexer.tokenise();
+
+    let mut trivia = lexer.eof_trivia().to_vec();
+
+    for TokenTrivia { leading, trailing, dangling } in lexer.trivia().iter() {
+        trivia.extend(leading.iter().chain(trailing).chain(dangling));
+    }
+
+    let mut spans: Vec<_> = trivia
+        .into_iter()
+        .filter(|trivia| trivia.kind != TriviaKind::Whitespace)
+        .map(|trivia| trivia.span)
+        .collect();
+
+    spans.sort_by_key(|span| span.start());
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hash_source::InteractiveId;
+
+    fn trivia_kinds(trivia: &[Trivia]) -> Vec<TriviaKind> {
+        trivia.iter().map(|trivia| trivia.kind).collect()
+    }
+
+    #[test]
+    fn preserve_trivia() {
+        let contents = "//! module\n/// item\na := 1; // trailing\n(b /* inner */)\n// end\n";
+        let mut lexer =
+            Lexer::new(contents, SourceId::Interactive(InteractiveId::default())).with_trivia();
+        let (tokens, errors) = lexer.tokenise();
+        assert!(errors.is_empty());
+
+        let first = lexer.trivia().get(&tokens[0]).unwrap();
+        assert_eq!(
+            trivia_kinds(&first.leading),
+            vec![
+                TriviaKind::DocComment(DocCommentKind::Inner),
+                TriviaKind::Whitespace,
+                TriviaKind::DocComment(DocCommentKind::Outer),
+                TriviaKind::Whitespace,
+            ]
+        );
+        assert_eq!(trivia_kinds(&first.trailing), vec![TriviaKind::Whitespace]);
+
+        // The comment after `;` is trailing, and the newline is leading trivia of the
+        // tree
+        let semi = lexer.trivia().get(&tokens[4]).unwrap();
+        assert_eq!(
+            trivia_kinds(&semi.trailing),
+            vec![TriviaKind::Whitespace, TriviaKind::LineComment]
+        );
+        assert_eq!(
+            &contents[semi.trailing[1].span.start()..semi.trailing[1].span.end()],
+            "// trailing"
+        );
+
+        let tree = lexer.trivia().get(&tokens[5]).unwrap();
+        assert_eq!(trivia_kinds(&tree.leading), vec![TriviaKind::Whitespace]);
+        assert_eq!(trivia_kinds(&tree.trailing), vec![]);
+
+        let inner = &lexer.token_trees[0][0];
+        assert_eq!(
+            trivia_kinds(&lexer.trivia().get(inner).unwrap().trailing),
+            vec![TriviaKind::Whitespace, TriviaKind::BlockComment]
+        );
+
+        assert_eq!(
+            trivia_kinds(lexer.eof_trivia()),
+            vec![TriviaKind::Whitespace, TriviaKind::LineComment, TriviaKind::Whitespace]
+        );
+    }
+
+    #[test]
+    fn string_literal_forms() {
+        let contents = r##"r"C:\path" r#"a "quoted" word"# b"\xff\n" """
+            first
+              second\tescaped
+
+            last
+            """ """inline""""##;
+        let mut lexer = Lexer::new(contents, SourceId::Interactive(InteractiveId::default()));
+        let (tokens, errors) = lexer.tokenise();
+        assert!(errors.is_empty());
+
+        let kinds = tokens.iter().map(|token| token.kind).collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                TokenKind::RawStrLit("C:\\path".into()),
+                TokenKind::RawStrLit("a \"quoted\" word".into()),
+                TokenKind::ByteStrLit([0xff, b'\n'].as_slice().into()),
+                TokenKind::MultiLineStrLit("first\n  second\tescaped\n\nlast".into()),
+                TokenKind::MultiLineStrLit("inline".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn strip_mixed_indentation() {
+        // Only the indentation that is shared by all of the lines is stripped, a tab
+        // isn't the same as a space
+        let contents = "\"\"\"\n \ta\n  b\n \"\"\" \"\"\"\n\ta\n\t\tb\n\t\"\"\"";
+        let mut lexer = Lexer::new(contents, SourceId::Interactive(InteractiveId::default()));
+        let (tokens, errors) = lexer.tokenise();
+        assert!(errors.is_empty());
+
+        let kinds = tokens.iter().map(|token| token.kind).collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                TokenKind::MultiLineStrLit("\ta\n b".into()),
+                TokenKind::MultiLineStrLit("a\n\tb".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn report_unterminated_literal_once() {
+        // The enclosing trees are also unclosed, but only the literal is reported
+        let mut lexer = Lexer::new("f(a, [\"b", SourceId::Interactive(InteractiveId::default()));
+        let (_, errors) = lexer.tokenise();
+
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0].kind, LexerErrorKind::UnclosedStringLit));
+    }
+
+    #[test]
+    fn interpolate_prefixed_strings_only() {
+        let contents = r#"a := "{"; b := f"{x} and {f"{y}"}\{"; c := 1;"#;
+        let mut lexer = Lexer::new(contents, SourceId::Interactive(InteractiveId::default()));
+        let (tokens, errors) = lexer.tokenise();
+        assert!(errors.is_empty());
+
+        let names = tokens.iter().map(|token| token.kind.name()).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "Ident",
+                "Colon",
+                "Eq",
+                "StrLit",
+                "Semi",
+                "Ident",
+                "Colon",
+                "Eq",
+                "InterpolatedStrLit",
+                "Semi",
+                "Ident",
+                "Colon",
+                "Eq",
+                "IntLit",
+                "Semi"
+            ]
+        );
+        assert_eq!(tokens[3].kind, TokenKind::StrLit("{".into()));
+
+        // The segments are the first interpolation, the string between them, the
+        // nested interpolated string and the escaped brace
+        let segments = match tokens[8].kind {
+            TokenKind::InterpolatedStrLit(index) => &lexer.token_trees[index],
+            _ => unreachable!(),
+        };
+        let names = segments.iter().map(|token| token.kind.name()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["Tree", "StrLit", "Tree", "StrLit"]);
+        assert_eq!(segments[1].kind, TokenKind::StrLit(" and ".into()));
+        assert_eq!(segments[3].kind, TokenKind::StrLit("{".into()));
+
+        let nested = match segments[2].kind {
+            TokenKind::Tree(_, index) => &lexer.token_trees[index],
+            _ => unreachable!(),
+        };
+        assert_eq!(nested[0].kind.name(), "InterpolatedStrLit");
+    }
+
+    #[test]
+    fn report_unclosed_interpolation_once() {
+        // The interpolation ends at the closing quote, so the rest of the contents
+        // are lexed as usual
+        let contents = r#"a := f"{x + "; b := (1);"#;
+        let mut lexer = Lexer::new(contents, SourceId::Interactive(InteractiveId::default()));
+        let (tokens, errors) = lexer.tokenise();
+
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0].kind, LexerErrorKind::UnclosedInterpolation));
+        assert_eq!(errors[0].span, Span::new(7, 8));
+
+        let names = tokens.iter().map(|token| token.kind.name()).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec!["Ident", "Colon", "Eq", "Err", "Semi", "Ident", "Colon", "Eq", "Tree", "Semi"]
+        );
+    }
+
+    #[test]
+    fn discard_trivia_by_default() {
+        let mut lexer =
+            Lexer::new("a /* b */ // c", SourceId::Interactive(InteractiveId::default()));
+        let (tokens, _) = lexer.tokenise();
+
+        assert_eq!(tokens.len(), 1);
+        assert!(lexer.trivia().is_empty());
+        assert!(lexer.eof_trivia().is_empty());
+    }
+
+    #[test]
+    fn normalise_identifiers() {
+        // The composed `\u{e9}` and the decomposed `e\u{301}` are the same identifier
+        let contents = "caf\u{e9} cafe\u{301} \u{43f}\u{440}\u{438}\u{432}\u{435}\u{442}";
+        let mut lexer = Lexer::new(contents, SourceId::Interactive(InteractiveId::default()));
+        let (tokens, errors) = lexer.tokenise();
+
+        assert!(errors.is_empty());
+        assert!(lexer.take_warnings().is_empty());
+        assert_eq!(tokens.len(), 3);
+        assert_eq!(tokens[0].kind, tokens[1].kind);
+        assert_eq!(tokens[1].span, Span::new(6, 12));
+    }
+
+    #[test]
+    fn int_literal_range_bounds() {
+        let mut lexer =
+            Lexer::new("0..10 1..=2.5", SourceId::Interactive(InteractiveId::default()));
+        let (tokens, errors) = lexer.tokenise();
+        assert!(errors.is_empty());
+
+        let names = tokens.iter().map(|token| token.kind.name()).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec!["IntLit", "Dot", "Dot", "IntLit", "IntLit", "Dot", "Dot", "Eq", "FloatLit"]
+        );
+    }
+}
@@ -1,13 +1,109 @@
 //! Hash Compiler lexer utilities for identifiers and other character sequences.
+use unicode_script::{Script, UnicodeScript};
+use unicode_security::{confusable_detection::skeleton, mixed_script::AugmentedScriptSet};
+use unicode_xid::UnicodeXID;
 
 /// True if `c` is valid as a first character of an identifier.
-// @@Future: Support unicode within idents?
 pub(crate) fn is_id_start(c: char) -> bool {
-    ('a'..='z').contains(&c) || ('A'..='Z').contains(&c) || c == '_'
+    c.is_ascii_alphabetic() || c == '_' || (!c.is_ascii() && c.is_xid_start())
 }
 
 /// True if `c` is valid as a non-first character of an identifier.
-// @@Future: Support unicode within idents?
 pub(crate) fn is_id_continue(c: char) -> bool {
-    ('a'..='z').contains(&c) || ('A'..='Z').contains(&c) || ('0'..='9').contains(&c) || c == '_'
+    c.is_ascii_alphanumeric() || c == '_' || (!c.is_ascii() && c.is_xid_continue())
+}
+
+/// Find the first character within an identifier that is from a different
+/// script than the characters before it. This returns the byte offset of
+/// the character within the identifier, the character itself, and the names
+/// of the script of the identifier and the script of the character.
+pub(crate) fn find_mixed_script_char(
+    name: &str,
+) -> Option<(usize, char, &'static str, &'static str)> {
+    let mut scripts = AugmentedScriptSet::default();
+
+    // The first script that is used within the identifier, ignoring characters that
+    // are common to many scripts such as digits or `_`
+    let mut first_script = None;
+
+    for (offset, ch) in name.char_indices() {
+        let script = ch.script();
+        scripts.intersect_with(AugmentedScriptSet::for_char(ch));
+
+        if scripts.is_empty() {
+            let first: Script = first_script?;
+            return Some((offset, ch, first.full_name(), script.full_name()));
+        }
+
+        if first_script.is_none() && !matches!(script, Script::Common | Script::Inherited) {
+            first_script = Some(script);
+        }
+    }
+
+    None
+}
+
+/// If all of the characters of the identifier look like ASCII characters,
+/// return the ASCII identifier that the identifier could be confused with.
+pub(crate) fn ascii_lookalike(name: &str) -> Option<String> {
+    let lookalike: String = skeleton(name).collect();
+    (lookalike.is_ascii() && lookalike != name).then_some(lookalike)
+}
+
+/// Check if the given line of a multi-line string literal is blank, i.e. it
+/// only consists of un-escaped whitespace.
+fn is_blank_line(line: &[(char, bool)]) -> bool {
+    line.iter().all(|(c, escaped)| !escaped && c.is_whitespace())
+}
+
+/// Get the indentation of a line of a multi-line string literal, this is the
+/// leading un-escaped spaces and tabs.
+fn line_indentation(line: &[(char, bool)]) -> &[(char, bool)] {
+    let len = line.iter().take_while(|(c, escaped)| !escaped && matches!(c, ' ' | '\t')).count();
+    &line[..len]
+}
+
+/// Get the length of the longest common prefix of two indentations. Tabs and
+/// spaces are distinct, so a tab is never stripped as part of the indentation
+/// of a line that is indented by spaces and vice versa.
+fn common_indentation(left: &[(char, bool)], right: &[(char, bool)]) -> usize {
+    left.iter().zip(right).take_while(|(left, right)| left == right).count()
+}
+
+/// Strip the common indentation from the lines of a multi-line string
+/// literal, and join them into the final value of the literal. Each character
+/// is paired with whether it was produced by an escape sequence. Blank lines
+/// don't contribute to the common indentation, apart from the line that the
+/// closing delimiter is on. The common indentation is the longest prefix of
+/// whitespace that all of the lines start with.
+pub(crate) fn strip_indentation(mut lines: Vec<Vec<(char, bool)>>) -> String {
+    // The line directly after the opening delimiter is omitted if it is blank
+    if lines.len() > 1 && is_blank_line(&lines[0]) {
+        lines.remove(0);
+    }
+
+    // The line of the closing delimiter is omitted if it only contains
+    // the indentation of the closing delimiter
+    let closing_line = match lines.last() {
+        Some(line) if lines.len() > 1 && is_blank_line(line) => lines.pop(),
+        _ => None,
+    };
+
+    let mut indentations = lines
+        .iter()
+        .filter(|line| !is_blank_line(line))
+        .chain(closing_line.as_ref())
+        .map(|line| line_indentation(line));
+
+    let indentation = match indentations.next() {
+        Some(first) => indentations
+            .fold(first.len(), |len, indentation| common_indentation(&first[..len], indentation)),
+        None => 0,
+    };
+
+    lines
+        .iter()
+        .map(|line| line.iter().skip(indentation).map(|(c, _)| *c).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
 }
@@ -3,7 +3,10 @@
 use std::path::{Path, PathBuf};
 
 use crossbeam_channel::Sender;
-use hash_pipeline::fs::{read_in_path, resolve_path, ImportError};
+use hash_pipeline::{
+    fs::{read_in_path, resolve_path, ImportError},
+    settings::CompilerSettings,
+};
 use hash_source::{location::SourceLocation, SourceId};
 
 use crate::ParserAction;
@@ -14,21 +17,31 @@ use crate::ParserAction;
 pub struct ImportResolver<'p> {
     /// The associated [SourceId] with the import resolution.
     source_id: SourceId,
-    /// Working directory from where the import path resolution occurs.
-    root_dir: &'p Path,
+    /// Working directory from where the import path resolution occurs, along
+    /// with the compiler settings which specify additional locations that
+    /// imported modules are searched for in. This is [None] if imports aren't
+    /// followed.
+    search: Option<(&'p Path, &'p CompilerSettings)>,
     /// The parser message queue sender.
     sender: Sender<ParserAction>,
 }
 
 impl<'p> ImportResolver<'p> {
     /// Create a new [ImportResolver] with a specified [SourceId], working
-    /// directory and a message queue sender.
+    /// directory, compiler settings and a message queue sender.
     pub(crate) fn new(
         source_id: SourceId,
         root_dir: &'p Path,
+        settings: &'p CompilerSettings,
         sender: Sender<ParserAction>,
     ) -> Self {
-        Self { root_dir, sender, source_id }
+        Self { search: Some((root_dir, settings)), sender, source_id }
+    }
+
+    /// Create a new [ImportResolver] which doesn't follow any imports, the
+    /// path of each import is kept as it is written.
+    pub(crate) fn without_imports(source_id: SourceId, sender: Sender<ParserAction>) -> Self {
+        Self { search: None, sender, source_id }
     }
 
     /// Get the [SourceId] associated with the current [ImportResolver]
@@ -39,14 +52,21 @@ impl<'p> ImportResolver<'p> {
     /// Function to perform import resolution. It will attempt to resolve the
     /// contents of the provided `import_path`, resolve the contents of the
     /// module, and then proceed to send a [ParserAction::ParseImport]
-    /// through the message queue.
+    /// through the message queue. The `source_location` is the location of
+    /// the `import(...)` that is being resolved. If imports aren't followed,
+    /// then the `import_path` is returned as it is.
     pub(crate) fn resolve_import(
         &self,
         import_path: &Path,
         source_location: SourceLocation,
     ) -> Result<PathBuf, ImportError> {
+        let (root_dir, settings) = match self.search {
+            Some(search) => search,
+            None => return Ok(import_path.to_path_buf()),
+        };
+
         // Read the contents of the file
-        let resolved_path = resolve_path(import_path, &self.root_dir, Some(source_location))?;
+        let resolved_path = resolve_path(import_path, root_dir, settings, Some(source_location))?;
         let contents = read_in_path(&resolved_path)?;
 
         // Send over the resolved path and the contents of the file
@@ -54,6 +74,7 @@ impl<'p> ImportResolver<'p> {
             .send(ParserAction::ParseImport {
                 resolved_path: resolved_path.clone(),
                 contents,
+                location: source_location,
                 sender: self.sender.clone(),
             })
             .unwrap();
@@ -8,16 +8,23 @@ mod source;
 
 use crossbeam_channel::{unbounded, Sender};
 use hash_ast::ast::{self};
-use hash_lexer::Lexer;
+use hash_lexer::{
+    error::{LexerErrorWrapper, LexerWarningWrapper},
+    Lexer,
+};
 use hash_pipeline::{
+    settings::CompilerSettings,
     sources::{Module, Workspace},
     traits::Parser,
     CompilerResult,
 };
 use hash_reporting::report::Report;
-use hash_source::{InteractiveId, ModuleId, ModuleKind, SourceId};
+use hash_source::{location::SourceLocation, InteractiveId, ModuleId, ModuleKind, SourceId};
 use import_resolver::ImportResolver;
-use parser::{error::ParseError, AstGen};
+use parser::{
+    error::{create_lexer_warning_report, ParseError},
+    AstGen,
+};
 use source::ParseSource;
 use std::{env, path::PathBuf};
 
@@ -26,9 +33,16 @@ use std::{env, path::PathBuf};
 pub enum ParserAction {
     /// An error occurred during the parsing or lexing of a module.
     Error(ParseError),
+    /// A warning was produced during the lexing of a module.
+    Warning(LexerWarningWrapper),
     /// A worker has specified that a module should be put in the queue for
-    /// lexing and parsing.
-    ParseImport { resolved_path: PathBuf, contents: String, sender: Sender<ParserAction> },
+    /// lexing and parsing. The `location` is where the module is imported.
+    ParseImport {
+        resolved_path: PathBuf,
+        contents: String,
+        location: SourceLocation,
+        sender: Sender<ParserAction>,
+    },
     /// A worker has completed processing an interactive block and now provides
     /// the generated AST.
     SetInteractiveNode { interactive_id: InteractiveId, node: ast::AstNode<ast::BodyBlock> },
@@ -37,25 +51,45 @@ pub enum ParserAction {
     SetModuleNode { module_id: ModuleId, node: ast::AstNode<ast::Module> },
 }
 
-/// Parse a specific source specified by [ParseSource].
-fn parse_source(source: ParseSource, sender: Sender<ParserAction>) {
+/// Parse a specific source specified by [ParseSource], the [CompilerSettings]
+/// are used to resolve any imports within the source. If no settings are
+/// given, then imports aren't followed.
+fn parse_source(
+    source: ParseSource,
+    settings: Option<&CompilerSettings>,
+    sender: Sender<ParserAction>,
+) {
     let source_id = source.source_id();
     let contents = source.contents();
 
     // Lex the contents of the module or interactive block
     let mut lexer = Lexer::new(&contents, source_id);
 
-    let tokens = match lexer.tokenise() {
-        Ok(source) => source,
-        Err(err) => {
-            return sender.send(ParserAction::Error(err.into())).unwrap();
+    let (tokens, errors) = lexer.tokenise();
+
+    for warning in lexer.take_warnings() {
+        sender.send(ParserAction::Warning(LexerWarningWrapper(source_id, warning))).unwrap();
+    }
+
+    // Report all of the errors that the lexer encountered, we don't continue
+    // to parse the token stream since it would produce additional errors from
+    // the malformed tokens.
+    if !errors.is_empty() {
+        for err in errors {
+            sender.send(ParserAction::Error(LexerErrorWrapper(source_id, err).into())).unwrap();
         }
-    };
+
+        return;
+    }
+
     let trees = lexer.into_token_trees();
 
     // Create a new import resolver in the event of more modules that
     // are encountered whilst parsing this module.
-    let resolver = ImportResolver::new(source_id, source.path(), sender);
+    let resolver = match settings {
+        Some(settings) => ImportResolver::new(source_id, source.path(), settings, sender),
+        None => ImportResolver::without_imports(source_id, sender),
+    };
 
     let gen = AstGen::new(&tokens, &trees, &resolver);
 
@@ -72,7 +106,15 @@ fn parse_source(source: ParseSource, sender: Sender<ParserAction>) {
         },
     };
 
+    // The generator recovers from syntax errors, so the produced tree is still
+    // sent, but all of the errors that were recovered from must be reported.
+    let errors = gen.take_errors();
     let sender = resolver.into_sender();
+
+    for err in errors {
+        sender.send(ParserAction::Error(err.into())).unwrap();
+    }
+
     sender.send(action).unwrap();
 }
 
@@ -91,22 +133,26 @@ impl<'pool> HashParser {
     /// will initiate the thread pool message queue. Parser workers add more
     /// `jobs` by sending [ParserAction::ParseImport] messages through the
     /// channel, and other workers set the parsed contents of the modules.
-    /// When all message senders go out of scope, the parser finishes executing
+    /// When all message senders go out of scope, the parser finishes executing.
+    /// The errors and the warnings that were produced are returned separately
+    /// as [Report]s.
     pub fn begin(
         &mut self,
         entry_point_id: SourceId,
         current_dir: PathBuf,
+        settings: &CompilerSettings,
         workspace: &mut Workspace,
         pool: &'pool rayon::ThreadPool,
-    ) -> Vec<ParseError> {
+    ) -> (Vec<Report>, Vec<Report>) {
         let mut errors = Vec::new();
+        let mut warnings = Vec::new();
         let (sender, receiver) = unbounded::<ParserAction>();
 
         assert!(pool.current_num_threads() > 1, "Parser loop requires at least 2 workers");
 
         // Parse the entry point
         let entry_source_kind = ParseSource::from_source(entry_point_id, workspace, current_dir);
-        parse_source(entry_source_kind, sender);
+        parse_source(entry_source_kind, Some(settings), sender);
 
         pool.scope(|scope| {
             while let Ok(message) = receiver.recv() {
@@ -120,28 +166,91 @@ impl<'pool> HashParser {
                     ParserAction::SetModuleNode { module_id, node } => {
                         workspace.node_map_mut().get_module_mut(module_id).set_node(node);
                     }
-                    ParserAction::ParseImport { resolved_path, contents, sender } => {
-                        if workspace.get_module_id_by_path(&resolved_path).is_some() {
-                            continue;
-                        }
+                    ParserAction::ParseImport { resolved_path, contents, location, sender } => {
+                        let module_id = match workspace.get_module_id_by_path(&resolved_path) {
+                            Some(module_id) => module_id,
+                            None => {
+                                let module_id = workspace.add_module(
+                                    contents,
+                                    Module::new(resolved_path.clone()),
+                                    ModuleKind::Normal,
+                                );
 
-                        let module_id = workspace.add_module(
-                            contents,
-                            Module::new(resolved_path.clone()),
-                            ModuleKind::Normal,
-                        );
+                                let source = ParseSource::from_module(module_id, workspace);
+                                scope.spawn(move |_| parse_source(source, Some(settings), sender));
+                                module_id
+                            }
+                        };
 
-                        let source = ParseSource::from_module(module_id, workspace);
-                        scope.spawn(move |_| parse_source(source, sender));
+                        // Record the import, and check if it completes a cycle of imports
+                        workspace.add_dependency(location.source_id, module_id, location);
+
+                        if let Some(chain) =
+                            workspace.find_import_cycle(location.source_id, module_id)
+                        {
+                            let chain = chain
+                                .into_iter()
+                                .map(|location| {
+                                    let name =
+                                        workspace.source_map().source_name(location.source_id);
+                                    (location, name.to_string())
+                                })
+                                .collect();
+
+                            errors.push(ParseError::ImportCycle(chain).create_report());
+                        }
                     }
                     ParserAction::Error(err) => {
-                        errors.push(err);
+                        errors.push(err.create_report());
+                    }
+                    ParserAction::Warning(warning) => {
+                        warnings.push(create_lexer_warning_report(warning));
                     }
                 }
             }
         });
 
-        errors
+        (errors, warnings)
+    }
+
+    /// Parse a single module without following any of its imports, the path
+    /// of each import is kept as it is written. This is used by tools that
+    /// only need the module itself, such as the formatter. In the same way as
+    /// [Parser::parse], the warnings are returned on success.
+    pub fn parse_module_only(
+        &mut self,
+        module_id: ModuleId,
+        workspace: &mut Workspace,
+    ) -> CompilerResult<Vec<Report>> {
+        let (sender, receiver) = unbounded::<ParserAction>();
+        parse_source(ParseSource::from_module(module_id, workspace), None, sender);
+
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+
+        // The sender is dropped once the module is parsed, so all of the messages
+        // are already in the queue
+        for message in receiver.iter() {
+            match message {
+                ParserAction::SetModuleNode { module_id, node } => {
+                    workspace.node_map_mut().get_module_mut(module_id).set_node(node);
+                }
+                ParserAction::Error(err) => errors.push(err.create_report()),
+                ParserAction::Warning(warning) => {
+                    warnings.push(create_lexer_warning_report(warning))
+                }
+                ParserAction::ParseImport { .. } | ParserAction::SetInteractiveNode { .. } => {
+                    unreachable!("parsing a single module doesn't produce {:?}", message)
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(warnings)
+        } else {
+            errors.extend(warnings);
+            Err(errors)
+        }
     }
 }
 
@@ -152,21 +261,20 @@ impl<'pool> Parser<'pool> for HashParser {
         &mut self,
         target: SourceId,
         workspace: &mut Workspace,
+        settings: &CompilerSettings,
         pool: &'pool rayon::ThreadPool,
-    ) -> CompilerResult<()> {
+    ) -> CompilerResult<Vec<Report>> {
         let current_dir =
             env::current_dir().map_err(ParseError::from).map_err(|err| vec![Report::from(err)])?;
 
-        // Parse and collect any errors that occurred
-        let errors: Vec<_> = self
-            .begin(target, current_dir, workspace, pool)
-            .into_iter()
-            .map(|err| err.create_report())
-            .collect();
+        // Parse and collect any errors or warnings that occurred, warnings alone
+        // don't stop the parsed modules from being used
+        let (mut errors, warnings) = self.begin(target, current_dir, settings, workspace, pool);
 
         if errors.is_empty() {
-            Ok(())
+            Ok(warnings)
         } else {
+            errors.extend(warnings);
             Err(errors)
         }
     }
@@ -38,15 +38,30 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
         // firstly check if the first token signals a beginning of a statement, we can
         // tell this by checking for keywords that must begin a statement...
         while self.has_token() {
-            let (has_semi, statement) = self.parse_top_level_expr(false)?;
+            let start = self.offset();
+
+            let (has_semi, statement) = match self.parse_top_level_expr() {
+                Ok(result) => result,
+                Err(err) => {
+                    let (statement, has_semi) = self.recover_statement(err, start);
+                    (has_semi, statement)
+                }
+            };
 
             match (has_semi, self.peek()) {
                 (true, _) => block.statements.nodes.push(statement),
-                (false, Some(token)) => self.error(
-                    AstGenErrorKind::Expected,
-                    Some(TokenKindVector::from_row(vec![TokenKind::Semi])),
-                    Some(token.kind),
-                )?,
+                (false, Some(token)) => {
+                    // The statement is kept since only the terminator is missing
+                    let err = self.make_error(
+                        AstGenErrorKind::Expected,
+                        Some(TokenKindVector::from_row(vec![TokenKind::Semi])),
+                        Some(token.kind),
+                        None,
+                    );
+
+                    self.synchronise(err, start);
+                    block.statements.nodes.push(statement);
+                }
                 (false, None) => block.expr = Some(statement),
             }
         }
@@ -111,8 +126,25 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
         let gen = self.parse_delim_tree(Delimiter::Brace, None)?;
 
         while gen.has_token() {
-            cases.nodes.push(gen.parse_match_case()?);
-            gen.parse_token(TokenKind::Semi)?;
+            let start = gen.offset();
+
+            match gen.parse_match_case() {
+                Ok(case) => {
+                    cases.nodes.push(case);
+
+                    if let Err(err) = gen.parse_token(TokenKind::Semi) {
+                        gen.synchronise(err, start);
+                    }
+                }
+                Err(err) => {
+                    // Replace the malformed case with one that is made of error nodes
+                    let (expr, _) = gen.recover_statement(err, start);
+                    let span = expr.span();
+                    let pat = gen.node_with_span(Pat::Err(ErrPat), span);
+
+                    cases.nodes.push(gen.node_with_span(MatchCase { pat, expr }, span));
+                }
+            }
         }
 
         Ok(self.node_with_joined_span(
@@ -2,7 +2,7 @@
 use std::io;
 
 use derive_more::Constructor;
-use hash_lexer::error::LexerErrorWrapper;
+use hash_lexer::error::{LexerErrorWrapper, LexerWarningKind, LexerWarningWrapper};
 use hash_pipeline::fs::ImportError;
 use hash_reporting::{
     builder::ReportBuilder,
@@ -26,6 +26,13 @@ pub struct AstGenError {
     received: Option<TokenKind>,
 }
 
+impl AstGenError {
+    /// Get the location that the error references.
+    pub fn location(&self) -> SourceLocation {
+        self.span
+    }
+}
+
 /// Enum representing the kind of statement where type arguments can be expected
 /// to be present.
 #[derive(Debug)]
@@ -90,6 +97,12 @@ pub enum AstGenErrorKind {
     /// Malformed spread pattern (if for any reason there is a problem with
     /// parsing the spread operator)
     MalformedSpreadPattern(u8),
+    /// Expected a literal as the upper bound of a range pattern.
+    ExpectedRangePatBound,
+    /// Expected a numeric literal after a `-` within a pattern.
+    ExpectedNumericLitAfterMinus,
+    /// Attributes were applied to something that isn't a declaration.
+    ExpectedDeclarationAfterAttributes,
 }
 
 impl std::fmt::Display for TyArgumentKind {
@@ -104,6 +117,12 @@ impl std::fmt::Display for TyArgumentKind {
 /// Conversion implementation from an AST Generator Error into a Parser Error.
 impl From<AstGenError> for ParseError {
     fn from(err: AstGenError) -> Self {
+        // Import errors are reported with all of their information, but at the location
+        // of the import
+        if let AstGenErrorKind::ErroneousImport(import_error) = &err.kind {
+            return Self::Import(ImportError { src: Some(err.span), ..import_error.clone() });
+        }
+
         let expected = err.expected;
 
         let mut base_message = match &err.kind {
@@ -149,7 +168,7 @@ impl From<AstGenError> for ParseError {
             AstGenErrorKind::ImportPath => {
                 "Expected an import path which should be a string".to_string()
             }
-            AstGenErrorKind::ErroneousImport(err) => err.to_string(),
+            AstGenErrorKind::ErroneousImport(_) => unreachable!(),
             AstGenErrorKind::Namespace => {
                 "Expected identifier after a name access qualifier '::'".to_string()
             }
@@ -158,6 +177,15 @@ impl From<AstGenError> for ParseError {
                     "Malformed spread pattern, expected {dots} more `.` to complete the pattern"
                 )
             }
+            AstGenErrorKind::ExpectedRangePatBound => {
+                "Expected a literal as the upper bound of the range pattern".to_string()
+            }
+            AstGenErrorKind::ExpectedNumericLitAfterMinus => {
+                "Expected a numeric literal after `-`, only numeric literals can be negated within a pattern".to_string()
+            }
+            AstGenErrorKind::ExpectedDeclarationAfterAttributes => {
+                "Expected a declaration after the attributes".to_string()
+            }
         };
 
         // Block and expected format the error message in their own way, whereas all the
@@ -195,7 +223,15 @@ impl From<AstGenError> for ParseError {
 pub enum ParseError {
     Import(ImportError),
     IO(io::Error),
-    Parsing { message: String, src: Option<SourceLocation> },
+    Parsing {
+        message: String,
+        src: Option<SourceLocation>,
+    },
+    Lexer(LexerErrorWrapper),
+    /// A cycle of module imports, each entry is the location of an
+    /// `import(...)` within the cycle along with the name of the module that
+    /// contains the import.
+    ImportCycle(Vec<(SourceLocation, String)>),
 }
 
 impl From<io::Error> for ParseError {
@@ -229,6 +265,66 @@ impl ParseError {
             ParseError::Parsing { message, src: None } => {
                 builder.with_message(message);
             }
+            ParseError::Lexer(LexerErrorWrapper(source_id, err)) => {
+                let location = |span| SourceLocation { span, source_id };
+
+                if err.labels.is_empty() {
+                    builder.add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                        location(err.span),
+                        "here",
+                    )));
+                }
+
+                for (span, label) in &err.labels {
+                    builder.add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                        location(*span),
+                        label,
+                    )));
+                }
+
+                builder.add_element(ReportElement::Note(ReportNote::new(
+                    ReportNoteKind::Note,
+                    err.to_string(),
+                )));
+
+                if let Some((span, help)) = err.help {
+                    builder
+                        .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                            location(span),
+                            "here",
+                        )))
+                        .add_element(ReportElement::Note(ReportNote::new(
+                            ReportNoteKind::Help,
+                            help,
+                        )));
+                }
+            }
+            ParseError::ImportCycle(chain) => {
+                builder.with_message("Encountered a circular module import");
+
+                // Each module in the cycle imports the module that contains the next import
+                let names = chain.iter().map(|(_, name)| name.as_str());
+                let imported = names.clone().cycle().skip(1);
+
+                for ((location, _), name) in chain.iter().zip(imported.clone()) {
+                    builder.add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                        *location,
+                        format!("`{name}` is imported here"),
+                    )));
+                }
+
+                let modules = names
+                    .take(1)
+                    .chain(imported.take(chain.len()))
+                    .map(|name| format!("`{name}`"))
+                    .collect::<Vec<_>>()
+                    .join(" -> ");
+
+                builder.add_element(ReportElement::Note(ReportNote::new(
+                    ReportNoteKind::Note,
+                    format!("the import chain {modules} forms a cycle, which is not allowed"),
+                )));
+            }
             ParseError::IO(inner) => {
                 // @@ErrorReporting: we might want to show a bit more info here.
                 builder.with_message(inner.to_string());
@@ -248,10 +344,39 @@ impl From<LexerErrorWrapper> for ParseError {
     /// @@Future: In the future, there is a hope that we don't
     /// need to convert between various error kinds in crates and they can just
     /// be passed around as reports which are pipeline stage agnostic.
-    fn from(LexerErrorWrapper(source_id, err): LexerErrorWrapper) -> Self {
-        ParseError::Parsing {
-            message: err.to_string(),
-            src: Some(SourceLocation { span: err.span, source_id }),
-        }
+    fn from(wrapper: LexerErrorWrapper) -> Self {
+        ParseError::Lexer(wrapper)
     }
 }
+
+/// Create a [Report] from a [`hash_lexer::error::LexerWarning`] that was
+/// produced whilst lexing the source with the given [`hash_source::SourceId`].
+pub fn create_lexer_warning_report(
+    LexerWarningWrapper(source_id, warning): LexerWarningWrapper,
+) -> Report {
+    let mut builder = ReportBuilder::new();
+    builder.with_kind(ReportKind::Warning).with_message(warning.to_string()).add_element(
+        ReportElement::CodeBlock(ReportCodeBlock::new(
+            SourceLocation { span: warning.span, source_id },
+            "here",
+        )),
+    );
+
+    let note = match warning.kind {
+        LexerWarningKind::MixedScriptIdent { ch, other, lookalike: Some(lookalike), .. } => {
+            format!(
+                "the character `{ch}` (U+{:04X}) is from the {other} script, and looks like `{lookalike}`",
+                ch as u32
+            )
+        }
+        LexerWarningKind::MixedScriptIdent { ch, other, lookalike: None, .. } => {
+            format!("the character `{ch}` (U+{:04X}) is from the {other} script", ch as u32)
+        }
+        LexerWarningKind::ConfusableIdent { ch, .. } => {
+            format!("the character `{ch}` (U+{:04X}) is not an ASCII character", ch as u32)
+        }
+    };
+
+    builder.add_element(ReportElement::Note(ReportNote::new(ReportNoteKind::Note, note)));
+    builder.build()
+}
@@ -9,11 +9,15 @@ use hash_token::{delimiter::Delimiter, keyword::Keyword, Token, TokenKind, Token
 use super::{error::AstGenErrorKind, AstGen, AstGenResult};
 
 impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
-    /// Parse a top level [Expr] that are terminated with a semi-colon.
+    /// Parse a top level [Expr] that can be terminated with a semi-colon, the
+    /// returned flag denotes whether the semi-colon was present.
     #[profiling::function]
-    pub fn parse_top_level_expr(&self, semi_required: bool) -> AstGenResult<(bool, AstNode<Expr>)> {
+    pub fn parse_top_level_expr(&self) -> AstGenResult<(bool, AstNode<Expr>)> {
         let start = self.next_location();
 
+        // Attributes can only be applied to declarations
+        let attributes = self.parse_attributes()?;
+
         // So here we want to check that the next token(s) could make up a singular
         // pattern which is then followed by a `:` to denote that this is a
         // declaration.
@@ -23,10 +27,16 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
 
                 self.parse_token(TokenKind::Colon)?;
 
-                let decl = self.parse_declaration(pat)?;
+                let decl = self.parse_declaration(attributes, pat)?;
 
-                Some(self.node_with_span(Expr::new(ExprKind::Declaration(decl)), start))
+                Some(self.node_with_joined_span(Expr::new(ExprKind::Declaration(decl)), &start))
             }
+            false if !attributes.is_empty() => self.error_with_location(
+                AstGenErrorKind::ExpectedDeclarationAfterAttributes,
+                None,
+                self.peek().map(|token| token.kind),
+                self.next_location(),
+            )?,
             false => None,
         };
 
@@ -72,14 +82,9 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
             }
         }?;
 
-        // Depending on whether it's expected of the expression to have a semi-colon, we
-        // try and parse one anyway, if so
-        let has_semi = if semi_required {
-            self.parse_token(TokenKind::Semi)?;
-            true
-        } else {
-            self.parse_token_fast(TokenKind::Semi).is_some()
-        };
+        // Try and parse a terminating semi-colon, the caller decides whether it is
+        // required for the statement
+        let has_semi = self.parse_token_fast(TokenKind::Semi).is_some();
 
         Ok((has_semi, expr))
     }
@@ -97,6 +102,9 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
         let subject = match &token.kind {
             kind if kind.is_unary_op() => return self.parse_unary_expr(),
 
+            // Shorthand closure definition
+            TokenKind::Pipe => self.parse_closure_fn_def()?,
+
             // Handle primitive literals
             kind if kind.is_lit() => self.parse_lit(),
             TokenKind::Ident(ident) => {
@@ -185,6 +193,13 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
             // Import
             TokenKind::Keyword(Keyword::Import) => self.parse_import()?,
 
+            // Interpolated string literal
+            TokenKind::InterpolatedStrLit(tree_index) => {
+                let tree = self.token_trees.get(*tree_index).unwrap();
+
+                self.parse_interpolated_str_lit(tree, token.span)?
+            }
+
             // List literal
             TokenKind::Tree(Delimiter::Bracket, tree_index) => {
                 let tree = self.token_trees.get(*tree_index).unwrap();
@@ -359,7 +374,7 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
                                 expr: lhs,
                                 ty: self.parse_type()?,
                             })),
-                            &op_span,
+                            &lhs_span,
                         );
 
                         // since we don't descend, we still need to update the precedence to
@@ -380,6 +395,22 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
                         );
                     }
                 }
+                // Ranges bind less tightly than any binary operator, so they can only
+                // occur at the lowest precedence level. The bounds of the range can't
+                // be ranges themselves, so we stop after parsing the upper bound.
+                None if min_prec == 0 => {
+                    if let Some(end) = self.parse_range_operator() {
+                        let hi = self.parse_expr_with_precedence(1)?;
+                        self.is_compound_expr.set(true);
+
+                        lhs = self.node_with_joined_span(
+                            Expr::new(ExprKind::Range(RangeExpr { lo: lhs, hi, end })),
+                            &lhs_span,
+                        );
+                    }
+
+                    break;
+                }
                 _ => break,
             }
         }
@@ -399,8 +430,9 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
         // or a function call...
         while let Some(token) = self.peek() {
             subject = match token.kind {
-                // Property access or method call
-                TokenKind::Dot => {
+                // Property access or method call, unless this is the beginning of a range
+                TokenKind::Dot if !matches!(self.peek_second(), Some(token) if token.has_kind(TokenKind::Dot)) =>
+                {
                     self.skip_token();
                     self.parse_property_access(subject)?
                 }
@@ -428,6 +460,16 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
                     let tree = self.token_trees.get(tree_index).unwrap();
                     self.parse_constructor_call(subject, tree, self.current_location())?
                 }
+                // Error propagation: expr?
+                TokenKind::Question => {
+                    self.skip_token();
+
+                    let subject_span = subject.span();
+                    self.node_with_joined_span(
+                        Expr::new(ExprKind::Propagate(PropagateExpr { subject })),
+                        &subject_span,
+                    )
+                }
                 _ => break,
             }
         }
@@ -450,8 +492,8 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
 
         let gen = self.parse_delim_tree(Delimiter::Paren, None)?;
 
-        let (raw, path, span) = match gen.peek() {
-            Some(Token { kind: TokenKind::StrLit(str), span }) => (str, *str, span),
+        let (raw, path) = match gen.peek() {
+            Some(Token { kind: TokenKind::StrLit(str), .. }) => (str, *str),
             _ => gen.error(AstGenErrorKind::ImportPath, None, None)?,
         };
 
@@ -460,8 +502,9 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
 
         // Attempt to add the module via the resolver
         let import_path = PathBuf::from_str(path.into()).unwrap();
-        let resolved_import_path =
-            self.resolver.resolve_import(&import_path, self.source_location(span));
+        let resolved_import_path = self
+            .resolver
+            .resolve_import(&import_path, self.source_location(&pre.join(self.current_location())));
 
         match resolved_import_path {
             Ok(resolved_import_path) => Ok(self.node_with_joined_span(
@@ -488,6 +531,22 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
         tree: &'stream [Token],
         span: Span,
     ) -> AstGenResult<AstNode<Expr>> {
+        let args = self.parse_constructor_call_args(tree, span)?;
+        let subject_span = subject.span();
+
+        Ok(self.node_with_joined_span(
+            Expr::new(ExprKind::ConstructorCall(ConstructorCallExpr { subject, args })),
+            &subject_span,
+        ))
+    }
+
+    /// Parse the [ConstructorCallArgs] of a constructor call from the token
+    /// tree that is enclosed by the parentheses.
+    pub(crate) fn parse_constructor_call_args(
+        &self,
+        tree: &'stream [Token],
+        span: Span,
+    ) -> AstGenResult<AstNode<ConstructorCallArgs>> {
         let gen = self.from_stream(tree, span);
         let mut args = vec![];
 
@@ -496,11 +555,12 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
 
             // here we trying to check if this argument is in form of just an expression or
             // if there is a name being assigned here...
-            let name = match (gen.peek(), gen.peek_second()) {
+            let name = match (gen.peek(), gen.peek_second(), gen.peek_nth(2)) {
                 (
                     Some(Token { kind: TokenKind::Ident(_), .. }),
                     Some(Token { kind: TokenKind::Eq, .. }),
-                ) => {
+                    third,
+                ) if !matches!(third, Some(token) if token.has_kind(TokenKind::Eq)) => {
                     let name = gen.parse_name()?;
                     gen.skip_token(); // '='
 
@@ -512,7 +572,7 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
             // Now here we expect an expression...
             let value = gen.parse_expr_with_precedence(0)?;
 
-            args.push(gen.node_with_span(ConstructorCallArg { name, value }, start));
+            args.push(gen.node_with_joined_span(ConstructorCallArg { name, value }, &start));
 
             // now we eat the next token, checking that it is a comma
             match gen.peek() {
@@ -528,18 +588,8 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
         }
         gen.verify_is_empty()?;
 
-        let subject_span = subject.span();
-
-        Ok(self.node_with_joined_span(
-            Expr::new(ExprKind::ConstructorCall(ConstructorCallExpr {
-                subject,
-                args: self.node_with_span(
-                    ConstructorCallArgs { entries: AstNodes::new(args, Some(span)) },
-                    span,
-                ),
-            })),
-            &subject_span,
-        ))
+        Ok(self
+            .node_with_span(ConstructorCallArgs { entries: AstNodes::new(args, Some(span)) }, span))
     }
 
     /// Parse an array index. Array indexes are constructed with square brackets
@@ -551,7 +601,7 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
         span: Span,
     ) -> AstGenResult<AstNode<Expr>> {
         let gen = self.from_stream(tree, span);
-        let start = gen.current_location();
+        let subject_span = subject.span();
 
         // parse the indexing expression between the square brackets...
         let index_expr = gen.parse_expr_with_precedence(0)?;
@@ -561,9 +611,9 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
         // unexpected_token
         gen.verify_is_empty()?;
 
-        Ok(self.node_with_joined_span(
+        Ok(self.node_with_span(
             Expr::new(ExprKind::Index(IndexExpr { subject, index_expr })),
-            &start,
+            subject_span.join(span),
         ))
     }
 
@@ -595,11 +645,11 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
                             mutability,
                         })
                     }
-                    Some(Token { kind: TokenKind::Keyword(Keyword::Mut), span }) => {
+                    Some(Token { kind: TokenKind::Keyword(Keyword::Mut), span, .. }) => {
                         self.skip_token();
                         ExprKind::Ref(RefExpr {
                             inner_expr: self.parse_expr()?,
-                            kind: RefKind::Raw,
+                            kind: RefKind::Normal,
                             mutability: Some(self.node_with_span(Mutability::Mutable, *span)),
                         })
                     }
@@ -659,7 +709,11 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
     /// ^^^^^^^^  ^^^^^   ^^^─────┐
     /// pattern    type    the right hand-side expr
     /// ```
-    pub(crate) fn parse_declaration(&self, pattern: AstNode<Pat>) -> AstGenResult<Declaration> {
+    pub(crate) fn parse_declaration(
+        &self,
+        attributes: AstNodes<Attribute>,
+        pattern: AstNode<Pat>,
+    ) -> AstGenResult<Declaration> {
         // Attempt to parse an optional type...
         let ty = match self.peek() {
             Some(token) if token.has_kind(TokenKind::Eq) => None,
@@ -672,10 +726,58 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
                 self.skip_token();
 
                 let value = self.parse_expr_with_precedence(0)?;
-                Ok(Declaration { pat: pattern, ty, value: Some(value) })
+                Ok(Declaration { attributes, pat: pattern, ty, value: Some(value) })
             }
-            _ => Ok(Declaration { pat: pattern, ty, value: None }),
+            _ => Ok(Declaration { attributes, pat: pattern, ty, value: None }),
+        }
+    }
+
+    /// Parse the attributes that are applied to a declaration, e.g.
+    /// `#[inline]`. Multiple attributes can either be stacked, or separated by
+    /// commas within a single `#[...]`.
+    pub(crate) fn parse_attributes(&self) -> AstGenResult<AstNodes<Attribute>> {
+        let start = self.next_location();
+        let mut attributes = vec![];
+
+        while let (
+            Some(Token { kind: TokenKind::Hash, .. }),
+            Some(Token { kind: TokenKind::Tree(Delimiter::Bracket, tree_index), span, .. }),
+        ) = (self.peek(), self.peek_second())
+        {
+            self.offset.update(|x| x + 2);
+
+            let tree = self.token_trees.get(*tree_index).unwrap();
+            let gen = self.from_stream(tree, *span);
+
+            let entries = gen.parse_separated_fn(
+                || gen.parse_attribute(),
+                || gen.parse_token(TokenKind::Comma),
+            )?;
+
+            attributes.extend(entries.nodes);
         }
+
+        let span = (!attributes.is_empty()).then(|| start.join(self.current_location()));
+        Ok(AstNodes::new(attributes, span))
+    }
+
+    /// Parse a singular [Attribute], which is a name that is optionally
+    /// followed by arguments, e.g. `deprecated("use foo instead")`.
+    fn parse_attribute(&self) -> AstGenResult<AstNode<Attribute>> {
+        let name = self.parse_name()?;
+        let start = name.span();
+
+        let args = match self.peek() {
+            Some(Token { kind: TokenKind::Tree(Delimiter::Paren, tree_index), span, .. }) => {
+                self.skip_token();
+
+                let tree = self.token_trees.get(*tree_index).unwrap();
+                Some(self.parse_constructor_call_args(tree, *span)?)
+            }
+            _ => None,
+        };
+
+        Ok(self.node_with_joined_span(Attribute { name, args }, &start))
     }
 
     /// Function to pass a [MergeDeclaration] which is a pattern on the
@@ -710,7 +812,7 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
             return Ok((self.parse_merge_declaration(lhs)?, false));
         }
 
-        let start = self.current_location();
+        let start = self.next_location();
         let (operator, consumed_tokens) = self.parse_binary_operator();
 
         // Look at the token after the consumed tokens and see if it's an equal sign
@@ -724,9 +826,9 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
 
                 // Now we need to transform the re-assignment operator into a function call
                 Ok((
-                    self.node_with_span(
+                    self.node_with_joined_span(
                         Expr::new(ExprKind::AssignOp(AssignOpExpr { lhs, rhs, operator })),
-                        lhs_span,
+                        &lhs_span,
                     ),
                     false,
                 ))
@@ -909,11 +1011,75 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
         };
 
         Ok(self.node_with_joined_span(
-            Expr::new(ExprKind::FnDef(FnDef { params, return_ty, fn_body })),
+            Expr::new(ExprKind::FnDef(FnDef {
+                params,
+                return_ty,
+                fn_body,
+                captures: FnCaptures::default(),
+            })),
+            &start,
+        ))
+    }
+
+    /// Parse a [FnDef] that is written using the shorthand closure syntax,
+    /// e.g. `|x: i32| x + 1` or `|| 2`. This function expects that the opening
+    /// `|` has already been consumed. The parameters of a closure cannot have
+    /// default values, and their type annotations cannot contain union or
+    /// merge types since the `|` would be ambiguous with the end of the
+    /// parameter list. Such functions have to be written in the full
+    /// `(x: i32 | str) => ...` form.
+    pub(crate) fn parse_closure_fn_def(&self) -> AstGenResult<AstNode<Expr>> {
+        let start = self.current_location();
+        let mut params = vec![];
+
+        while self.parse_token_fast(TokenKind::Pipe).is_none() {
+            params.push(self.parse_closure_param()?);
+
+            // If there is no separating comma, then the parameter list must end.
+            if self.parse_token_fast(TokenKind::Comma).is_none() {
+                self.parse_token(TokenKind::Pipe)?;
+                break;
+            }
+        }
+
+        let params = AstNodes::new(params, Some(start.join(self.current_location())));
+
+        let fn_body = match self.peek() {
+            Some(_) => self.parse_expr_with_precedence(0)?,
+            None => self.error(AstGenErrorKind::ExpectedFnBody, None, None)?,
+        };
+
+        Ok(self.node_with_joined_span(
+            Expr::new(ExprKind::FnDef(FnDef {
+                params,
+                return_ty: None,
+                fn_body,
+                captures: FnCaptures::default(),
+            })),
             &start,
         ))
     }
 
+    /// Parse a [Param] within the parameter list of a shorthand closure.
+    fn parse_closure_param(&self) -> AstGenResult<AstNode<Param>> {
+        let name = self.parse_name()?;
+        let name_span = name.span();
+
+        // Stop before any union or merge type operators, otherwise the `|` that closes
+        // the parameter list would be parsed as a union type.
+        let ty = match self.parse_token_fast(TokenKind::Colon) {
+            Some(_) => {
+                Some(self.parse_type_with_precedence(BinTyOp::Union.infix_binding_power().0 + 1)?)
+            }
+            None => None,
+        };
+
+        Ok(self.node_with_joined_span(
+            Param { name, ty, default: None, origin: ParamOrigin::Fn },
+            &name_span,
+        ))
+    }
+
     /// Function to parse a sequence of top-level [Expr]s from a
     /// brace-block exhausting all of the remaining tokens within the block.
     /// This function expects that the next token is a [TokenKind::Tree] and
@@ -925,8 +1091,7 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
 
         // Continue eating the generator until no more tokens are present
         while gen.has_token() {
-            let (_, expr) = gen.parse_top_level_expr(true)?;
-            exprs.push(expr);
+            exprs.push(gen.parse_terminated_statement());
         }
         gen.verify_is_empty()?;
 
@@ -18,6 +18,9 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
                 TokenKind::FloatLit(num) => Lit::Float(FloatLit(num)),
                 TokenKind::CharLit(ch) => Lit::Char(CharLit(ch)),
                 TokenKind::StrLit(str) => Lit::Str(StrLit(str)),
+                TokenKind::RawStrLit(str) => Lit::RawStr(RawStrLit(str)),
+                TokenKind::ByteStrLit(str) => Lit::ByteStr(ByteStrLit(str)),
+                TokenKind::MultiLineStrLit(str) => Lit::MultiLineStr(MultiLineStrLit(str)),
                 TokenKind::Keyword(Keyword::False) => Lit::Bool(BoolLit(false)),
                 TokenKind::Keyword(Keyword::True) => Lit::Bool(BoolLit(true)),
                 _ => unreachable!(),
@@ -28,6 +31,44 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
         self.node_with_span(Expr::new(ExprKind::LitExpr(LitExpr(lit))), token.span)
     }
 
+    /// Parse an interpolated string literal from the segments that are stored
+    /// in the token tree of the literal. String segments are converted into
+    /// [Lit::Str] expressions, whilst the interpolated expressions are parsed
+    /// from the brace token trees that the lexer produced.
+    pub(crate) fn parse_interpolated_str_lit(
+        &self,
+        tree: &'stream [Token],
+        span: Span,
+    ) -> AstGenResult<AstNode<Expr>> {
+        let mut segments = AstNodes::empty();
+
+        for token in tree {
+            let segment = match token.kind {
+                TokenKind::StrLit(str) => self.node_with_span(
+                    Expr::new(ExprKind::LitExpr(LitExpr(
+                        self.node_with_span(Lit::Str(StrLit(str)), token.span),
+                    ))),
+                    token.span,
+                ),
+                TokenKind::Tree(Delimiter::Brace, tree_index) => {
+                    let tree = self.token_trees.get(tree_index).unwrap();
+                    let gen = self.from_stream(tree, token.span);
+
+                    let expr = gen.parse_expr_with_precedence(0)?;
+                    gen.verify_is_empty()?;
+
+                    expr
+                }
+                _ => unreachable!(),
+            };
+
+            segments.nodes.push(segment);
+        }
+
+        let lit = self.node_with_span(Lit::InterpolatedStr(InterpolatedStrLit { segments }), span);
+        Ok(self.node_with_span(Expr::new(ExprKind::LitExpr(LitExpr(lit))), span))
+    }
+
     /// Parse a single map entry in a literal.
     pub(crate) fn parse_map_entry(&self) -> AstGenResult<AstNode<MapLitEntry>> {
         let start = self.current_location();
@@ -76,8 +117,20 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
 
         // Determine if this might have a tuple field name and optional type
         let entry = if let Some(name) = self.peek_resultant_fn(|| self.parse_name()) {
-            // Here we can identify if we need to backtrack and just parse an expression...
-            if !matches!(self.peek(), Some(Token { kind: TokenKind::Colon | TokenKind::Eq, .. })) {
+            // Here we can identify if we need to backtrack and just parse an expression,
+            // which is also the case when the name is followed by a namespace access `::`
+            // or by a `==` comparison.
+            let is_entry_name = match (self.peek(), self.peek_second()) {
+                (Some(Token { kind: TokenKind::Colon, .. }), second) => {
+                    !matches!(second, Some(token) if token.has_kind(TokenKind::Colon))
+                }
+                (Some(Token { kind: TokenKind::Eq, .. }), second) => {
+                    !matches!(second, Some(token) if token.has_kind(TokenKind::Eq))
+                }
+                _ => false,
+            };
+
+            if !is_entry_name {
                 self.offset.set(offset);
                 None
             } else {
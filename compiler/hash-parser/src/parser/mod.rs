@@ -10,7 +10,10 @@ mod operator;
 mod pat;
 mod ty;
 
-use std::cell::Cell;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
 use hash_ast::ast::*;
 use hash_source::location::{SourceLocation, Span};
@@ -88,6 +91,11 @@ pub struct AstGen<'stream, 'resolver> {
     /// Instance of an [ImportResolver] to notify the parser of encountered
     /// imports.
     resolver: &'resolver ImportResolver<'resolver>,
+
+    /// Errors that the generator has recovered from. The collection is shared
+    /// between the root generator and all of the child generators that are
+    /// created from it, so that errors from nested token trees are not lost.
+    errors: Rc<RefCell<Vec<AstGenError>>>,
 }
 
 /// Implementation of the [AstGen] with accompanying functions to parse specific
@@ -106,6 +114,7 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
             is_compound_expr: Cell::new(false),
             offset: Cell::new(0),
             resolver,
+            errors: Rc::new(RefCell::new(vec![])),
         }
     }
 
@@ -120,9 +129,20 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
             is_compound_expr: self.is_compound_expr.clone(),
             parent_span: Some(parent_span),
             resolver: self.resolver,
+            errors: self.errors.clone(),
         }
     }
 
+    /// Record an error that the generator has recovered from.
+    pub(crate) fn add_error(&self, err: AstGenError) {
+        self.errors.borrow_mut().push(err);
+    }
+
+    /// Take all of the errors that the generator has recovered from.
+    pub fn take_errors(&self) -> Vec<AstGenError> {
+        self.errors.take()
+    }
+
     /// Function to create a [SourceLocation] from a [Span] by using the
     /// provided resolver
     pub(crate) fn source_location(&self, span: &Span) -> SourceLocation {
@@ -316,11 +336,14 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
     /// back to it's original settings.
     pub(crate) fn peek_resultant_fn<T, E>(&self, parse_fn: impl Fn() -> Result<T, E>) -> Option<T> {
         let start = self.offset();
+        let error_count = self.errors.borrow().len();
 
         match parse_fn() {
             Ok(result) => Some(result),
             Err(_) => {
+                // Any errors that were recovered from during the attempt are discarded too
                 self.offset.set(start);
+                self.errors.borrow_mut().truncate(error_count);
                 None
             }
         }
@@ -391,7 +414,7 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
         error: Option<AstGenErrorKind>,
     ) -> AstGenResult<Self> {
         match self.peek() {
-            Some(Token { kind: TokenKind::Tree(inner, tree_index), span })
+            Some(Token { kind: TokenKind::Tree(inner, tree_index), span, .. })
                 if *inner == delimiter =>
             {
                 self.skip_token();
@@ -408,13 +431,74 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
         }
     }
 
+    /// Record an error that occurred whilst parsing a statement that began at
+    /// the `start` offset, and then synchronise the generator by skipping to
+    /// the first `;` after the location of the error, or to the end of the
+    /// current token stream if there isn't one. Returns whether the
+    /// synchronisation consumed a `;`.
+    pub(crate) fn synchronise(&self, err: AstGenError, start: usize) -> bool {
+        let location = err.location().span;
+        self.add_error(err);
+
+        let semi = self.stream[start..].iter().position(|token| {
+            token.has_kind(TokenKind::Semi) && token.span.start() >= location.start()
+        });
+
+        match semi {
+            Some(index) => {
+                self.offset.set(start + index + 1);
+                true
+            }
+            None => {
+                self.offset.set(self.stream.len());
+                false
+            }
+        }
+    }
+
+    /// Recover from an error that occurred whilst parsing a statement that
+    /// began at the `start` offset by using [AstGen::synchronise]. The skipped
+    /// tokens are replaced by an [ExprKind::Err] node, which is returned
+    /// along with whether the recovery consumed a `;`.
+    pub(crate) fn recover_statement(
+        &self,
+        err: AstGenError,
+        start: usize,
+    ) -> (AstNode<Expr>, bool) {
+        let has_semi = self.synchronise(err, start);
+
+        let span = self.token_at(start).span.join(self.current_location());
+        (self.node_with_span(Expr::new(ExprKind::Err(ErrExpr)), span), has_semi)
+    }
+
+    /// Parse a top-level statement that must be terminated by a `;`. If the
+    /// statement is malformed, the generator recovers from the error and an
+    /// [ExprKind::Err] node is produced in place of the statement.
+    pub(crate) fn parse_terminated_statement(&self) -> AstNode<Expr> {
+        let start = self.offset();
+
+        match self.parse_top_level_expr() {
+            Ok((has_semi, statement)) => {
+                // The statement is kept since only the terminator is missing
+                if !has_semi {
+                    if let Err(err) = self.parse_token(TokenKind::Semi) {
+                        self.synchronise(err, start);
+                    }
+                }
+
+                statement
+            }
+            Err(err) => self.recover_statement(err, start).0,
+        }
+    }
+
     /// Parse a [Module] which is simply made of a list of statements
     pub(crate) fn parse_module(&self) -> AstGenResult<AstNode<Module>> {
         let start = self.current_location();
         let mut contents = vec![];
 
         while self.has_token() {
-            contents.push(self.parse_top_level_expr(true).map(|(_, statement)| statement)?);
+            contents.push(self.parse_terminated_statement());
         }
 
         let span = start.join(self.current_location());
@@ -18,7 +18,7 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
     #[inline]
     pub fn parse_name_with_error(&self, err: AstGenErrorKind) -> AstGenResult<AstNode<Name>> {
         match self.next_token() {
-            Some(Token { kind: TokenKind::Ident(ident), span })
+            Some(Token { kind: TokenKind::Ident(ident), span, .. })
                 if *ident != CORE_IDENTIFIERS.underscore =>
             {
                 Ok(self.node_with_span(Name { ident: *ident }, *span))
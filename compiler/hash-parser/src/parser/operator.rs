@@ -57,6 +57,7 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
             },
             TokenKind::Pipe => match self.peek_second() {
                 Some(token) if token.kind == TokenKind::Pipe => (Some(BinOp::Or), 2),
+                Some(token) if token.kind == TokenKind::Gt => (Some(BinOp::Pipe), 2),
                 _ => (Some(BinOp::BitOr), 1),
             },
             TokenKind::Exclamation => match self.peek_second() {
@@ -67,6 +68,29 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
         }
     }
 
+    /// Parse the operator of a range, either being `..` or `..=`, returning the
+    /// [RangeEnd] that the operator denotes. If the next tokens don't form a
+    /// range operator, then no tokens are consumed. A spread `...` is never
+    /// considered to be a range operator.
+    pub(crate) fn parse_range_operator(&self) -> Option<RangeEnd> {
+        let is_dot = |n| matches!(self.peek_nth(n), Some(token) if token.has_kind(TokenKind::Dot));
+
+        if !is_dot(0) || !is_dot(1) || is_dot(2) {
+            return None;
+        }
+
+        match self.peek_nth(2) {
+            Some(token) if token.has_kind(TokenKind::Eq) => {
+                self.offset.update(|x| x + 3);
+                Some(RangeEnd::Included)
+            }
+            _ => {
+                self.offset.update(|x| x + 2);
+                Some(RangeEnd::Excluded)
+            }
+        }
+    }
+
     /// Function to parse a [BinTyOp] which returns a type operator if one is
     /// present, and the number of tokens consumed. If no type operator follows,
     /// then the consumed tokens count will be 0.
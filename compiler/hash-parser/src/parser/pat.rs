@@ -1,7 +1,11 @@
 //! Hash Compiler AST generation sources. This file contains the sources to the
 //! logic that transforms tokens into an AST.
 use hash_ast::{ast::*, ast_nodes};
-use hash_source::{identifier::CORE_IDENTIFIERS, location::Span};
+use hash_source::{
+    constant::{FloatConstantValue, IntConstantValue},
+    identifier::CORE_IDENTIFIERS,
+    location::Span,
+};
 use hash_token::{delimiter::Delimiter, keyword::Keyword, Token, TokenKind, TokenKindVector};
 
 use super::{error::AstGenErrorKind, AstGen, AstGenResult};
@@ -133,20 +137,25 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
             // Spread pattern
             token if token.has_kind(TokenKind::Dot) => Pat::Spread(self.parse_spread_pat()?),
 
-            // Literal patterns
-            token if token.kind.is_lit() => {
-                self.skip_token();
-                Pat::Lit(self.convert_lit_into_pat(&token.kind))
+            // Literal patterns, byte strings can't be used as patterns
+            token if is_pat_lit(token) || token.has_kind(TokenKind::Minus) => {
+                let lit = self.parse_lit_pat()?;
+
+                // A literal might be the lower bound of a range pattern
+                match self.parse_range_operator() {
+                    Some(end) => Pat::Range(self.parse_range_pat(lit, end)?),
+                    None => Pat::Lit(lit.into_body()),
+                }
             }
             // Tuple patterns
-            Token { kind: TokenKind::Tree(Delimiter::Paren, tree_index), span } => {
+            Token { kind: TokenKind::Tree(Delimiter::Paren, tree_index), span, .. } => {
                 self.skip_token();
                 let tree = self.token_trees.get(*tree_index).unwrap();
 
                 return self.parse_tuple_pat(tree, *span);
             }
             // Namespace patterns
-            Token { kind: TokenKind::Tree(Delimiter::Brace, tree_index), span } => {
+            Token { kind: TokenKind::Tree(Delimiter::Brace, tree_index), span, .. } => {
                 self.skip_token();
                 let tree = self.token_trees.get(*tree_index).unwrap();
 
@@ -155,7 +164,7 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
                 Pat::Module(pat)
             }
             // List pattern
-            Token { kind: TokenKind::Tree(Delimiter::Bracket, tree_index), span } => {
+            Token { kind: TokenKind::Tree(Delimiter::Bracket, tree_index), span, .. } => {
                 self.skip_token();
                 let tree = self.token_trees.get(*tree_index).unwrap();
 
@@ -321,7 +330,9 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
     /// Convert a [Lit] into a [LitPat].
     pub(crate) fn convert_lit_into_pat(&self, kind: &TokenKind) -> LitPat {
         match kind {
-            TokenKind::StrLit(s) => LitPat::Str(StrLitPat(*s)),
+            TokenKind::StrLit(s) | TokenKind::RawStrLit(s) | TokenKind::MultiLineStrLit(s) => {
+                LitPat::Str(StrLitPat(*s))
+            }
             TokenKind::CharLit(s) => LitPat::Char(CharLitPat(*s)),
             TokenKind::IntLit(s) => LitPat::Int(IntLitPat(*s)),
             TokenKind::FloatLit(s) => LitPat::Float(FloatLitPat(*s)),
@@ -331,6 +342,58 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
         }
     }
 
+    /// Parse a [LitPat], a numeric literal can be preceded by a `-` to
+    /// negate it, e.g. `-5`.
+    fn parse_lit_pat(&self) -> AstGenResult<AstNode<LitPat>> {
+        let token = self.next_token().unwrap();
+
+        if !token.has_kind(TokenKind::Minus) {
+            return Ok(self.node_with_span(self.convert_lit_into_pat(&token.kind), token.span));
+        }
+
+        let lit = self.peek();
+
+        let pat = match lit.map(|lit| lit.kind) {
+            Some(TokenKind::IntLit(constant)) => {
+                let IntConstantValue { value, suffix } = constant.value();
+                LitPat::Int(IntLitPat(IntConstantValue { value: -value, suffix }.into()))
+            }
+            Some(TokenKind::FloatLit(constant)) => {
+                let FloatConstantValue { text, suffix } = constant.value();
+                let text = format!("-{}", text);
+                LitPat::Float(FloatLitPat(FloatConstantValue { text, suffix }.into()))
+            }
+            kind => self.error_with_location(
+                AstGenErrorKind::ExpectedNumericLitAfterMinus,
+                None,
+                kind,
+                lit.map_or_else(|| self.next_location(), |lit| lit.span),
+            )?,
+        };
+
+        let span = token.span.join(lit.unwrap().span);
+        self.skip_token();
+
+        Ok(self.node_with_span(pat, span))
+    }
+
+    /// Parse the rest of a [RangePat] after the range operator, given the
+    /// lower bound of the range.
+    fn parse_range_pat(&self, lo: AstNode<LitPat>, end: RangeEnd) -> AstGenResult<RangePat> {
+        match self.peek() {
+            Some(token) if is_pat_lit(token) || token.has_kind(TokenKind::Minus) => {
+                let hi = self.parse_lit_pat()?;
+                Ok(RangePat { lo, hi, end })
+            }
+            token => self.error_with_location(
+                AstGenErrorKind::ExpectedRangePatBound,
+                None,
+                token.map(|t| t.kind),
+                token.map_or_else(|| self.next_location(), |t| t.span),
+            ),
+        }
+    }
+
     /// Parse a spread operator from the current token tree. A spread operator
     /// can have an optional name attached to the spread operator on the
     /// right hand-side.
@@ -378,10 +441,10 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
     /// Parse a [Visibility] modifier, either being a `pub` or `priv`.
     fn parse_visibility(&self) -> AstGenResult<AstNode<Visibility>> {
         match self.next_token() {
-            Some(Token { kind: TokenKind::Keyword(Keyword::Pub), span }) => {
+            Some(Token { kind: TokenKind::Keyword(Keyword::Pub), span, .. }) => {
                 Ok(self.node_with_span(Visibility::Public, *span))
             }
-            Some(Token { kind: TokenKind::Keyword(Keyword::Priv), span }) => {
+            Some(Token { kind: TokenKind::Keyword(Keyword::Priv), span, .. }) => {
                 Ok(self.node_with_span(Visibility::Private, *span))
             }
             token => self.error_with_location(
@@ -401,9 +464,10 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
         // Perform the initial pattern component lookahead
         let mut n_lookahead = match self.peek() {
             // Literals are allowed, but they must be immediately followed
-            // by a colon
+            // by a colon, and not by a `::` which begins a namespace access on the literal
             Some(token) if token.kind.is_lit() => {
-                return matches!(self.peek_second(), Some(token) if token.has_kind(TokenKind::Colon));
+                return matches!(self.peek_second(), Some(token) if token.has_kind(TokenKind::Colon))
+                    && !matches!(self.peek_nth(2), Some(token) if token.has_kind(TokenKind::Colon));
             }
             // Namespace, List, Tuple, etc.
             Some(Token { kind: TokenKind::Tree(_, _), .. }) => 1,
@@ -462,3 +526,9 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
         matches!(self.peek_nth(n_lookahead), Some(token) if token.has_kind(TokenKind::Colon))
     }
 }
+
+/// Check if the token is a literal that can be used within a pattern, byte
+/// strings can't be used as patterns.
+fn is_pat_lit(token: &Token) -> bool {
+    token.kind.is_lit() && !matches!(token.kind, TokenKind::ByteStrLit(_))
+}
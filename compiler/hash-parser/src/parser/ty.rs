@@ -17,7 +17,7 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
     /// contains [TyOp] operators which are binary operators for type terms.
     /// This function implements the same precedence algorithm for correctly
     /// forming binary type expressions.
-    fn parse_type_with_precedence(&self, min_prec: u8) -> AstGenResult<AstNode<Ty>> {
+    pub(crate) fn parse_type_with_precedence(&self, min_prec: u8) -> AstGenResult<AstNode<Ty>> {
         let mut lhs = self.parse_singular_type()?;
         let lhs_span = lhs.span();
 
@@ -314,7 +314,7 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
         let mut args = vec![];
 
         loop {
-            let span = self.current_location();
+            let start = self.next_location();
             let name = self.parse_name()?;
 
             let ty = match self.parse_token_fast(TokenKind::Colon) {
@@ -331,7 +331,7 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
                 None => None,
             };
 
-            args.push(self.node_with_span(
+            args.push(self.node_with_joined_span(
                 Param {
                     name,
                     ty,
@@ -341,7 +341,7 @@ impl<'stream, 'resolver> AstGen<'stream, 'resolver> {
                     }),
                     origin: ParamOrigin::TyFn,
                 },
-                span,
+                &start,
             ));
 
             // Now consider if the bound is closing or continuing with a comma...
@@ -4,8 +4,11 @@ use hash_reporting::{
     report::{Report, ReportCodeBlock, ReportElement, ReportKind, ReportNote, ReportNoteKind},
 };
 use hash_source::location::SourceLocation;
+use hash_utils::path::adjust_canonicalization;
+
+use crate::settings::CompilerSettings;
 use std::{
-    fs,
+    fs, iter,
     path::{Path, PathBuf},
 };
 use thiserror::Error;
@@ -25,6 +28,8 @@ pub struct ImportError {
     pub filename: PathBuf,
     pub message: String,
     pub src: Option<SourceLocation>,
+    /// The paths that were tried when attempting to resolve the module.
+    pub tried: Vec<PathBuf>,
 }
 
 impl ImportError {
@@ -38,12 +43,19 @@ impl ImportError {
                 .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(src, "here")))
                 .add_element(ReportElement::Note(ReportNote::new(
                     ReportNoteKind::Note,
-                    &self.message,
+                    self.to_string(),
                 )));
         } else {
             builder.with_message(format!("Failed to import: {}", self.message));
         }
 
+        for path in &self.tried {
+            builder.add_element(ReportElement::Note(ReportNote::new(
+                ReportNoteKind::Note,
+                format!("tried `{}`", adjust_canonicalization(path)),
+            )));
+        }
+
         builder.build()
     }
 }
@@ -101,6 +113,7 @@ pub fn read_in_path(path: &Path) -> Result<String, ImportError> {
         src: None,
         message: format!("Cannot read file: {}", path.to_string_lossy()),
         filename: path.to_owned(),
+        tried: vec![],
     })
 }
 
@@ -116,6 +129,14 @@ pub fn read_in_path(path: &Path) -> Result<String, ImportError> {
 ///   within the standard library. If the specified path has `.hash` extension,
 ///   this circumvents the assumption.
 ///
+/// - If the first component of the path is the name of a dependency within the
+///   package manifest of the `settings`, then the rest of the path is resolved
+///   relative to the directory of the dependency.
+///
+/// - Otherwise, the path is resolved relative to `wd`, and then relative to
+///   each of the search paths of the `settings` in order. The first of these
+///   roots that contains the module is used.
+///
 /// - If the specified path has no `.hash` extension, and the path is not a
 ///   file, but a directory, then it is assumed that the path might be referring
 ///   to a `index.hash` file within the directory itself. If there is an
@@ -123,15 +144,16 @@ pub fn read_in_path(path: &Path) -> Result<String, ImportError> {
 ///   the directory level, then it is assumed that `index.hash` is the
 ///   reference.
 ///
-/// - If the file path exists, relative `wd`, then the combined file path is the
-///   module path.
+/// - If the file path exists, relative to the root, then the combined file path
+///   is the module path.
 ///
 /// ## Errors
-/// - If the path to the module couldn't be resolved, an [ImportError] is
-///   raised.
+/// - If the path to the module couldn't be resolved, an [ImportError] is raised
+///   which lists all of the paths that were tried.
 pub fn resolve_path(
     path: impl AsRef<Path>,
     wd: impl AsRef<Path>,
+    settings: &CompilerSettings,
     location: Option<SourceLocation>,
 ) -> Result<PathBuf, ImportError> {
     let path = path.as_ref();
@@ -144,35 +166,55 @@ pub fn resolve_path(
         return Ok(path.to_path_buf());
     }
 
-    // otherwise, we have to resolve the module path based on the working directory
-    let work_dir = wd.canonicalize().unwrap();
-    let raw_path = work_dir.join(path);
+    // If the path refers to a dependency of the package, then it is only resolved
+    // within the dependency, otherwise we have to resolve the module path based on
+    // the working directory, and then any of the provided search paths
+    let dependency =
+        settings.manifest.as_ref().and_then(|manifest| manifest.resolve_dependency(path));
 
-    // If the provided path is a directory, we assume that the user is referencing
-    // an index module that is located within the given directory. This takes
-    // precedence over checking if a module is named that directory.
-    // More info on this topic: https://hash-org.github.io/lang/modules.html#importing
-    if raw_path.is_dir() {
-        let idx_path = raw_path.join("index.hash");
+    let candidates: Vec<PathBuf> = match dependency {
+        Some(dependency) => vec![dependency],
+        None => iter::once(wd.canonicalize().unwrap())
+            .chain(settings.search_paths.iter().cloned())
+            .map(|root| root.join(path))
+            .collect(),
+    };
 
-        if idx_path.exists() {
-            return Ok(idx_path);
-        }
+    let mut tried = vec![];
+    let mut found_dir = false;
+
+    for raw_path in candidates {
+        found_dir |= raw_path.is_dir();
 
-        // ok now check if the user is referencing a module instead of the dir
-        let raw_path_hash = raw_path.with_extension("hash");
-        if raw_path_hash.exists() {
-            return Ok(raw_path_hash);
+        if let Some(resolved) = resolve_path_in_root(raw_path, &mut tried) {
+            return Ok(resolved);
         }
+    }
 
-        // @@Copied
-        Err(ImportError {
-            filename: path.to_path_buf(),
-            message:
-                "This directory likely doesn't have a `index.hash` module, consider creating one."
-                    .to_string(),
-            src: location,
-        })
+    let message = if found_dir {
+        "This directory likely doesn't have a `index.hash` module, consider creating one."
+    } else {
+        "Module couldn't be found"
+    };
+
+    Err(ImportError {
+        filename: path.to_path_buf(),
+        message: message.to_string(),
+        src: location,
+        tried,
+    })
+}
+
+/// Attempt to resolve a module at the `raw_path` which is the module path
+/// joined with a search root, or the path within a dependency. Any of the
+/// paths that are checked are recorded in `tried`.
+fn resolve_path_in_root(raw_path: PathBuf, tried: &mut Vec<PathBuf>) -> Option<PathBuf> {
+    // If the provided path is a directory, we assume that the user is referencing
+    // an index module that is located within the given directory. This takes
+    // precedence over checking if a module is named that directory.
+    // More info on this topic: https://hash-org.github.io/lang/modules.html#importing
+    let candidates = if raw_path.is_dir() {
+        vec![raw_path.join("index.hash"), raw_path.with_extension("hash")]
     } else {
         // we don't need to anything if the given raw_path already has a extension
         // '.hash', since we don't disallow someone to import a module and
@@ -185,25 +227,21 @@ pub fn resolve_path(
         // ```text
         // lib := import("lib");
         // ```
-
         match raw_path.extension() {
-            Some(k) if k == "hash" => Ok(raw_path),
-            _ => {
-                // add hash extension regardless and check if the path exists...
-                let raw_path_hash = raw_path.with_extension("hash");
-
-                // Only try to check this route if the provided file did not already have an
-                // extension
-                if raw_path.extension().is_none() && raw_path_hash.exists() {
-                    Ok(raw_path_hash)
-                } else {
-                    Err(ImportError {
-                        filename: path.to_path_buf(),
-                        message: "Module couldn't be found".to_string(),
-                        src: location,
-                    })
-                }
-            }
+            Some(k) if k == "hash" => vec![raw_path],
+            // Only try to add the extension if the provided file did not already have one
+            None => vec![raw_path.with_extension("hash")],
+            Some(_) => vec![],
+        }
+    };
+
+    for candidate in candidates {
+        if candidate.is_file() {
+            return Some(candidate);
         }
+
+        tried.push(candidate);
     }
+
+    None
 }
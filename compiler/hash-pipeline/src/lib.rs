@@ -6,6 +6,7 @@
 //! used. This file also has definitions for how to access sources whether
 //! module or interactive.
 pub mod fs;
+pub mod manifest;
 pub mod settings;
 pub mod sources;
 pub mod traits;
@@ -13,11 +14,11 @@ pub mod traits;
 use std::{collections::HashMap, env, time::Duration};
 
 use fs::{read_in_path, resolve_path, PRELUDE};
-use hash_ast::{ast::OwnsAstNode, tree::AstTreeGenerator, visitor::AstVisitor};
+use hash_ast::{ast::OwnsAstNode, json::to_json, tree::AstTreeGenerator, visitor::AstVisitor};
 use hash_reporting::{report::Report, writer::ReportWriter};
 use hash_source::{ModuleKind, SourceId};
 use hash_utils::{path::adjust_canonicalization, timed, tree_writing::TreeWriter};
-use settings::{CompilerJobParams, CompilerMode, CompilerSettings};
+use settings::{AstFormat, CompilerJobParams, CompilerMode, CompilerSettings};
 use sources::{Module, Workspace};
 use traits::{Desugar, Parser, SemanticPass, Tc, VirtualMachine};
 
@@ -155,7 +156,11 @@ where
 
     /// Utility function used by AST like stages in order to print the
     /// current [Sources].
-    fn print_sources(&self, workspace: &Workspace, entry_point: SourceId) {
+    fn print_sources(&self, workspace: &Workspace, entry_point: SourceId, format: AstFormat) {
+        if format == AstFormat::Json {
+            return self.print_sources_as_json(workspace, entry_point);
+        }
+
         match entry_point {
             SourceId::Interactive(id) => {
                 // If this is an interactive statement, we want to print the statement that was
@@ -182,15 +187,40 @@ where
         }
     }
 
-    /// Function to invoke a parsing job of a specified [SourceId].
+    /// Print the AST of the given [SourceId] as JSON. An interactive block is
+    /// printed as a single [hash_ast::ast::BodyBlock], and modules are
+    /// printed as an array of objects with the `path` and the `module` of
+    /// each of the generated modules.
+    fn print_sources_as_json(&self, workspace: &Workspace, entry_point: SourceId) {
+        let output = match entry_point {
+            SourceId::Interactive(id) => {
+                to_json(workspace.node_map().get_interactive_block(id).node())
+            }
+            SourceId::Module(_) => workspace
+                .node_map()
+                .iter_modules()
+                .map(|(_, generated_module)| {
+                    serde_json::json!({
+                        "path": adjust_canonicalization(generated_module.path()),
+                        "module": to_json(generated_module.node()),
+                    })
+                })
+                .collect(),
+        };
+
+        println!("{}", output);
+    }
+
+    /// Function to invoke a parsing job of a specified [SourceId], returning
+    /// any warnings that were generated by parsing.
     fn parse_source(
         &mut self,
         entry_point: SourceId,
         workspace: &mut Workspace,
         job_params: &CompilerJobParams,
-    ) -> CompilerResult<()> {
-        timed(
-            || self.parser.parse(entry_point, workspace, self.pool),
+    ) -> CompilerResult<Vec<Report>> {
+        let warnings = timed(
+            || self.parser.parse(entry_point, workspace, &self.settings, self.pool),
             log::Level::Debug,
             |time| {
                 self.metrics.insert(CompilerMode::Parse, time);
@@ -200,10 +230,10 @@ where
         // We want to loop through all of the generated modules and print
         // the resultant AST
         if job_params.mode == CompilerMode::Parse && job_params.output_stage_result {
-            self.print_sources(workspace, entry_point);
+            self.print_sources(workspace, entry_point, job_params.ast_format);
         }
 
-        Ok(())
+        Ok(warnings)
     }
 
     /// De-sugaring stage within the pipeline.
@@ -225,7 +255,7 @@ where
         // We want to loop through all of the generated modules and print
         // the resultant AST
         if job_params.mode == CompilerMode::DeSugar && job_params.output_stage_result {
-            self.print_sources(workspace, entry_point);
+            self.print_sources(workspace, entry_point, job_params.ast_format);
         }
 
         Ok(())
@@ -335,14 +365,23 @@ where
     /// Run a particular job within the pipeline. This function handles both
     /// cases of either the entry point being an [InteractiveId] or a
     /// [ModuleId]. The function deals with executing the required stages in
-    /// order as specified by the `job_parameters`
+    /// order as specified by the `job_parameters`, the parsing stage is only
+    /// run if `parse` is set.
     fn run_pipeline(
         &mut self,
         entry_point: SourceId,
         compiler_state: &mut CompilerState<'c, 'pool, D, S, C, V>,
         job_params: CompilerJobParams,
+        parse: bool,
     ) -> Result<(), ()> {
-        let result = self.parse_source(entry_point, &mut compiler_state.workspace, &job_params);
+        // The parsing stage is skipped if the AST of the entry point was already
+        // provided
+        let result = if parse {
+            self.parse_source(entry_point, &mut compiler_state.workspace, &job_params)
+                .map(|warnings| compiler_state.diagnostics.extend(warnings))
+        } else {
+            Ok(())
+        };
         self.maybe_terminate(result, compiler_state, &job_params, CompilerMode::Parse)?;
 
         let result = self.desugar_sources(
@@ -404,8 +443,34 @@ where
         mut compiler_state: CompilerState<'c, 'pool, D, S, C, V>,
         job_params: CompilerJobParams,
     ) -> CompilerState<'c, 'pool, D, S, C, V> {
-        let result = self.run_pipeline(entry_point, &mut compiler_state, job_params);
+        let result = self.run_pipeline(entry_point, &mut compiler_state, job_params, true);
+        self.finish(result, compiler_state)
+    }
 
+    /// Run a job within the compiler pipeline on an entry point whose AST is
+    /// already stored within the [Workspace], for example a
+    /// [hash_ast::ast::Module] or a [hash_ast::ast::BodyBlock] that was
+    /// loaded by [hash_ast::json::from_json]. The parsing stage is skipped,
+    /// so any modules that the entry point imports must already be within the
+    /// [Workspace] too. The node can be added with [Module::with_node] or
+    /// [sources::InteractiveBlock::with_node].
+    pub fn run_parsed(
+        &mut self,
+        entry_point: SourceId,
+        mut compiler_state: CompilerState<'c, 'pool, D, S, C, V>,
+        job_params: CompilerJobParams,
+    ) -> CompilerState<'c, 'pool, D, S, C, V> {
+        let result = self.run_pipeline(entry_point, &mut compiler_state, job_params, false);
+        self.finish(result, compiler_state)
+    }
+
+    /// Print all of the diagnostics that were collected by a job, and the
+    /// metrics of the stages if they are enabled.
+    fn finish(
+        &self,
+        result: Result<(), ()>,
+        compiler_state: CompilerState<'c, 'pool, D, S, C, V>,
+    ) -> CompilerState<'c, 'pool, D, S, C, V> {
         // we can print the diagnostics here
         if !compiler_state.diagnostics.is_empty() || result.is_err() {
             let mut err_count = 0;
@@ -458,7 +523,7 @@ where
     ) -> CompilerState<'c, 'pool, D, S, C, V> {
         // First we have to work out if we need to transform the path
         let current_dir = env::current_dir().unwrap();
-        let filename = resolve_path(filename, current_dir, None);
+        let filename = resolve_path(filename, current_dir, &self.settings, None);
 
         if let Err(err) = filename {
             eprintln!(
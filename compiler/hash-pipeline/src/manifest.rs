@@ -0,0 +1,200 @@
+//! Hash Compiler package manifest. A manifest is a `hash.toml` file that is
+//! located at the root of a package, it declares the name of the package, the
+//! entry point module of the package, and any local dependencies that can be
+//! imported by name. For example:
+//!
+//! ```toml
+//! [package]
+//! name = "app"
+//! entry = "src/main.hash"
+//!
+//! [dependencies]
+//! mylib = { path = "../mylib" }
+//! ```
+//!
+//! With this manifest, `import("mylib/utils")` resolves to the `utils` module
+//! within the `../mylib` directory.
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use serde::Deserialize;
+use thiserror::Error;
+
+/// The name of the file that contains the manifest of a package.
+pub static MANIFEST_FILE_NAME: &str = "hash.toml";
+
+/// An error that occurs when a manifest cannot be read or is malformed.
+#[derive(Debug, Clone, Error)]
+#[error("Couldn't load the manifest `{}`: {message}", path.display())]
+pub struct ManifestError {
+    pub path: PathBuf,
+    pub message: String,
+}
+
+/// The `[package]` section of the manifest.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Package {
+    /// The name of the package.
+    pub name: String,
+    /// The path to the entry point module of the package, relative to the
+    /// manifest. This is `main.hash` if it is not specified.
+    #[serde(default = "default_entry")]
+    pub entry: PathBuf,
+}
+
+/// Get the default entry point of a package.
+fn default_entry() -> PathBuf {
+    PathBuf::from("main.hash")
+}
+
+/// A dependency of the package which is declared in the `[dependencies]`
+/// section of the manifest.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Dependency {
+    /// The directory of the dependency, relative to the manifest.
+    pub path: PathBuf,
+}
+
+/// The contents of the manifest file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ManifestContents {
+    package: Package,
+    #[serde(default)]
+    dependencies: BTreeMap<String, Dependency>,
+}
+
+/// A loaded package manifest.
+#[derive(Debug, Clone)]
+pub struct Manifest {
+    /// The directory that contains the manifest, all of the paths within the
+    /// manifest are relative to this directory.
+    pub root: PathBuf,
+    /// Information about the package itself.
+    pub package: Package,
+    /// Dependencies of the package by the name that they are imported with.
+    pub dependencies: BTreeMap<String, Dependency>,
+}
+
+impl Manifest {
+    /// Load the manifest from the file at the given `path`.
+    pub fn load(path: &Path) -> Result<Self, ManifestError> {
+        let error = |message: String| ManifestError { path: path.to_path_buf(), message };
+
+        let contents = fs::read_to_string(path).map_err(|err| error(err.to_string()))?;
+        let ManifestContents { package, dependencies } =
+            toml::from_str(&contents).map_err(|err| error(err.to_string()))?;
+
+        let root = path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+        // Dependency names are matched against the first component of an import path
+        if let Some(name) = dependencies.keys().find(|name| !is_valid_dependency_name(name)) {
+            return Err(error(format!("`{name}` is not a valid dependency name")));
+        }
+
+        Ok(Self { root, package, dependencies })
+    }
+
+    /// Find the manifest of the package that contains the directory `dir`, by
+    /// searching for a manifest file in `dir` and then in each of its
+    /// ancestors. Returns [None] if there is no manifest.
+    pub fn find(dir: &Path) -> Result<Option<Self>, ManifestError> {
+        dir.ancestors()
+            .map(|ancestor| ancestor.join(MANIFEST_FILE_NAME))
+            .find(|path| path.is_file())
+            .map(|path| Self::load(&path))
+            .transpose()
+    }
+
+    /// Get the path of the entry point module of the package.
+    pub fn entry_point(&self) -> PathBuf {
+        self.root.join(&self.package.entry)
+    }
+
+    /// Resolve an import path through the dependencies of the package. If the
+    /// first component of the path names a dependency, then the path within
+    /// the directory of the dependency is returned.
+    pub fn resolve_dependency(&self, path: &Path) -> Option<PathBuf> {
+        let mut components = path.components();
+
+        let name = match components.next()? {
+            Component::Normal(name) => name.to_str()?,
+            _ => return None,
+        };
+
+        let dependency = self.root.join(&self.dependencies.get(name)?.path);
+        let rest = components.as_path();
+
+        if rest.as_os_str().is_empty() {
+            Some(dependency)
+        } else {
+            Some(dependency.join(rest))
+        }
+    }
+}
+
+/// Check whether the name of a dependency can be used to import it, which
+/// means that it must be a single path component.
+fn is_valid_dependency_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains(['/', '\\']) && name != "." && name != ".."
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    /// Write the `contents` into a manifest file within a fresh directory, and
+    /// load it.
+    fn manifest(contents: &str) -> Result<Manifest, ManifestError> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let id = COUNTER.fetch_add(1, Ordering::SeqCst);
+        let dir = std::env::temp_dir().join(format!("hash-manifest-{}-{id}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let path = dir.join(MANIFEST_FILE_NAME);
+        fs::write(&path, contents).unwrap();
+
+        let result = Manifest::load(&path);
+        fs::remove_dir_all(&dir).unwrap();
+        result
+    }
+
+    #[test]
+    fn resolve_dependencies() {
+        let manifest = manifest(
+            "[package]\nname = \"app\"\n\n[dependencies]\nmylib = { path = \"vendor/mylib\" }\n",
+        )
+        .unwrap();
+
+        let root = &manifest.root;
+        assert_eq!(manifest.entry_point(), root.join("main.hash"));
+        assert_eq!(
+            manifest.resolve_dependency(Path::new("mylib/utils")),
+            Some(root.join("vendor/mylib/utils"))
+        );
+        assert_eq!(
+            manifest.resolve_dependency(Path::new("mylib")),
+            Some(root.join("vendor/mylib"))
+        );
+        assert_eq!(manifest.resolve_dependency(Path::new("other/utils")), None);
+        assert_eq!(manifest.resolve_dependency(Path::new("./mylib")), None);
+    }
+
+    #[test]
+    fn malformed_manifests() {
+        assert!(manifest("[package]\n").is_err());
+        assert!(manifest("[package]\nname = \"app\"\nversion = 1\n").is_err());
+        assert!(manifest(
+            "[package]\nname = \"app\"\n[dependencies]\n\"a/b\" = { path = \"b\" }\n"
+        )
+        .is_err());
+    }
+}
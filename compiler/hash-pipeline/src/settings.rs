@@ -1,11 +1,17 @@
 //! Hash Compiler pipeline implementation. This file contains various structures
 //! and utilities representing settings and configurations that can be applied
 //! to the Compiler pipeline.
-use std::fmt::Display;
+use std::{env, fmt::Display, path::PathBuf};
+
+use crate::manifest::Manifest;
+
+/// The environment variable that specifies additional module search paths,
+/// the paths are separated in the same way as the `PATH` variable.
+pub static HASH_PATH: &str = "HASH_PATH";
 
 /// Various settings that are present on the compiler pipeline when initially
 /// launching.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct CompilerSettings {
     /// Print metrics about each stage when the entire pipeline has completed.
     ///
@@ -17,11 +23,37 @@ pub struct CompilerSettings {
     /// This value is used to determine the thread pool size that is then shared
     /// across arbitrary stages within the compiler.
     pub worker_count: usize,
+
+    /// An ordered list of directories that are searched when resolving a
+    /// module import, after the directory of the importing module.
+    pub search_paths: Vec<PathBuf>,
+
+    /// The manifest of the package that is being compiled, if there is one.
+    /// Dependencies that are declared in the manifest can be imported by name.
+    pub manifest: Option<Manifest>,
 }
 
 impl CompilerSettings {
     pub fn new(display_metrics: bool, worker_count: usize) -> Self {
-        Self { worker_count, display_metrics }
+        Self { worker_count, display_metrics, search_paths: vec![], manifest: None }
+    }
+
+    /// Set the package [Manifest] that is used to resolve dependency imports.
+    pub fn with_manifest(mut self, manifest: Option<Manifest>) -> Self {
+        self.manifest = manifest;
+        self
+    }
+
+    /// Set the module search paths to the provided `include` paths, followed
+    /// by any of the paths that are specified by the [HASH_PATH] environment
+    /// variable.
+    pub fn with_search_paths(mut self, include: Vec<PathBuf>) -> Self {
+        let env_paths = env::var_os(HASH_PATH).into_iter().flat_map(|paths| {
+            env::split_paths(&paths).filter(|path| !path.as_os_str().is_empty()).collect::<Vec<_>>()
+        });
+
+        self.search_paths = include.into_iter().chain(env_paths).collect();
+        self
     }
 }
 
@@ -30,6 +62,8 @@ impl Default for CompilerSettings {
         Self {
             display_metrics: false, // @@TODO: determine this by the mode of operation
             worker_count: num_cpus::get(),
+            search_paths: vec![],
+            manifest: None,
         }
     }
 }
@@ -61,6 +95,21 @@ impl Display for CompilerMode {
     }
 }
 
+/// The format that the AST is printed in when a stage outputs its result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AstFormat {
+    /// A rendered tree that is intended to be read by a person.
+    Tree,
+    /// The JSON representation of the AST, see [hash_ast::json].
+    Json,
+}
+
+impl Default for AstFormat {
+    fn default() -> Self {
+        AstFormat::Tree
+    }
+}
+
 pub struct CompilerJobParams {
     /// Denoting to what stage the pipeline should get before terminating.
     pub mode: CompilerMode,
@@ -72,16 +121,25 @@ pub struct CompilerJobParams {
     /// is only used by stages that might print debug information about what
     /// happened during the stage.
     pub output_stage_result: bool,
+
+    /// The format that the AST is printed in when the result of a stage is
+    /// output.
+    pub ast_format: AstFormat,
 }
 
 impl CompilerJobParams {
     pub fn new(mode: CompilerMode, output_stage_result: bool) -> Self {
-        Self { mode, output_stage_result }
+        Self { mode, output_stage_result, ast_format: AstFormat::default() }
+    }
+
+    pub fn with_ast_format(mut self, ast_format: AstFormat) -> Self {
+        self.ast_format = ast_format;
+        self
     }
 }
 
 impl Default for CompilerJobParams {
     fn default() -> Self {
-        Self { mode: CompilerMode::Full, output_stage_result: false }
+        Self::new(CompilerMode::Full, false)
     }
 }
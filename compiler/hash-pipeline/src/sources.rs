@@ -1,11 +1,13 @@
 //! Hash Compiler sources map and interfaces for accessing and storing
 //! job sources.
 use hash_ast::ast;
-use hash_source::{InteractiveId, ModuleId, ModuleKind, SourceId, SourceMap};
+use hash_source::{
+    location::SourceLocation, InteractiveId, ModuleId, ModuleKind, SourceId, SourceMap,
+};
 use std::{
     collections::{
         hash_map::{Iter, IterMut},
-        HashMap, HashSet,
+        BTreeMap, HashMap, HashSet,
     },
     path::{Path, PathBuf},
 };
@@ -26,6 +28,13 @@ impl InteractiveBlock {
         Self { node: None }
     }
 
+    /// Create a new [InteractiveBlock] with a `node` that was generated
+    /// outside of the parser, for example one that was loaded by
+    /// [hash_ast::json::from_json].
+    pub fn with_node(node: ast::AstNode<ast::BodyBlock>) -> Self {
+        Self { node: Some(node) }
+    }
+
     /// Set the `node` for given [InteractiveBlock]
     pub fn set_node(&mut self, node: ast::AstNode<ast::BodyBlock>) {
         self.node = Some(node);
@@ -66,6 +75,13 @@ impl Module {
         Self { path, node: None }
     }
 
+    /// Create a new [Module] with a specified `path` and a `node` that was
+    /// generated outside of the parser, for example one that was loaded by
+    /// [hash_ast::json::from_json].
+    pub fn with_node(path: PathBuf, node: ast::AstNode<ast::Module>) -> Self {
+        Self { path, node: Some(node) }
+    }
+
     /// Get the `path` from the [Module].
     pub fn path(&self) -> &Path {
         &self.path
@@ -187,8 +203,9 @@ impl NodeMap {
 /// access information about the current job.
 #[derive(Debug, Default)]
 pub struct Workspace {
-    /// Dependency map between sources and modules.
-    dependencies: HashMap<SourceId, HashSet<ModuleId>>,
+    /// Dependency map between sources and the modules that they import, along
+    /// with the location of the `import(...)` of each module.
+    dependencies: HashMap<SourceId, BTreeMap<ModuleId, SourceLocation>>,
     /// Stores all of the raw file contents of the interactive blocks and
     /// modules.
     pub source_map: SourceMap,
@@ -236,9 +253,75 @@ impl Workspace {
     }
 
     /// Add a module dependency specified by a [ModuleId] to a specific source
-    /// specified by a [SourceId].
-    pub fn add_dependency(&mut self, source_id: SourceId, dependency: ModuleId) {
-        self.dependencies.entry(source_id).or_insert_with(HashSet::new).insert(dependency);
+    /// specified by a [SourceId], the `location` is where the module is
+    /// imported within the source. If the source imports the module more than
+    /// once, the location of the first import is kept.
+    pub fn add_dependency(
+        &mut self,
+        source_id: SourceId,
+        dependency: ModuleId,
+        location: SourceLocation,
+    ) {
+        self.dependencies.entry(source_id).or_default().entry(dependency).or_insert(location);
+    }
+
+    /// Get the modules that the given source imports, along with the location
+    /// of the import.
+    pub fn dependencies_of(
+        &self,
+        source_id: SourceId,
+    ) -> impl Iterator<Item = (ModuleId, SourceLocation)> + '_ {
+        self.dependencies.get(&source_id).into_iter().flatten().map(|(id, loc)| (*id, *loc))
+    }
+
+    /// Find a cycle within the import graph that goes through the import of
+    /// `dependency` within the source specified by `source_id`. If there is a
+    /// cycle, the locations of all of the imports that make up the cycle are
+    /// returned, beginning with the import of `dependency`.
+    pub fn find_import_cycle(
+        &self,
+        source_id: SourceId,
+        dependency: ModuleId,
+    ) -> Option<Vec<SourceLocation>> {
+        let location = *self.dependencies.get(&source_id)?.get(&dependency)?;
+
+        // Search for a chain of imports that leads from the dependency back to the
+        // source
+        let mut chain = vec![location];
+        let mut visited = HashSet::new();
+
+        self.find_import_chain(SourceId::Module(dependency), source_id, &mut visited, &mut chain)
+            .then_some(chain)
+    }
+
+    /// Depth-first search for a chain of imports between the `from` and `to`
+    /// sources, the locations of the imports are pushed onto `chain`.
+    fn find_import_chain(
+        &self,
+        from: SourceId,
+        to: SourceId,
+        visited: &mut HashSet<SourceId>,
+        chain: &mut Vec<SourceLocation>,
+    ) -> bool {
+        if from == to {
+            return true;
+        }
+
+        if !visited.insert(from) {
+            return false;
+        }
+
+        for (module, location) in self.dependencies_of(from) {
+            chain.push(location);
+
+            if self.find_import_chain(SourceId::Module(module), to, visited, chain) {
+                return true;
+            }
+
+            chain.pop();
+        }
+
+        false
     }
 
     /// Get a reference to [SourceMap]
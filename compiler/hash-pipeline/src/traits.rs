@@ -5,7 +5,10 @@
 use hash_reporting::report::Report;
 use hash_source::{InteractiveId, ModuleId, SourceId};
 
-use crate::{settings::CompilerJobParams, sources::Workspace};
+use crate::{
+    settings::{CompilerJobParams, CompilerSettings},
+    sources::Workspace,
+};
 
 pub type CompilerResult<T> = Result<T, Vec<Report>>;
 
@@ -14,14 +17,16 @@ pub type CompilerResult<T> = Result<T, Vec<Report>>;
 pub trait Parser<'pool> {
     /// Given a [SourceId], parse the current job and append any parsed modules
     /// to the provided sources parameter. On success, the function returns
-    /// nothing and on failure, the stage provides a generated diagnostics
-    /// [Report].
+    /// the warnings that were generated, and on failure, the stage provides
+    /// the generated diagnostics [Report]s, including any warnings. The
+    /// [CompilerSettings] specify where imported modules are searched for.
     fn parse(
         &mut self,
         entry_point: SourceId,
         workspace: &mut Workspace,
+        settings: &CompilerSettings,
         pool: &'pool rayon::ThreadPool,
-    ) -> CompilerResult<()>;
+    ) -> CompilerResult<Vec<Report>>;
 }
 
 /// The [Desugar] represents an abstract parser that can parse all aspects of
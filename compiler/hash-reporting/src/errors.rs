@@ -36,6 +36,12 @@ pub enum CompilerError {
     /// Errors that occur when running a command.
     #[error("{0}")]
     InterpreterError(#[from] InteractiveCommandError),
+    /// Error when the package manifest couldn't be loaded.
+    #[error("{message}")]
+    ManifestError { message: String },
+    /// Error when the formatter configuration couldn't be loaded.
+    #[error("{message}")]
+    FormatterConfigError { message: String },
 }
 
 impl CompilerError {
@@ -0,0 +1,488 @@
+//! Hash Compiler numerical constant storage utilities and wrappers.
+use std::{fmt::Display, str::FromStr};
+
+use dashmap::DashMap;
+use fnv::FnvBuildHasher;
+use hash_utils::counter;
+use lazy_static::lazy_static;
+use num_bigint::{BigInt, BigUint};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+/// The type of an integer literal, which is specified by a suffix on the
+/// literal, e.g. `255u8` or `1_000i64`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntTy {
+    I8,
+    I16,
+    I32,
+    I64,
+    ISize,
+    U8,
+    U16,
+    U32,
+    U64,
+    USize,
+}
+
+impl IntTy {
+    /// Attempt to convert a literal suffix into an [IntTy].
+    pub fn from_suffix(suffix: &str) -> Option<Self> {
+        match suffix {
+            "i8" => Some(IntTy::I8),
+            "i16" => Some(IntTy::I16),
+            "i32" => Some(IntTy::I32),
+            "i64" => Some(IntTy::I64),
+            "isize" => Some(IntTy::ISize),
+            "u8" => Some(IntTy::U8),
+            "u16" => Some(IntTy::U16),
+            "u32" => Some(IntTy::U32),
+            "u64" => Some(IntTy::U64),
+            "usize" => Some(IntTy::USize),
+            _ => None,
+        }
+    }
+
+    /// Get the name of the primitive type that the [IntTy] represents.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IntTy::I8 => "i8",
+            IntTy::I16 => "i16",
+            IntTy::I32 => "i32",
+            IntTy::I64 => "i64",
+            IntTy::ISize => "isize",
+            IntTy::U8 => "u8",
+            IntTy::U16 => "u16",
+            IntTy::U32 => "u32",
+            IntTy::U64 => "u64",
+            IntTy::USize => "usize",
+        }
+    }
+
+    /// Check whether the [IntTy] is signed.
+    pub fn is_signed(&self) -> bool {
+        matches!(self, IntTy::I8 | IntTy::I16 | IntTy::I32 | IntTy::I64 | IntTy::ISize)
+    }
+
+    /// Get the size of the [IntTy] in bits.
+    ///
+    /// @@Future: `isize` and `usize` are assumed to be 64 bits wide, this
+    /// should be determined by the target.
+    pub fn bits(&self) -> u32 {
+        match self {
+            IntTy::I8 | IntTy::U8 => 8,
+            IntTy::I16 | IntTy::U16 => 16,
+            IntTy::I32 | IntTy::U32 => 32,
+            IntTy::I64 | IntTy::U64 | IntTy::ISize | IntTy::USize => 64,
+        }
+    }
+
+    /// Get the minimum value that the [IntTy] can represent.
+    pub fn min(&self) -> BigInt {
+        if self.is_signed() {
+            -(BigInt::from(1) << (self.bits() - 1))
+        } else {
+            BigInt::from(0)
+        }
+    }
+
+    /// Get the maximum value that the [IntTy] can represent.
+    pub fn max(&self) -> BigInt {
+        let bits = if self.is_signed() { self.bits() - 1 } else { self.bits() };
+        (BigInt::from(1) << bits) - 1
+    }
+}
+
+impl Display for IntTy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The type of a float literal, which is specified by a suffix on the
+/// literal, e.g. `1.5f64`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FloatTy {
+    F32,
+    F64,
+}
+
+impl FloatTy {
+    /// Attempt to convert a literal suffix into a [FloatTy].
+    pub fn from_suffix(suffix: &str) -> Option<Self> {
+        match suffix {
+            "f32" => Some(FloatTy::F32),
+            "f64" => Some(FloatTy::F64),
+            _ => None,
+        }
+    }
+
+    /// Get the name of the primitive type that the [FloatTy] represents.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FloatTy::F32 => "f32",
+            FloatTy::F64 => "f64",
+        }
+    }
+
+    /// The number of bits of precision in the significand of the [FloatTy],
+    /// including the implicit leading bit.
+    fn precision(&self) -> u64 {
+        match self {
+            FloatTy::F32 => 24,
+            FloatTy::F64 => 53,
+        }
+    }
+
+    /// The binary exponent of the smallest positive (subnormal) value that
+    /// the [FloatTy] can represent.
+    fn min_exponent(&self) -> i64 {
+        match self {
+            FloatTy::F32 => -149,
+            FloatTy::F64 => -1074,
+        }
+    }
+}
+
+impl Display for FloatTy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The value of a float constant that was parsed during the tokenisation
+/// process. The original text of the literal is kept rather than a
+/// pre-computed value, so that the literal can be rounded exactly to the width
+/// of its type once it is known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FloatConstantValue {
+    /// The original text of the literal without the type suffix, e.g.
+    /// `1_000.5e-3` or `0x1.8p3`.
+    pub text: String,
+    /// The type suffix of the literal, if one was specified.
+    pub suffix: Option<FloatTy>,
+}
+
+impl FloatConstantValue {
+    /// Compute the value of the literal, correctly rounded to an [f64].
+    pub fn as_f64(&self) -> f64 {
+        self.compute(FloatTy::F64)
+    }
+
+    /// Compute the value of the literal, correctly rounded to an [f32].
+    ///
+    /// This does not round via [f64], which avoids double rounding errors.
+    pub fn as_f32(&self) -> f32 {
+        // The value has already been rounded to the precision of an `f32`, so
+        // the conversion is exact.
+        self.compute(FloatTy::F32) as f32
+    }
+
+    /// Compute the value of the literal, rounded to the given [FloatTy].
+    fn compute(&self, ty: FloatTy) -> f64 {
+        // Negated literals only occur within patterns, e.g. `-1.5..=1.5`
+        if let Some(text) = self.text.strip_prefix('-') {
+            return -FloatConstantValue { text: text.to_string(), suffix: self.suffix }.compute(ty);
+        }
+
+        let digits = self.text.chars().filter(|c| *c != '_').collect::<String>();
+
+        match digits.strip_prefix("0x") {
+            Some(hex_digits) => hex_float_value(hex_digits, ty),
+            None => match ty {
+                FloatTy::F32 => digits.parse::<f32>().unwrap() as f64,
+                FloatTy::F64 => digits.parse::<f64>().unwrap(),
+            },
+        }
+    }
+}
+
+/// Compute the value of a hexadecimal float literal (without the `0x` prefix)
+/// in the form of `<hex digits>[.<hex digits>]p[+-]<decimal digits>`. The
+/// value is rounded (ties to even) to the precision of the given [FloatTy],
+/// the result is then exactly representable as a value of that type.
+fn hex_float_value(digits: &str, ty: FloatTy) -> f64 {
+    let (mantissa, exponent) = digits.split_once(['p', 'P']).unwrap_or((digits, "0"));
+    let (int_part, frac_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+
+    // If the exponent doesn't fit, it is so large that the literal is either
+    // zero or infinite, so we clamp it.
+    let exponent = exponent.parse::<i64>().unwrap_or_else(|_| {
+        if exponent.starts_with('-') {
+            i64::MIN / 2
+        } else {
+            i64::MAX / 2
+        }
+    });
+
+    let mut significand =
+        BigUint::parse_bytes(format!("{int_part}{frac_part}").as_bytes(), 16).unwrap();
+    let mut exponent = exponent - 4 * frac_part.len() as i64;
+
+    if significand == BigUint::from(0u32) {
+        return 0.0;
+    }
+
+    // The exponent of the lowest significant bit that can be represented for
+    // a value of this magnitude
+    let top_exponent = exponent + significand.bits() as i64 - 1;
+    let lsb_exponent = (top_exponent - (ty.precision() as i64 - 1)).max(ty.min_exponent());
+
+    if lsb_exponent > exponent {
+        let shift = (lsb_exponent - exponent) as u64;
+
+        // The value is less than half of the lowest significant bit, so it rounds
+        // to zero. This is checked before building the masks, since the shift can
+        // be enormous for a very negative exponent.
+        if shift > significand.bits() + 1 {
+            return 0.0;
+        }
+
+        let one = BigUint::from(1u32);
+
+        let remainder = &significand & ((&one << shift) - &one);
+        let half = &one << (shift - 1);
+        significand >>= shift;
+        exponent = lsb_exponent;
+
+        if remainder > half || (remainder == half && &significand % 2u32 == one) {
+            significand += one;
+        }
+    }
+
+    // The significand now has at most `precision + 1` bits, so it can be
+    // converted exactly, and the scaling by the exponent is exact unless it
+    // overflows to infinity.
+    let significand = u64::try_from(&significand).unwrap() as f64;
+    let exponent = exponent.min(i32::MAX as i64 / 2) as i32;
+
+    significand * 2f64.powi(exponent / 2) * 2f64.powi(exponent - exponent / 2)
+}
+
+counter! {
+    name: FloatConstant,
+    counter_name: FLOAT_CONSTANT_COUNTER,
+    visibility: pub,
+    method_visibility:,
+}
+
+impl FloatConstant {
+    /// Get the [FloatConstantValue] behind the [FloatConstant].
+    pub fn value(&self) -> FloatConstantValue {
+        CONSTANT_MAP.lookup_float(*self)
+    }
+}
+
+impl Display for FloatConstant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let FloatConstantValue { text, suffix } = self.value();
+
+        match suffix {
+            Some(suffix) => write!(f, "{}{}", text, suffix),
+            None => write!(f, "{}", text),
+        }
+    }
+}
+
+impl From<FloatConstantValue> for FloatConstant {
+    fn from(value: FloatConstantValue) -> Self {
+        CONSTANT_MAP.create_float(value)
+    }
+}
+
+/// The value of an integer constant that was parsed during the tokenisation
+/// process. The value is stored with arbitrary precision, the range of the
+/// value is only checked once the type of the literal is known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntConstantValue {
+    /// The value of the integer literal.
+    pub value: BigInt,
+    /// The type suffix of the literal, if one was specified.
+    pub suffix: Option<IntTy>,
+}
+
+counter! {
+    name: IntConstant,
+    counter_name: INT_CONSTANT_COUNTER,
+    visibility: pub,
+    method_visibility:,
+}
+
+impl IntConstant {
+    /// Get the [IntConstantValue] behind the [IntConstant].
+    pub fn value(&self) -> IntConstantValue {
+        CONSTANT_MAP.lookup_int(*self)
+    }
+}
+
+impl Display for IntConstant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let IntConstantValue { value, suffix } = self.value();
+
+        match suffix {
+            Some(suffix) => write!(f, "{}{}", value, suffix),
+            None => write!(f, "{}", value),
+        }
+    }
+}
+
+impl From<IntConstantValue> for IntConstant {
+    fn from(value: IntConstantValue) -> Self {
+        CONSTANT_MAP.create_int(value)
+    }
+}
+
+/// The serialised form of a numerical constant, the value is kept as text so
+/// that it is exact. The suffix is the name of the type of the constant, if
+/// the literal had a type suffix.
+#[derive(Serialize, Deserialize)]
+struct SerialisedConstant {
+    value: String,
+    suffix: Option<String>,
+}
+
+impl Serialize for IntConstant {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let IntConstantValue { value, suffix } = self.value();
+
+        SerialisedConstant {
+            value: value.to_string(),
+            suffix: suffix.map(|suffix| suffix.as_str().to_owned()),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for IntConstant {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let SerialisedConstant { value, suffix } = SerialisedConstant::deserialize(deserializer)?;
+
+        let value = BigInt::from_str(&value)
+            .map_err(|_| D::Error::custom(format!("invalid integer constant `{value}`")))?;
+        let suffix = suffix
+            .map(|suffix| {
+                IntTy::from_suffix(&suffix)
+                    .ok_or_else(|| D::Error::custom(format!("invalid integer type `{suffix}`")))
+            })
+            .transpose()?;
+
+        Ok(IntConstant::from(IntConstantValue { value, suffix }))
+    }
+}
+
+impl Serialize for FloatConstant {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let FloatConstantValue { text, suffix } = self.value();
+
+        SerialisedConstant { value: text, suffix: suffix.map(|suffix| suffix.as_str().to_owned()) }
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for FloatConstant {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let SerialisedConstant { value, suffix } = SerialisedConstant::deserialize(deserializer)?;
+
+        if !is_float_text(&value) {
+            return Err(D::Error::custom(format!("invalid float constant `{value}`")));
+        }
+
+        let suffix = suffix
+            .map(|suffix| {
+                FloatTy::from_suffix(&suffix)
+                    .ok_or_else(|| D::Error::custom(format!("invalid float type `{suffix}`")))
+            })
+            .transpose()?;
+
+        Ok(FloatConstant::from(FloatConstantValue { text: value, suffix }))
+    }
+}
+
+/// Check whether the given text is in a form that [FloatConstantValue] can
+/// compute the value of.
+fn is_float_text(text: &str) -> bool {
+    let digits = text.chars().filter(|c| *c != '_').collect::<String>();
+
+    match digits.strip_prefix("0x") {
+        Some(hex_digits) => {
+            let mantissa = hex_digits.split(['p', 'P']).next().unwrap_or_default();
+            let (int_part, frac_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+
+            int_part.len() + frac_part.len() > 0
+                && int_part.chars().chain(frac_part.chars()).all(|c| c.is_ascii_hexdigit())
+        }
+        None => digits.parse::<f64>().is_ok(),
+    }
+}
+
+/// A map containing numerical constants that have been parsed during the
+/// tokenisation process. Since numerical constants can be arbitrarily large,
+/// they are stored here and an [IntConstant] is used to refer to them, this
+/// is so that tokens can remain small and cheap to copy.
+#[derive(Debug, Default)]
+pub struct ConstantMap {
+    int_table: DashMap<IntConstant, IntConstantValue, FnvBuildHasher>,
+    float_table: DashMap<FloatConstant, FloatConstantValue, FnvBuildHasher>,
+}
+
+lazy_static! {
+    pub static ref CONSTANT_MAP: ConstantMap = ConstantMap::default();
+}
+
+impl ConstantMap {
+    /// Add a new integer constant to the map.
+    pub fn create_int(&self, value: IntConstantValue) -> IntConstant {
+        let id = IntConstant::new();
+        self.int_table.insert(id, value);
+        id
+    }
+
+    /// Get the [IntConstantValue] behind the [IntConstant].
+    pub fn lookup_int(&self, id: IntConstant) -> IntConstantValue {
+        self.int_table.get(&id).unwrap().value().clone()
+    }
+
+    /// Add a new float constant to the map.
+    pub fn create_float(&self, value: FloatConstantValue) -> FloatConstant {
+        let id = FloatConstant::new();
+        self.float_table.insert(id, value);
+        id
+    }
+
+    /// Get the [FloatConstantValue] behind the [FloatConstant].
+    pub fn lookup_float(&self, id: FloatConstant) -> FloatConstantValue {
+        self.float_table.get(&id).unwrap().value().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn float(text: &str) -> FloatConstantValue {
+        FloatConstantValue { text: text.to_string(), suffix: None }
+    }
+
+    #[test]
+    fn decimal_float_precision() {
+        assert_eq!(float("1e-300").as_f64(), 1e-300);
+        assert_eq!(float("0.1").as_f64(), 0.1);
+        assert_eq!(float("1_000.000_1").as_f64(), 1000.0001);
+        assert_eq!(float("16777217.0").as_f32(), 16777216.0);
+        assert_eq!(float("2.5e+3").as_f32(), 2500.0);
+    }
+
+    #[test]
+    fn hex_float_value() {
+        assert_eq!(float("0x1.8p3").as_f64(), 12.0);
+        assert_eq!(float("0x1p-2").as_f64(), 0.25);
+        assert_eq!(float("0xA.8P0").as_f64(), 10.5);
+        assert_eq!(float("0x1p-1074").as_f64(), f64::from_bits(1));
+        assert_eq!(float("0x1p-1076").as_f64(), 0.0);
+        assert_eq!(float("0x1p-99999999999999999999").as_f64(), 0.0);
+        assert_eq!(float("0x1.8p-99999999999999999999").as_f32(), 0.0);
+        assert_eq!(float("0x1p1024").as_f64(), f64::INFINITY);
+        assert_eq!(float("0x1.fffffffffffff8p0").as_f64(), 2.0);
+        assert_eq!(float("0x1.000001p0").as_f32(), 1.0);
+        assert_eq!(float("0x1.000003p0").as_f32(), 1.0000002);
+    }
+}
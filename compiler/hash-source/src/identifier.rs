@@ -5,6 +5,7 @@ use fnv::FnvBuildHasher;
 use hash_alloc::{collections::string::BrickString, Castle, Wall};
 use hash_utils::counter;
 use lazy_static::lazy_static;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::{
     borrow::{Borrow, Cow},
     fmt::{Debug, Display},
@@ -63,6 +64,19 @@ impl From<Identifier> for Cow<'static, str> {
     }
 }
 
+/// [Identifier]s are serialised as the string that they refer to.
+impl Serialize for Identifier {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(IDENTIFIER_MAP.get_ident(*self))
+    }
+}
+
+impl<'de> Deserialize<'de> for Identifier {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Identifier::from)
+    }
+}
+
 thread_local! {
     static IDENTIFIER_STORAGE_WALL: Wall<'static> = IDENTIFIER_STORAGE_CASTLE.wall();
 }
@@ -87,6 +101,7 @@ pub struct IdentifierMap<'c> {
 pub struct CoreIdentifiers {
     pub underscore: Identifier,
     pub intrinsics: Identifier,
+    pub result: Identifier,
 }
 
 impl CoreIdentifiers {
@@ -95,6 +110,7 @@ impl CoreIdentifiers {
         Self {
             underscore: ident_map.create_ident("_"),
             intrinsics: ident_map.create_ident("intrinsics"),
+            result: ident_map.create_ident("Result"),
         }
     }
 }
@@ -8,6 +8,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
+pub mod constant;
 pub mod identifier;
 pub mod location;
 pub mod string;
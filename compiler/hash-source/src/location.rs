@@ -1,6 +1,7 @@
 //! Hash Compiler source locations utilities and definitions.
 use crate::SourceId;
 use derive_more::Constructor;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 use std::{convert::TryInto, fmt};
 
 /// Enum representing a location of a token within the source.
@@ -74,6 +75,34 @@ impl fmt::Display for Span {
     }
 }
 
+/// The serialised form of a [Span].
+#[derive(Serialize, Deserialize)]
+struct SerialisedSpan {
+    start: u32,
+    end: u32,
+}
+
+/// [Span]s are serialised as an object with a `start` and an `end` offset.
+impl Serialize for Span {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerialisedSpan { start: self.0, end: self.1 }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Span {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let SerialisedSpan { start, end } = SerialisedSpan::deserialize(deserializer)?;
+
+        if end < start {
+            return Err(D::Error::custom(format!(
+                "invalid span {start}:{end}, the end precedes the start"
+            )));
+        }
+
+        Ok(Span(start, end))
+    }
+}
+
 #[derive(Debug, Clone, Copy, Constructor, PartialEq, Eq, Hash)]
 pub struct SourceLocation {
     pub span: Span,
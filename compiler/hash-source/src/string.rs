@@ -4,6 +4,7 @@ use std::fmt::Display;
 use fnv::FnvBuildHasher;
 use hash_utils::counter;
 use lazy_static::lazy_static;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use dashmap::DashMap;
 
@@ -57,6 +58,19 @@ impl From<Str> for String {
     }
 }
 
+/// [Str]s are serialised as the string that they refer to.
+impl Serialize for Str {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(STR_LIT_MAP.lookup(*self))
+    }
+}
+
+impl<'de> Deserialize<'de> for Str {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Str::from)
+    }
+}
+
 lazy_static! {
     pub static ref STR_LIT_MAP: StrLitMap = StrLitMap::default();
 }
@@ -84,3 +98,80 @@ impl StrLitMap {
         self.string_table.get(&ident).unwrap().value()
     }
 }
+
+/// A map containing byte string literals that have been parsed during the
+/// tokenisation process. Byte strings are stored separately from [Str]s
+/// since they may contain bytes that are not valid UTF-8.
+#[derive(Debug, Default)]
+pub struct ByteStrLitMap {
+    byte_string_table: DashMap<ByteStr, &'static [u8], FnvBuildHasher>,
+    reverse_table: DashMap<&'static [u8], ByteStr, FnvBuildHasher>,
+}
+
+counter! {
+    name: ByteStr,
+    counter_name: BYTE_STR_LIT_COUNTER,
+    visibility: pub,
+    method_visibility:,
+}
+
+impl ByteStr {
+    /// Get the bytes behind the [ByteStr].
+    pub fn bytes(&self) -> &'static [u8] {
+        BYTE_STR_LIT_MAP.lookup(*self)
+    }
+}
+
+impl Display for ByteStr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.bytes().escape_ascii())
+    }
+}
+
+impl From<&[u8]> for ByteStr {
+    fn from(bytes: &[u8]) -> Self {
+        BYTE_STR_LIT_MAP.create_byte_string(bytes)
+    }
+}
+
+/// [ByteStr]s are serialised as a sequence of bytes, since they may not be
+/// valid UTF-8.
+impl Serialize for ByteStr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.bytes())
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteStr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<u8>::deserialize(deserializer).map(|bytes| ByteStr::from(bytes.as_slice()))
+    }
+}
+
+lazy_static! {
+    pub static ref BYTE_STR_LIT_MAP: ByteStrLitMap = ByteStrLitMap::default();
+}
+
+impl ByteStrLitMap {
+    /// Add a new byte string to the map, if the byte string is already within
+    /// the map, the existing [ByteStr] is returned.
+    pub fn create_byte_string(&self, value: &[u8]) -> ByteStr {
+        if let Some(key) = self.reverse_table.get(value) {
+            *key
+        } else {
+            let ident = ByteStr::new();
+
+            // copy over the bytes so that we can insert it into the reverse lookup table
+            let value_copy = Box::leak(value.to_owned().into_boxed_slice());
+
+            self.reverse_table.insert(value_copy, ident);
+            self.byte_string_table.insert(ident, value_copy);
+            ident
+        }
+    }
+
+    /// Get the bytes behind the [ByteStr]
+    pub fn lookup(&self, ident: ByteStr) -> &'static [u8] {
+        self.byte_string_table.get(&ident).unwrap().value()
+    }
+}
@@ -2,16 +2,22 @@
 //! the input sources.
 pub mod delimiter;
 pub mod keyword;
+pub mod trivia;
 
 use delimiter::Delimiter;
-use hash_source::{identifier::Identifier, location::Span, string::Str};
+use hash_source::{
+    constant::{FloatConstant, IntConstant},
+    identifier::Identifier,
+    location::Span,
+    string::{ByteStr, Str},
+};
 use keyword::Keyword;
 
 /// A Lexeme token that represents the smallest code unit of a hash source file.
 /// The token contains a kind which is elaborated by [TokenKind] and a [Span] in
 /// the source that is represented as a span. The span is the beginning byte
 /// offset, and the number of bytes for the said token.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct Token {
     /// The current token type.
     pub kind: TokenKind,
@@ -70,7 +76,6 @@ impl TokenKind {
             TokenKind::Plus
                     | TokenKind::Minus
                     | TokenKind::Star
-                    | TokenKind::Slash
                     | TokenKind::Hash // directives
                     | TokenKind::Amp
                     | TokenKind::Tilde
@@ -107,6 +112,9 @@ impl TokenKind {
                 | TokenKind::FloatLit(_)
                 | TokenKind::CharLit(_)
                 | TokenKind::StrLit(_)
+                | TokenKind::RawStrLit(_)
+                | TokenKind::ByteStrLit(_)
+                | TokenKind::MultiLineStrLit(_)
         )
     }
 }
@@ -114,7 +122,7 @@ impl TokenKind {
 /// An Atom represents all variants of a token that can be present in a source
 /// file. Atom token kinds can represent a single character, literal or an
 /// identifier.
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum TokenKind {
     /// '='
     Eq,
@@ -160,14 +168,29 @@ pub enum TokenKind {
     Quote,
     /// "'"
     SingleQuote,
-    /// Integer Literal
-    IntLit(u64),
-    /// Float literal
-    FloatLit(f64),
+    /// Integer Literal, the value and the optional type suffix of the literal
+    /// are stored within the [hash_source::constant::CONSTANT_MAP].
+    IntLit(IntConstant),
+    /// Float literal, the original text and the optional type suffix of the
+    /// literal are stored within the [hash_source::constant::CONSTANT_MAP].
+    FloatLit(FloatConstant),
     /// Character literal
     CharLit(char),
     /// StrLiteral,
     StrLit(Str),
+    /// A raw string literal, which doesn't process any escape sequences, e.g.
+    /// `r"C:\path"` or `r#"contains "quotes""#`.
+    RawStrLit(Str),
+    /// A byte string literal, e.g. `b"bytes\xff"`.
+    ByteStrLit(ByteStr),
+    /// A multi-line string literal delimited by `"""`, the common
+    /// indentation of the lines within the literal is stripped.
+    MultiLineStrLit(Str),
+    /// An interpolated string literal, e.g. `f"value is {x + 1}"`. The index
+    /// refers to the token tree that holds the segments of the literal, the
+    /// string segments are stored as [TokenKind::StrLit] and the interpolated
+    /// expressions as [TokenKind::Tree] with a [Delimiter::Brace].
+    InterpolatedStrLit(usize),
     /// Identifier
     Ident(Identifier),
 
@@ -185,21 +208,77 @@ pub enum TokenKind {
     /// A token that was unexpected by the lexer, e.g. a unicode symbol not
     /// within string literal.
     Unexpected(char),
+
+    /// A token that the lexer failed to produce, e.g. a malformed numerical
+    /// literal or an unclosed string literal. The lexer records the error
+    /// and emits this token in place of the malformed one so that lexing can
+    /// continue.
+    Err,
 }
 
 impl TokenKind {
+    /// Get the name of the kind of the token, this is the name of the variant
+    /// of the [TokenKind].
+    pub fn name(&self) -> &'static str {
+        match self {
+            TokenKind::Eq => "Eq",
+            TokenKind::Lt => "Lt",
+            TokenKind::Gt => "Gt",
+            TokenKind::Plus => "Plus",
+            TokenKind::Minus => "Minus",
+            TokenKind::Star => "Star",
+            TokenKind::Slash => "Slash",
+            TokenKind::Percent => "Percent",
+            TokenKind::Caret => "Caret",
+            TokenKind::Amp => "Amp",
+            TokenKind::Tilde => "Tilde",
+            TokenKind::Pipe => "Pipe",
+            TokenKind::Question => "Question",
+            TokenKind::Exclamation => "Exclamation",
+            TokenKind::Dot => "Dot",
+            TokenKind::Colon => "Colon",
+            TokenKind::Semi => "Semi",
+            TokenKind::Hash => "Hash",
+            TokenKind::Dollar => "Dollar",
+            TokenKind::Comma => "Comma",
+            TokenKind::Quote => "Quote",
+            TokenKind::SingleQuote => "SingleQuote",
+            TokenKind::IntLit(_) => "IntLit",
+            TokenKind::FloatLit(_) => "FloatLit",
+            TokenKind::CharLit(_) => "CharLit",
+            TokenKind::StrLit(_) => "StrLit",
+            TokenKind::RawStrLit(_) => "RawStrLit",
+            TokenKind::ByteStrLit(_) => "ByteStrLit",
+            TokenKind::MultiLineStrLit(_) => "MultiLineStrLit",
+            TokenKind::InterpolatedStrLit(_) => "InterpolatedStrLit",
+            TokenKind::Ident(_) => "Ident",
+            TokenKind::Keyword(_) => "Keyword",
+            TokenKind::Unexpected(_) => "Unexpected",
+            TokenKind::Tree(_, _) => "Tree",
+            TokenKind::Delimiter(_, _) => "Delimiter",
+            TokenKind::Err => "Err",
+        }
+    }
+
     /// This function is used to create an error message representing when a
     /// token was unexpectedly encountered or was expected in a particular
     /// context.
     pub fn as_error_string(&self) -> String {
         match self {
             TokenKind::Unexpected(ch) => format!("an unknown character `{}`", ch),
+            TokenKind::Err => "a malformed token".to_string(),
             TokenKind::IntLit(num) => format!("`{}`", num),
             TokenKind::FloatLit(num) => format!("`{}`", num),
             TokenKind::CharLit(ch) => format!("`{}`", ch),
-            TokenKind::StrLit(str) => {
+            TokenKind::StrLit(str)
+            | TokenKind::RawStrLit(str)
+            | TokenKind::MultiLineStrLit(str) => {
                 format!("the string `{}`", *str)
             }
+            TokenKind::ByteStrLit(str) => {
+                format!("the byte string `{}`", *str)
+            }
+            TokenKind::InterpolatedStrLit(_) => "an interpolated string".to_string(),
             TokenKind::Keyword(kwd) => format!("`{}`", kwd),
             TokenKind::Ident(ident) => {
                 format!("the identifier `{}`", *ident)
@@ -235,6 +314,7 @@ impl std::fmt::Display for TokenKind {
             TokenKind::Quote => write!(f, "\""),
             TokenKind::SingleQuote => write!(f, "'"),
             TokenKind::Unexpected(ch) => write!(f, "{}", ch),
+            TokenKind::Err => write!(f, "<error>"),
             TokenKind::IntLit(num) => write!(f, "{}", num),
             TokenKind::FloatLit(num) => write!(f, "{}", num),
             TokenKind::CharLit(ch) => write!(f, "'{}'", ch),
@@ -249,6 +329,16 @@ impl std::fmt::Display for TokenKind {
             TokenKind::StrLit(str) => {
                 write!(f, "\"{}\"", *str)
             }
+            TokenKind::RawStrLit(str) => {
+                write!(f, "r\"{}\"", *str)
+            }
+            TokenKind::ByteStrLit(str) => {
+                write!(f, "b\"{}\"", *str)
+            }
+            TokenKind::MultiLineStrLit(str) => {
+                write!(f, "\"\"\"{}\"\"\"", *str)
+            }
+            TokenKind::InterpolatedStrLit(_) => write!(f, "f\"{{...}}\""),
             TokenKind::Keyword(kwd) => kwd.fmt(f),
             TokenKind::Ident(ident) => {
                 write!(f, "{}", String::from(*ident))
@@ -0,0 +1,110 @@
+//! Hash Compiler token trivia definitions. Trivia are the parts of the source
+//! that don't affect the meaning of the program, such as whitespace and
+//! comments, which are only preserved by the lexer when it is asked to.
+use std::collections::HashMap;
+
+use hash_source::location::Span;
+
+use super::Token;
+
+/// The kind of a documentation comment.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DocCommentKind {
+    /// A `///` comment, which documents the item that follows it.
+    Outer,
+    /// A `//!` comment, which documents the enclosing item or module.
+    Inner,
+}
+
+/// The kind of a piece of [Trivia].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TriviaKind {
+    /// Any run of whitespace characters.
+    Whitespace,
+    /// A `// ...` comment.
+    LineComment,
+    /// A `/* ... */` comment, which may be nested.
+    BlockComment,
+    /// A `///` or `//!` documentation comment.
+    DocComment(DocCommentKind),
+}
+
+/// A piece of trivia in the source, the contents of the trivia can be
+/// retrieved from the source using the [Span].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Trivia {
+    /// The kind of trivia.
+    pub kind: TriviaKind,
+    /// The span of the trivia.
+    pub span: Span,
+}
+
+impl Trivia {
+    /// Create a new [Trivia] from a kind and a provided [Span].
+    pub fn new(kind: TriviaKind, span: Span) -> Self {
+        Trivia { kind, span }
+    }
+}
+
+/// The trivia that surrounds a [Token] when the lexer preserves trivia.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TokenTrivia {
+    /// Trivia that precedes the token, this is everything after the trailing
+    /// trivia of the previous token.
+    pub leading: Vec<Trivia>,
+    /// Trivia that follows the token on the same line, the trailing trivia
+    /// never includes the newline that ends the line.
+    pub trailing: Vec<Trivia>,
+    /// For token trees, the trivia that appears before the closing delimiter
+    /// of the tree which is not attached to any of the tokens within the tree.
+    pub dangling: Vec<Trivia>,
+}
+
+/// The [TokenTrivia] of the tokens that were produced by a lexer which
+/// preserves trivia. The trivia is stored separately from the tokens so that
+/// the tokens don't grow in size when trivia isn't preserved. A token is
+/// identified by the start of its [Span], since no two tokens start at the
+/// same offset.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TriviaMap {
+    trivia: HashMap<usize, TokenTrivia>,
+}
+
+impl TriviaMap {
+    /// Create a new empty [TriviaMap].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the [TokenTrivia] of the given token.
+    pub fn insert(&mut self, token: &Token, trivia: TokenTrivia) {
+        self.trivia.insert(token.span.start(), trivia);
+    }
+
+    /// Get the [TokenTrivia] of the given token, if the token has any.
+    pub fn get(&self, token: &Token) -> Option<&TokenTrivia> {
+        self.trivia.get(&token.span.start())
+    }
+
+    /// Remove the [TokenTrivia] of the given token, if the token has any.
+    pub fn remove(&mut self, token: &Token) -> Option<TokenTrivia> {
+        self.trivia.remove(&token.span.start())
+    }
+
+    /// Remove the trivia of all of the tokens that start at or after the given
+    /// offset.
+    pub fn truncate(&mut self, offset: usize) {
+        self.trivia.retain(|start, _| *start < offset);
+    }
+
+    /// Check whether any trivia has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.trivia.is_empty()
+    }
+
+    /// Iterate over the [TokenTrivia] of all of the tokens, in no particular
+    /// order.
+    pub fn iter(&self) -> impl Iterator<Item = &TokenTrivia> {
+        self.trivia.values()
+    }
+}
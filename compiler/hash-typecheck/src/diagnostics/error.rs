@@ -5,7 +5,7 @@ use crate::storage::{
     location::LocationTarget,
     primitives::{AccessOp, AccessTerm, ArgsId, ParamsId, PatId, TermId, TyFnCase},
 };
-use hash_source::identifier::Identifier;
+use hash_source::{constant::IntTy, identifier::Identifier};
 
 /// Convenient type alias for a result with a [TcError] as the error type.
 pub type TcResult<T> = Result<T, TcError>;
@@ -140,6 +140,8 @@ pub enum TcError {
     },
     /// Given match case is never going to match the subject.
     UselessMatchCase { pat: PatId, subject: TermId },
+    /// The subject of an error propagation `?` expression isn't a `Result`.
+    PropagateOnNonResult { subject: TermId, subject_ty: TermId },
     /// Cannot use pattern matching in a declaration without an assignment
     CannotPatMatchWithoutAssignment { pat: PatId },
     /// Cannot use a non-name as an assign subject.
@@ -151,6 +153,9 @@ pub enum TcError {
     /// When a bind within a pattern is declared more than one
     IdentifierBoundMultipleTimes { name: Identifier, pat: PatId },
 
+    /// The given integer literal does not fit into the range of its type.
+    IntLitOutOfRange { term: TermId, kind: IntTy },
+
     /// Within an `or` pattern, where there is a discrepancy between the
     /// declared bounds within two patterns. For example, if one pattern
     /// binds `k`, but the other doesn't.
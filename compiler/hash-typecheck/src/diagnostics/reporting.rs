@@ -1040,6 +1040,20 @@ impl<'gs, 'ls, 'cd, 's> From<TcErrorWithStorage<'gs, 'ls, 'cd, 's>> for Report {
                     )));
                 }
             }
+            TcError::PropagateOnNonResult { subject, subject_ty } => {
+                // @@Todo: error code
+                builder.with_message(format!(
+                    "the `?` operator can only be used on a `Result`, but the subject has type `{}`",
+                    subject_ty.for_formatting(err.global_storage())
+                ));
+
+                if let Some(location) = err.location_store().get_location(subject) {
+                    builder.add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                        location,
+                        "this is not a `Result`",
+                    )));
+                }
+            }
             TcError::CannotPatMatchWithoutAssignment { pat } => {
                 // @@Todo: error code
                 builder.with_message(
@@ -1090,6 +1104,28 @@ impl<'gs, 'ls, 'cd, 's> From<TcErrorWithStorage<'gs, 'ls, 'cd, 's>> for Report {
                         .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(location, "")));
                 }
             }
+            TcError::IntLitOutOfRange { term, kind } => {
+                builder
+                    .with_error_code(HashErrorCode::IntLitOutOfRange)
+                    .with_message(format!("literal out of range for type `{}`", kind));
+
+                if let Some(location) = err.location_store().get_location(term) {
+                    builder
+                        .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                            location,
+                            format!("this literal does not fit into the type `{}`", kind),
+                        )))
+                        .add_element(ReportElement::Note(ReportNote::new(
+                            ReportNoteKind::Note,
+                            format!(
+                                "the type `{}` has a range of `{}..={}`",
+                                kind,
+                                kind.min(),
+                                kind.max()
+                            ),
+                        )));
+                }
+            }
             TcError::MissingPatternBounds { pat, bounds } => {
                 builder.with_error_code(HashErrorCode::MissingPatternBounds).with_message(format!(
                     "variables {} are not declared in all patterns",
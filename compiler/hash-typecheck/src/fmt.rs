@@ -5,9 +5,9 @@ use crate::storage::{
     primitives::{
         AccessOp, AccessPat, ArgsId, BoundVar, ConstPat, ConstructedTerm, EnumDef, Level0Term,
         Level1Term, Level2Term, Level3Term, ListPat, LitTerm, MemberData, ModDefId, ModDefOrigin,
-        ModPat, Mutability, NominalDef, NominalDefId, ParamsId, Pat, PatArgsId, PatId, ScopeId,
-        ScopeVar, SpreadPat, StructDef, Sub, SubVar, Term, TermId, TrtDefId, UnresolvedTerm, Var,
-        Visibility,
+        ModPat, Mutability, NominalDef, NominalDefId, ParamsId, Pat, PatArgsId, PatId, RangePat,
+        ScopeId, ScopeVar, SpreadPat, StructDef, Sub, SubVar, Term, TermId, TrtDefId,
+        UnresolvedTerm, Var, Visibility,
     },
     GlobalStorage,
 };
@@ -232,8 +232,8 @@ impl<'gs> TcFormatter<'gs> {
                     LitTerm::Str(str) => {
                         write!(f, "\"{}\"", str)
                     }
-                    LitTerm::Int(int) => {
-                        write!(f, "{}", int)
+                    LitTerm::Int { value, .. } => {
+                        write!(f, "{}", value)
                     }
                     LitTerm::Char(char) => {
                         write!(f, "\'{}\'", char)
@@ -603,6 +603,12 @@ impl<'gs> TcFormatter<'gs> {
             }
             Pat::Const(ConstPat { term }) => self.fmt_term(f, *term, opts),
             Pat::Lit(lit_term) => self.fmt_term(f, *lit_term, opts),
+            Pat::Range(RangePat { lo, hi, end }) => {
+                opts.is_atomic.set(false);
+                self.fmt_term_as_single(f, *lo, opts.clone())?;
+                write!(f, "{}", end)?;
+                self.fmt_term_as_single(f, *hi, opts)
+            }
             Pat::Tuple(tuple_pat) => {
                 opts.is_atomic.set(true);
                 write!(f, "({})", tuple_pat.for_formatting(self.global_storage))
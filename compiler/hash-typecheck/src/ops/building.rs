@@ -7,13 +7,13 @@ use crate::storage::{
         ConstructedTerm, ConstructorPat, EnumDef, EnumVariant, EnumVariantValue, FnCall, FnLit,
         FnTy, IfPat, Level0Term, Level1Term, Level2Term, Level3Term, ListPat, LitTerm, Member,
         MemberData, ModDef, ModDefId, ModDefOrigin, ModPat, Mutability, NominalDef, NominalDefId,
-        Param, ParamList, ParamsId, Pat, PatArg, PatArgsId, PatId, Scope, ScopeId, ScopeKind,
-        ScopeVar, SetBound, StructDef, StructFields, Term, TermId, TrtDef, TrtDefId, TupleLit,
-        TupleTy, TyFn, TyFnCall, TyFnCase, TyFnTy, UnresolvedTerm, Var, Visibility,
+        Param, ParamList, ParamsId, Pat, PatArg, PatArgsId, PatId, RangePat, Scope, ScopeId,
+        ScopeKind, ScopeVar, SetBound, StructDef, StructFields, Term, TermId, TrtDef, TrtDefId,
+        TupleLit, TupleTy, TyFn, TyFnCall, TyFnCase, TyFnTy, UnresolvedTerm, Var, Visibility,
     },
     GlobalStorage,
 };
-use hash_ast::ast::ParamOrigin;
+use hash_ast::ast::{ParamOrigin, RangeEnd};
 use hash_source::{identifier::Identifier, location::SourceLocation};
 use std::cell::{Cell, RefCell};
 
@@ -685,6 +685,11 @@ impl<'gs> PrimitiveBuilder<'gs> {
         self.create_pat(Pat::Lit(lit_term))
     }
 
+    /// Create a range pattern.
+    pub fn create_range_pat(&self, lo: TermId, hi: TermId, end: RangeEnd) -> PatId {
+        self.create_pat(Pat::Range(RangePat { lo, hi, end }))
+    }
+
     /// Create an OR-pattern.
     pub fn create_or_pat(&self, pats: impl IntoIterator<Item = PatId>) -> PatId {
         let pats = pats.into_iter().collect();
@@ -15,7 +15,7 @@ use crate::{
     storage::{
         primitives::{
             AccessOp, AccessPat, ConstPat, ConstructorPat, IfPat, ListPat, Member, MemberData,
-            ModPat, Mutability, Param, Pat, PatArg, PatId, SpreadPat, TermId, Visibility,
+            ModPat, Mutability, Param, Pat, PatArg, PatId, RangePat, SpreadPat, TermId, Visibility,
         },
         AccessToStorage, AccessToStorageMut, StorageRef, StorageRefMut,
     },
@@ -195,6 +195,15 @@ impl<'gs, 'ls, 'cd, 's> PatMatcher<'gs, 'ls, 'cd, 's> {
                 Ok(_) => Ok(Some(vec![])),
                 Err(_) => Ok(None),
             },
+            // Range: The lower bound is unified with the subject type above, so we only
+            // need to check the upper bound. Whether a range matches can only be known
+            // at runtime, and so it never binds anything.
+            Pat::Range(RangePat { hi, .. }) => {
+                let hi_ty = self.typer().infer_ty_of_term(hi)?;
+                let _ = self.unifier().unify_terms(hi_ty, term_ty_id)?;
+
+                Ok(Some(vec![]))
+            }
             // Tuple: Unify the tuple with the subject, and then recurse to inner patterns
             Pat::Tuple(tuple_pat_params_id) => {
                 // Get the term of the tuple and try to unify it with the subject:
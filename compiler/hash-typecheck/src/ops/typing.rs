@@ -12,7 +12,7 @@ use crate::{
         primitives::{
             AccessOp, AccessPat, Arg, ArgsId, ConstPat, ConstructedTerm, Level0Term, Level1Term,
             Level2Term, Level3Term, ListPat, LitTerm, MemberData, ModDefOrigin, NominalDef, Param,
-            ParamsId, Pat, PatArgsId, PatId, StructFields, Term, TermId,
+            ParamsId, Pat, PatArgsId, PatId, RangePat, StructFields, Term, TermId,
         },
         AccessToStorage, AccessToStorageMut, StorageRefMut,
     },
@@ -246,10 +246,7 @@ impl<'gs, 'ls, 'cd, 's> Typer<'gs, 'ls, 'cd, 's> {
 
                         let var_to_resolve = match lit_term {
                             LitTerm::Str(_) => "str",
-                            LitTerm::Int(_) => {
-                                // @@Todo: do some more sophisticated inferring here
-                                "i32"
-                            }
+                            LitTerm::Int { kind, .. } => kind.as_str(),
                             LitTerm::Char(_) => "char",
                         };
                         let term = self.builder().create_var_term(var_to_resolve);
@@ -411,6 +408,11 @@ impl<'gs, 'ls, 'cd, 's> Typer<'gs, 'ls, 'cd, 's> {
                 // The term of a literal pattern is the literal (lol):
                 Ok(lit_term)
             }
+            Pat::Range(RangePat { lo, .. }) => {
+                // The subject of the range has the same type as the bounds, which
+                // are unified with the subject when matching
+                Ok(lo)
+            }
             Pat::Tuple(tuple_pat) => {
                 // For each parameter, get its type, and then create a tuple
                 // type:
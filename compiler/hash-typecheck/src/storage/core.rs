@@ -24,10 +24,12 @@ pub struct CoreDefs {
     pub i16_ty: NominalDefId,
     pub i32_ty: NominalDefId,
     pub i64_ty: NominalDefId,
+    pub isize_ty: NominalDefId,
     pub u8_ty: NominalDefId,
     pub u16_ty: NominalDefId,
     pub u32_ty: NominalDefId,
     pub u64_ty: NominalDefId,
+    pub usize_ty: NominalDefId,
     pub f32_ty: NominalDefId,
     pub f64_ty: NominalDefId,
     pub char_ty: NominalDefId,
@@ -59,11 +61,13 @@ impl CoreDefs {
         let i16_ty = builder.create_opaque_struct_def("i16");
         let i32_ty = builder.create_opaque_struct_def("i32");
         let i64_ty = builder.create_opaque_struct_def("i64");
+        let isize_ty = builder.create_opaque_struct_def("isize");
 
         let u8_ty = builder.create_opaque_struct_def("u8");
         let u16_ty = builder.create_opaque_struct_def("u16");
         let u32_ty = builder.create_opaque_struct_def("u32");
         let u64_ty = builder.create_opaque_struct_def("u64");
+        let usize_ty = builder.create_opaque_struct_def("usize");
 
         let f32_ty = builder.create_opaque_struct_def("f32");
         let f64_ty = builder.create_opaque_struct_def("f64");
@@ -368,10 +372,12 @@ impl CoreDefs {
             i16_ty,
             i32_ty,
             i64_ty,
+            isize_ty,
             u8_ty,
             u16_ty,
             u32_ty,
             u64_ty,
+            usize_ty,
             f32_ty,
             f64_ty,
             char_ty,
@@ -1,7 +1,7 @@
 //! Contains type definitions that the rest of the storage and the general
 //! typechecker use.
-use hash_ast::ast::ParamOrigin;
-use hash_source::{identifier::Identifier, SourceId};
+use hash_ast::ast::{ParamOrigin, RangeEnd};
+use hash_source::{constant::IntTy, identifier::Identifier, SourceId};
 use num_bigint::BigInt;
 use slotmap::new_key_type;
 use std::{
@@ -657,7 +657,7 @@ pub struct AccessTerm {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum LitTerm {
     Str(String),
-    Int(BigInt),
+    Int { value: BigInt, kind: IntTy },
     Char(char),
 }
 
@@ -675,13 +675,13 @@ impl From<String> for LitTerm {
 
 impl From<u64> for LitTerm {
     fn from(s: u64) -> Self {
-        LitTerm::Int(s.into())
+        LitTerm::Int { value: s.into(), kind: IntTy::U64 }
     }
 }
 
 impl From<i64> for LitTerm {
     fn from(s: i64) -> Self {
-        LitTerm::Int(s.into())
+        LitTerm::Int { value: s.into(), kind: IntTy::I64 }
     }
 }
 
@@ -1058,6 +1058,17 @@ pub struct SpreadPat {
     pub name: Option<Identifier>,
 }
 
+/// A range pattern, containing the terms of the lower and upper bounds of the
+/// range.
+///
+/// The bound terms must be literals, or run-time terms of a float type.
+#[derive(Clone, Debug, Copy)]
+pub struct RangePat {
+    pub lo: TermId,
+    pub hi: TermId,
+    pub end: RangeEnd,
+}
+
 /// A conditional pattern, containing a pattern and an condition.
 #[derive(Clone, Debug, Copy)]
 pub struct IfPat {
@@ -1096,6 +1107,8 @@ pub enum Pat {
     /// Spread pattern, which represents a pattern that captures a range of
     /// items within a list pattern
     Spread(SpreadPat),
+    /// Range pattern, which matches any value that lies between the bounds.
+    Range(RangePat),
     /// A set of patterns that are OR-ed together. If any one of them matches
     /// then the whole pattern matches.
     Or(Vec<PatId>),
@@ -9,9 +9,9 @@ use crate::{
     storage::{
         location::{IndexedLocationTarget, LocationTarget},
         primitives::{
-            AccessOp, Arg, ArgsId, BindingPat, ConstPat, EnumVariant, Member, MemberData,
-            ModDefOrigin, Mutability, Param, Pat, PatArg, PatId, ScopeKind, SpreadPat, Sub, TermId,
-            Visibility,
+            AccessOp, Arg, ArgsId, BindingPat, ConstPat, EnumVariant, Level1Term, LitTerm, Member,
+            MemberData, ModDefOrigin, Mutability, Param, Pat, PatArg, PatId, ScopeKind, SpreadPat,
+            Sub, Term, TermId, Visibility,
         },
         AccessToStorage, AccessToStorageMut, LocalStorage, StorageRef, StorageRefMut,
     },
@@ -23,6 +23,7 @@ use hash_ast::{
 use hash_pipeline::sources::{NodeMap, SourceRef};
 use hash_reporting::macros::panic_on_span;
 use hash_source::{
+    constant::{FloatConstant, FloatTy, IntConstant, IntConstantValue, IntTy},
     identifier::{Identifier, CORE_IDENTIFIERS},
     location::{SourceLocation, Span},
     ModuleKind, SourceId,
@@ -47,6 +48,10 @@ pub struct TcVisitorState {
     /// If traversing a declaration, what to set for the
     /// `assignments_until_closed` field.
     pub declaration_assignments_until_closed: usize,
+    /// If the current traversal is of an integer literal that is directly
+    /// negated, e.g. `-128i8`. This is used when checking that the literal
+    /// fits within its type.
+    pub within_negated_int_lit: bool,
 }
 
 impl TcVisitorState {
@@ -102,8 +107,9 @@ impl<'gs, 'ls, 'cd, 'src> TcVisitor<'gs, 'ls, 'cd, 'src> {
         }?;
 
         // Add the result to the checked sources.
-        // @@Correctness: the visitor will loop infinitely if there are circular module
-        // dependencies. Need to find a way to prevent this.
+        //
+        // The visitor would loop infinitely if there were circular module
+        // dependencies, but these are rejected by the parser.
         self.checked_sources_mut().mark_checked(source_id, result);
 
         log::debug!(
@@ -156,6 +162,104 @@ impl<'gs, 'ls, 'cd, 'src> TcVisitor<'gs, 'ls, 'cd, 'src> {
             self.copy_location_from_node_to_target(param, LocationTarget::from((targets, index)));
         }
     }
+
+    /// Create a [LitTerm::Int] from the given [IntConstant]. If the constant
+    /// has no type suffix, the literal defaults to being an `i32`.
+    pub(crate) fn create_int_lit_term(&mut self, constant: IntConstant) -> TermId {
+        let IntConstantValue { value, suffix } = constant.value();
+
+        // @@Todo: infer the type of un-suffixed literals from their usage.
+        let kind = suffix.unwrap_or(IntTy::I32);
+        self.builder().create_lit_term(LitTerm::Int { value, kind })
+    }
+
+    /// Create a run-time term of the float type that is specified by the suffix
+    /// of the given [FloatConstant]. If the constant has no type suffix, the
+    /// literal defaults to being an `f32`.
+    pub(crate) fn create_float_lit_term(&mut self, constant: FloatConstant) -> TermId {
+        // @@Todo: infer the type of un-suffixed literals from their usage.
+        let float_def = match constant.value().suffix {
+            Some(FloatTy::F64) => self.core_defs().f64_ty,
+            Some(FloatTy::F32) | None => self.core_defs().f32_ty,
+        };
+        let ty = self.builder().create_nominal_def_term(float_def);
+        self.builder().create_rt_term(ty)
+    }
+
+    /// Create the term of a bound of a range pattern, range patterns only allow
+    /// for `int`, `float` and `char` literals to be used as bounds.
+    fn create_range_pat_bound_term(&mut self, node: AstNodeRef<ast::LitPat>) -> TcResult<TermId> {
+        let term = match node.body() {
+            ast::LitPat::Int(ast::IntLitPat(constant)) => {
+                // The location is needed to report that the literal is out of range
+                let term = self.create_int_lit_term(*constant);
+                self.copy_location_from_node_to_target(node, term);
+                self.validate_int_lit_range(term, *constant)?;
+                term
+            }
+            ast::LitPat::Float(ast::FloatLitPat(constant)) => self.create_float_lit_term(*constant),
+            ast::LitPat::Char(ast::CharLitPat(value)) => self.builder().create_lit_term(*value),
+            ast::LitPat::Str(_) | ast::LitPat::Bool(_) => panic_on_span!(
+                self.source_location_at_node(node),
+                self.source_map(),
+                "hit illegal range pattern bound during typechecking"
+            ),
+        };
+
+        self.copy_location_from_node_to_target(node, term);
+        Ok(term)
+    }
+
+    /// Check that the value of the given [IntConstant] fits within the range
+    /// of its type, taking into account whether the literal is being negated.
+    /// Only literals with a type suffix are checked, since the type of an
+    /// un-suffixed literal isn't known yet.
+    pub(crate) fn validate_int_lit_range(
+        &self,
+        term: TermId,
+        constant: IntConstant,
+    ) -> TcResult<()> {
+        let IntConstantValue { value, suffix } = constant.value();
+
+        // @@Todo: check un-suffixed literals once their type is inferred from their
+        // usage.
+        let kind = match suffix {
+            Some(kind) => kind,
+            None => return Ok(()),
+        };
+
+        let value = if self.state.within_negated_int_lit { -value } else { value };
+
+        if value < kind.min() || value > kind.max() {
+            return Err(TcError::IntLitOutOfRange { term, kind });
+        }
+
+        Ok(())
+    }
+
+    /// Check that the subject of a de-sugared `?` expression is a `Result`,
+    /// since the generated match only has cases for `Ok` and `Err`. Subjects
+    /// whose type isn't known to be a nominal definition, tuple or function
+    /// are accepted.
+    fn validate_propagate_subject(&mut self, subject: TermId) -> TcResult<()> {
+        let subject_ty = self.typer().infer_ty_of_term(subject)?;
+        let subject_ty = self.simplifier().potentially_simplify_term(subject_ty)?;
+
+        let reader = self.reader();
+        let is_result = match reader.get_term(subject_ty) {
+            Term::Level1(Level1Term::NominalDef(nominal_def_id)) => {
+                reader.get_nominal_def(*nominal_def_id).name() == Some(CORE_IDENTIFIERS.result)
+            }
+            Term::Level1(_) => false,
+            _ => true,
+        };
+
+        if !is_result {
+            return Err(TcError::PropagateOnNonResult { subject, subject_ty });
+        }
+
+        Ok(())
+    }
 }
 
 /// Implementation of [visitor::AstVisitor] for [TcVisitor], to traverse the AST
@@ -350,6 +454,76 @@ impl<'gs, 'ls, 'cd, 'src> visitor::AstVisitor for TcVisitor<'gs, 'ls, 'cd, 'src>
         Ok(term)
     }
 
+    type RawStrLitRet = TermId;
+
+    fn visit_raw_str_lit(
+        &mut self,
+        _ctx: &Self::Ctx,
+        node: hash_ast::ast::AstNodeRef<hash_ast::ast::RawStrLit>,
+    ) -> Result<Self::RawStrLitRet, Self::Error> {
+        let term = self.builder().create_lit_term(node.0.to_string());
+
+        // add the location of the term to the location storage
+        self.copy_location_from_node_to_target(node, term);
+
+        Ok(term)
+    }
+
+    type ByteStrLitRet = TermId;
+
+    fn visit_byte_str_lit(
+        &mut self,
+        _ctx: &Self::Ctx,
+        node: hash_ast::ast::AstNodeRef<hash_ast::ast::ByteStrLit>,
+    ) -> Result<Self::ByteStrLitRet, Self::Error> {
+        // Byte strings are lists of `u8`s
+        let list_inner_ty = self.core_defs().list_ty_fn;
+        let u8_def = self.core_defs().u8_ty;
+
+        let builder = self.builder();
+        let element_ty = builder.create_nominal_def_term(u8_def);
+        let list_ty = builder.create_app_ty_fn_term(
+            list_inner_ty,
+            builder.create_args([builder.create_arg("T", element_ty)], ParamOrigin::TyFn),
+        );
+
+        let term = builder.create_rt_term(list_ty);
+
+        // add the location of the term to the location storage
+        self.copy_location_from_node_to_target(node, term);
+
+        Ok(term)
+    }
+
+    type MultiLineStrLitRet = TermId;
+
+    fn visit_multi_line_str_lit(
+        &mut self,
+        _ctx: &Self::Ctx,
+        node: hash_ast::ast::AstNodeRef<hash_ast::ast::MultiLineStrLit>,
+    ) -> Result<Self::MultiLineStrLitRet, Self::Error> {
+        let term = self.builder().create_lit_term(node.0.to_string());
+
+        // add the location of the term to the location storage
+        self.copy_location_from_node_to_target(node, term);
+
+        Ok(term)
+    }
+
+    type InterpolatedStrLitRet = TermId;
+
+    fn visit_interpolated_str_lit(
+        &mut self,
+        _ctx: &Self::Ctx,
+        node: hash_ast::ast::AstNodeRef<hash_ast::ast::InterpolatedStrLit>,
+    ) -> Result<Self::InterpolatedStrLitRet, Self::Error> {
+        panic_on_span!(
+            self.source_location_at_node(node),
+            self.source_map(),
+            "hit interpolated string literal during typechecking"
+        )
+    }
+
     type CharLitRet = TermId;
 
     fn visit_char_lit(
@@ -372,9 +546,7 @@ impl<'gs, 'ls, 'cd, 'src> visitor::AstVisitor for TcVisitor<'gs, 'ls, 'cd, 'src>
         _ctx: &Self::Ctx,
         node: hash_ast::ast::AstNodeRef<hash_ast::ast::FloatLit>,
     ) -> Result<Self::FloatLitRet, Self::Error> {
-        let f32_def = self.core_defs().f32_ty;
-        let ty = self.builder().create_nominal_def_term(f32_def);
-        let term = self.builder().create_rt_term(ty);
+        let term = self.create_float_lit_term(node.0);
 
         // add the location of the term to the location storage
         self.copy_location_from_node_to_target(node, term);
@@ -404,10 +576,11 @@ impl<'gs, 'ls, 'cd, 'src> visitor::AstVisitor for TcVisitor<'gs, 'ls, 'cd, 'src>
         _: &Self::Ctx,
         node: hash_ast::ast::AstNodeRef<hash_ast::ast::IntLit>,
     ) -> Result<Self::IntLitRet, Self::Error> {
-        let term = self.builder().create_lit_term(node.0);
+        let term = self.create_int_lit_term(node.0);
 
         // add the location of the term to the location storage
         self.copy_location_from_node_to_target(node, term);
+        self.validate_int_lit_range(term, node.0)?;
 
         Ok(term)
     }
@@ -1244,6 +1417,10 @@ impl<'gs, 'ls, 'cd, 'src> visitor::AstVisitor for TcVisitor<'gs, 'ls, 'cd, 'src>
     ) -> Result<Self::MatchBlockRet, Self::Error> {
         let walk::MatchBlock { subject, .. } = walk::walk_match_block(self, ctx, node)?;
 
+        if node.origin == ast::MatchOrigin::Propagate {
+            self.validate_propagate_subject(subject)?;
+        }
+
         let mut redundant_errors = vec![];
         let match_return_values: Vec<_> = node
             .cases
@@ -1539,6 +1716,18 @@ impl<'gs, 'ls, 'cd, 'src> visitor::AstVisitor for TcVisitor<'gs, 'ls, 'cd, 'src>
         Ok(*node.body())
     }
 
+    type AttributeRet = ();
+
+    fn visit_attribute(
+        &mut self,
+        _: &Self::Ctx,
+        _: hash_ast::ast::AstNodeRef<hash_ast::ast::Attribute>,
+    ) -> Result<Self::AttributeRet, Self::Error> {
+        // Attributes are validated during semantic analysis, and don't affect the
+        // types of declarations.
+        Ok(())
+    }
+
     type DeclarationRet = TermId;
 
     fn visit_declaration(
@@ -1725,7 +1914,9 @@ impl<'gs, 'ls, 'cd, 'src> visitor::AstVisitor for TcVisitor<'gs, 'ls, 'cd, 'src>
 
         let term = match node.operator.body() {
             BinOp::Merge => self.builder().create_merge_term([lhs, rhs]),
-            BinOp::As => unreachable!(),
+            // Casts are parsed into a separate expression, and pipelines are de-sugared
+            // into calls
+            BinOp::As | BinOp::Pipe => unreachable!(),
             BinOp::EqEq => operator_fn("eq"),
             BinOp::NotEq => operator_fn("not_eq"),
             BinOp::BitOr => operator_fn("bit_or"),
@@ -1760,7 +1951,19 @@ impl<'gs, 'ls, 'cd, 'src> visitor::AstVisitor for TcVisitor<'gs, 'ls, 'cd, 'src>
         ctx: &Self::Ctx,
         node: hash_ast::ast::AstNodeRef<hash_ast::ast::UnaryExpr>,
     ) -> Result<Self::UnaryExprRet, Self::Error> {
-        let walk::UnaryExpr { expr, .. } = walk::walk_unary_expr(self, ctx, node)?;
+        // If an integer literal is being negated, then the range of the literal must be
+        // checked with the negated value.
+        let old_negated_int_lit_state = self.state.within_negated_int_lit;
+        self.state.within_negated_int_lit = matches!(node.operator.body(), UnOp::Neg)
+            && matches!(
+                node.expr.kind(),
+                ast::ExprKind::LitExpr(ast::LitExpr(lit)) if matches!(lit.body(), ast::Lit::Int(_))
+            );
+
+        let walk_result = walk::walk_unary_expr(self, ctx, node);
+        self.state.within_negated_int_lit = old_negated_int_lit_state;
+
+        let walk::UnaryExpr { expr, .. } = walk_result?;
 
         let mut operator_fn = |trait_fn_name: &str| {
             let prop_access = self.builder().create_prop_access(expr, trait_fn_name);
@@ -1781,6 +1984,48 @@ impl<'gs, 'ls, 'cd, 'src> visitor::AstVisitor for TcVisitor<'gs, 'ls, 'cd, 'src>
         Ok(self.validator().validate_term(term)?.simplified_term_id)
     }
 
+    type RangeExprRet = TermId;
+
+    fn visit_range_expr(
+        &mut self,
+        _ctx: &Self::Ctx,
+        node: hash_ast::ast::AstNodeRef<hash_ast::ast::RangeExpr>,
+    ) -> Result<Self::RangeExprRet, Self::Error> {
+        panic_on_span!(
+            self.source_location_at_node(node),
+            self.source_map(),
+            "hit range expression during typechecking"
+        )
+    }
+
+    type PropagateExprRet = TermId;
+
+    fn visit_propagate_expr(
+        &mut self,
+        _ctx: &Self::Ctx,
+        node: hash_ast::ast::AstNodeRef<hash_ast::ast::PropagateExpr>,
+    ) -> Result<Self::PropagateExprRet, Self::Error> {
+        panic_on_span!(
+            self.source_location_at_node(node),
+            self.source_map(),
+            "hit error propagation expression during typechecking"
+        )
+    }
+
+    type ErrExprRet = TermId;
+
+    fn visit_err_expr(
+        &mut self,
+        _ctx: &Self::Ctx,
+        node: hash_ast::ast::AstNodeRef<hash_ast::ast::ErrExpr>,
+    ) -> Result<Self::ErrExprRet, Self::Error> {
+        panic_on_span!(
+            self.source_location_at_node(node),
+            self.source_map(),
+            "hit error expression during typechecking"
+        )
+    }
+
     type IndexExprRet = TermId;
 
     fn visit_index_expr(
@@ -2093,11 +2338,12 @@ impl<'gs, 'ls, 'cd, 'src> visitor::AstVisitor for TcVisitor<'gs, 'ls, 'cd, 'src>
         _: &Self::Ctx,
         node: hash_ast::ast::AstNodeRef<hash_ast::ast::IntLitPat>,
     ) -> Result<Self::IntLitPatRet, Self::Error> {
-        let lit = self.builder().create_lit_term(node.0);
+        let lit = self.create_int_lit_term(node.0);
         let lit_pat = self.builder().create_lit_pat(lit);
 
         self.copy_location_from_node_to_target(node, lit);
         self.copy_location_from_node_to_target(node, lit_pat);
+        self.validate_int_lit_range(lit, node.0)?;
 
         Ok(lit_pat)
     }
@@ -2211,6 +2457,35 @@ impl<'gs, 'ls, 'cd, 'src> visitor::AstVisitor for TcVisitor<'gs, 'ls, 'cd, 'src>
         Ok(pat)
     }
 
+    type RangePatRet = PatId;
+
+    fn visit_range_pat(
+        &mut self,
+        _ctx: &Self::Ctx,
+        node: hash_ast::ast::AstNodeRef<hash_ast::ast::RangePat>,
+    ) -> Result<Self::RangePatRet, Self::Error> {
+        let lo = self.create_range_pat_bound_term(node.lo.ast_ref())?;
+        let hi = self.create_range_pat_bound_term(node.hi.ast_ref())?;
+
+        let range_pat = self.builder().create_range_pat(lo, hi, node.end);
+        self.copy_location_from_node_to_target(node, range_pat);
+        Ok(range_pat)
+    }
+
+    type ErrPatRet = PatId;
+
+    fn visit_err_pat(
+        &mut self,
+        _ctx: &Self::Ctx,
+        node: hash_ast::ast::AstNodeRef<hash_ast::ast::ErrPat>,
+    ) -> Result<Self::ErrPatRet, Self::Error> {
+        panic_on_span!(
+            self.source_location_at_node(node),
+            self.source_map(),
+            "hit error pattern during typechecking"
+        )
+    }
+
     type ModulePatEntryRet = PatArg;
 
     fn visit_module_pat_entry(
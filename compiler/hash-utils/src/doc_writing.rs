@@ -0,0 +1,285 @@
+//! A line-breaking layout engine for pretty-printing source code. Contents are
+//! described as a [Doc], which contains the text to print along with the
+//! places where lines can be broken. The [DocWriter] then chooses which groups
+//! of the document to break so that the lines fit within a configured width.
+use core::fmt;
+use std::borrow::Cow;
+
+/// A document that can be laid out by the [DocWriter].
+#[derive(Debug, Clone)]
+pub enum Doc {
+    /// Text that is printed as is. The text should not contain line breaks,
+    /// apart from text that must be printed verbatim, e.g. multi-line string
+    /// literals or block comments. Only the first line of such text is
+    /// considered when checking whether a group fits.
+    Text(Cow<'static, str>),
+    /// A space if the enclosing group fits on one line, otherwise a line
+    /// break.
+    Line,
+    /// Nothing if the enclosing group fits on one line, otherwise a line
+    /// break.
+    SoftLine,
+    /// A line break that is always taken, which forces all of the enclosing
+    /// groups to be broken.
+    HardLine,
+    /// The first document is printed if the enclosing group is broken,
+    /// otherwise the second is printed.
+    IfBreak(Box<Doc>, Box<Doc>),
+    /// Increase the indentation of any line breaks within the document by one
+    /// level.
+    Nest(Box<Doc>),
+    /// A group of documents which is printed on one line if it fits within the
+    /// remaining width, otherwise the line breaks within the group are taken.
+    Group(Box<Doc>),
+    /// A sequence of documents.
+    Concat(Vec<Doc>),
+}
+
+impl Doc {
+    /// An empty document.
+    pub fn nil() -> Self {
+        Doc::Concat(vec![])
+    }
+
+    /// Create a [Doc::Text] document.
+    pub fn text(text: impl Into<Cow<'static, str>>) -> Self {
+        Doc::Text(text.into())
+    }
+
+    /// Create a [Doc::Concat] document from the given documents.
+    pub fn concat(docs: impl IntoIterator<Item = Doc>) -> Self {
+        Doc::Concat(docs.into_iter().collect())
+    }
+
+    /// Join the given documents with the given separator.
+    pub fn join(docs: impl IntoIterator<Item = Doc>, separator: Doc) -> Self {
+        let mut items = vec![];
+
+        for (index, doc) in docs.into_iter().enumerate() {
+            if index > 0 {
+                items.push(separator.clone());
+            }
+
+            items.push(doc);
+        }
+
+        Doc::Concat(items)
+    }
+
+    /// Print the given text only if the enclosing group is broken.
+    pub fn if_break(text: impl Into<Cow<'static, str>>) -> Self {
+        Doc::IfBreak(Box::new(Doc::text(text)), Box::new(Doc::nil()))
+    }
+
+    /// Wrap the document in a [Doc::Group].
+    pub fn group(self) -> Self {
+        Doc::Group(Box::new(self))
+    }
+
+    /// Wrap the document in a [Doc::Nest].
+    pub fn nest(self) -> Self {
+        Doc::Nest(Box::new(self))
+    }
+
+    /// Append another document to this document.
+    pub fn append(self, other: Doc) -> Self {
+        match self {
+            Doc::Concat(mut items) => {
+                items.push(other);
+                Doc::Concat(items)
+            }
+            doc => Doc::Concat(vec![doc, other]),
+        }
+    }
+
+    /// Check whether the document is empty.
+    pub fn is_nil(&self) -> bool {
+        match self {
+            Doc::Text(text) => text.is_empty(),
+            Doc::Concat(items) => items.iter().all(|item| item.is_nil()),
+            Doc::Nest(doc) | Doc::Group(doc) => doc.is_nil(),
+            _ => false,
+        }
+    }
+}
+
+impl From<&'static str> for Doc {
+    fn from(text: &'static str) -> Self {
+        Doc::text(text)
+    }
+}
+
+impl From<String> for Doc {
+    fn from(text: String) -> Self {
+        Doc::text(text)
+    }
+}
+
+/// Configuration for [DocWriter].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocWriterConfig {
+    /// The maximum width of a line.
+    pub max_width: usize,
+    /// The number of columns that a single level of indentation takes.
+    pub indent_width: usize,
+    /// Whether to indent using tabs rather than spaces.
+    pub hard_tabs: bool,
+}
+
+impl Default for DocWriterConfig {
+    fn default() -> Self {
+        Self { max_width: 100, indent_width: 4, hard_tabs: false }
+    }
+}
+
+/// Whether the contents of a group are printed on a single line, or broken
+/// over multiple lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Flat,
+    Break,
+}
+
+/// A document that is waiting to be printed, along with the indentation level
+/// and the mode of its enclosing group.
+type Command<'d> = (usize, Mode, &'d Doc);
+
+/// Can print a [Doc] through [fmt::Display], breaking the groups of the
+/// document that don't fit within the configured width.
+#[derive(Debug, Clone)]
+pub struct DocWriter<'d, 'cfg> {
+    doc: &'d Doc,
+    config: Cow<'cfg, DocWriterConfig>,
+}
+
+impl<'d, 'cfg> DocWriter<'d, 'cfg> {
+    /// Create a new [DocWriter] with the given [Doc] and default
+    /// configuration.
+    pub fn new(doc: &'d Doc) -> Self {
+        Self::new_with_config(doc, DocWriterConfig::default())
+    }
+
+    /// Create a new [DocWriter] with the given [Doc] and configuration
+    /// [DocWriterConfig].
+    pub fn new_with_config(doc: &'d Doc, config: DocWriterConfig) -> Self {
+        Self { doc, config: Cow::Owned(config) }
+    }
+
+    /// Check whether the `next` command fits within the `width` when it is
+    /// printed flat. The check continues with the `rest` of the commands until
+    /// the first line break is reached, so that the text which follows a group
+    /// on the same line is accounted for.
+    fn fits(&self, next: Command<'d>, rest: &[Command<'d>], width: usize) -> bool {
+        let mut width = width as isize;
+        let mut rest = rest.iter().rev();
+        let mut stack = vec![next];
+
+        loop {
+            let (indent, mode, doc) = match stack.pop() {
+                Some(command) => command,
+                None => match rest.next() {
+                    Some(command) => *command,
+                    None => return true,
+                },
+            };
+
+            match doc {
+                Doc::Text(text) => match text.find('\n') {
+                    Some(index) => return width >= text[..index].chars().count() as isize,
+                    None => width -= text.chars().count() as isize,
+                },
+                Doc::Line | Doc::SoftLine if mode == Mode::Break => return true,
+                Doc::Line => width -= 1,
+                Doc::SoftLine => {}
+                Doc::HardLine => return mode == Mode::Break,
+                Doc::IfBreak(broken, flat) => {
+                    stack.push((indent, mode, if mode == Mode::Break { broken } else { flat }))
+                }
+                Doc::Nest(doc) => stack.push((indent + 1, mode, doc)),
+                Doc::Group(doc) => stack.push((indent, mode, doc)),
+                Doc::Concat(items) => {
+                    stack.extend(items.iter().rev().map(|item| (indent, mode, item)))
+                }
+            }
+
+            if width < 0 {
+                return false;
+            }
+        }
+    }
+
+    /// Write the indentation for the given level.
+    fn write_indent(&self, f: &mut fmt::Formatter, level: usize) -> fmt::Result {
+        if self.config.hard_tabs {
+            write!(f, "{}", "\t".repeat(level))
+        } else {
+            write!(f, "{}", " ".repeat(level * self.config.indent_width))
+        }
+    }
+}
+
+impl fmt::Display for DocWriter<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut stack: Vec<Command> = vec![(0, Mode::Break, self.doc)];
+        let mut column = 0;
+
+        // The indentation of a line is only written once the line has contents,
+        // so that empty lines don't contain any trailing whitespace.
+        let mut pending_indent = None;
+
+        while let Some((indent, mode, doc)) = stack.pop() {
+            match doc {
+                Doc::Text(text) => {
+                    if text.is_empty() {
+                        continue;
+                    }
+
+                    if let Some(level) = pending_indent.take() {
+                        self.write_indent(f, level)?;
+                    }
+
+                    write!(f, "{}", text)?;
+
+                    column = match text.rfind('\n') {
+                        Some(index) => text[index + 1..].chars().count(),
+                        None => column + text.chars().count(),
+                    };
+                }
+                Doc::Line if mode == Mode::Flat => {
+                    if pending_indent.is_none() {
+                        write!(f, " ")?;
+                        column += 1;
+                    }
+                }
+                Doc::SoftLine if mode == Mode::Flat => {}
+                Doc::Line | Doc::SoftLine | Doc::HardLine => {
+                    writeln!(f)?;
+                    pending_indent = Some(indent);
+                    column = indent * self.config.indent_width;
+                }
+                Doc::IfBreak(broken, flat) => {
+                    stack.push((indent, mode, if mode == Mode::Break { broken } else { flat }))
+                }
+                Doc::Nest(doc) => stack.push((indent + 1, mode, doc)),
+                Doc::Group(doc) => {
+                    let width = self.config.max_width.saturating_sub(column);
+
+                    let mode = if mode == Mode::Flat
+                        || self.fits((indent, Mode::Flat, doc), &stack, width)
+                    {
+                        Mode::Flat
+                    } else {
+                        Mode::Break
+                    };
+
+                    stack.push((indent, mode, doc));
+                }
+                Doc::Concat(items) => {
+                    stack.extend(items.iter().rev().map(|item| (indent, mode, item)))
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
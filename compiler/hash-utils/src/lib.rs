@@ -1,5 +1,6 @@
 //! Hash Compiler timing and profiling utilities.
 
+pub mod doc_writing;
 pub mod path;
 pub mod printing;
 pub mod testing;
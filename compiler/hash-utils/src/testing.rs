@@ -1,4 +1,10 @@
-use std::path::PathBuf;
+//! Utilities that are shared between the UI test runners, which compare the
+//! output of the compiler for each case with the output that is recorded
+//! within the case.
+use std::{fs, path::PathBuf};
+
+use lazy_static::lazy_static;
+use regex::Regex;
 
 #[derive(Debug, Clone)]
 pub struct TestingInput {
@@ -7,3 +13,64 @@ pub struct TestingInput {
 }
 
 pub type TestingFn = fn(input: TestingInput);
+
+/// Whether or not the UI tests should re-generate the output.
+pub const REGENERATE_OUTPUT: bool = false;
+
+/// This is the ANSI Regular expression matcher. This will match all the
+/// specified ANSI escape codes that are used by the `hash_reporting` crate.
+const ANSI_RE: &str = r"[\x1b\x9b]\[[()#;?]*(?:[0-9]{1,4}(?:;[0-9]{0,4})*)?[0-9A-ORZcf-nqry=><]";
+
+lazy_static! {
+    static ref ANSI_REGEX: Regex = Regex::new(ANSI_RE).unwrap();
+}
+
+/// Remove any ANSI escape codes from the given rendered reports, and replace
+/// the directory of the case by `$DIR` so that the output doesn't depend on
+/// where the repository is.
+pub fn normalise_reports(input: &TestingInput, reports: &str) -> String {
+    let reports = ANSI_REGEX.replace_all(reports, "");
+
+    let dir_regex = Regex::new(&regex::escape(input.path.to_str().unwrap())).unwrap();
+    dir_regex.replace_all(reports.as_ref(), r"$$DIR").to_string()
+}
+
+/// Verify that the given output matches the output that is recorded within
+/// the file named `file_name` in the case. If the file doesn't exist, or if
+/// [REGENERATE_OUTPUT] is set, then the output is recorded first.
+pub fn compare_output(input: &TestingInput, file_name: &str, output: &str) {
+    let output_path = input.path.join(file_name);
+
+    if REGENERATE_OUTPUT || !output_path.exists() {
+        fs::write(&output_path, output).unwrap();
+    }
+
+    let expected = fs::read_to_string(output_path).unwrap();
+    pretty_assertions::assert_eq!(expected, output);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::assertions_on_constants)]
+    fn ensure_regenerate_output_is_disabled() {
+        assert!(
+            !REGENERATE_OUTPUT,
+            "
+        Verify that the `REGENERATE_OUTPUT` module flag is not accidentally left
+        on making all of the test cases that observe compiler output
+        automatically overwrite old results with current ones.
+        "
+        );
+    }
+
+    #[test]
+    fn normalise_reports_of_case() {
+        let input = TestingInput { path: PathBuf::from("/cases/a+b"), snake_name: "a_b".into() };
+        let reports = "\x1b[31;1merror\x1b[0m: oops\n --> /cases/a+b/case.hash:1:1";
+
+        assert_eq!(normalise_reports(&input, reports), "error: oops\n --> $DIR/case.hash:1:1");
+    }
+}
@@ -0,0 +1,479 @@
+//! Parsing the items that are given to `define_tree`, and collecting the
+//! definitions of the nodes from them.
+
+use std::collections::HashMap;
+
+use convert_case::{Case, Casing};
+use syn::{
+    parse::{Parse, ParseStream},
+    Attribute, Fields, GenericArgument, Ident, Index, Item, Lit, Member, Meta, NestedMeta,
+    PathArguments, Type,
+};
+
+/// The attribute that marks a type as a node in the tree.
+const NODE_ATTR: &str = "node";
+
+/// The attribute that marks the field of a struct node whose variants are
+/// visited as the node.
+const NODE_KIND_ATTR: &str = "node_kind";
+
+/// The attribute that renames the member of the walk structure for a field
+/// of a struct node.
+const NODE_FIELD_ATTR: &str = "node_field";
+
+/// How a child node is stored within the field of its parent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ChildKind {
+    /// `AstNode<T>`
+    One,
+    /// `Option<AstNode<T>>`
+    Optional,
+    /// `AstNodes<T>`
+    Many,
+    /// `T`, where `T` is a node that is visited by value.
+    Value,
+}
+
+/// The names that are derived from the type of a node, each of which can be
+/// overridden by the arguments of its `#[node]` attribute.
+#[derive(Debug, Clone)]
+pub(crate) struct NodeNames {
+    /// The name of the node within the names of the methods that visit and
+    /// fold it, e.g. `fn_def` for `visit_fn_def`. Given by `visit = "..."`.
+    pub method: Ident,
+    /// The name of the visitor return type of the node, e.g. `FnDefRet`.
+    /// Given by `ret = "..."`.
+    pub ret: Ident,
+    /// The name of the walk structure of the node, e.g. `FnDef`, which also
+    /// names the walk function, e.g. `walk_fn_def`. Given by `walk = "..."`.
+    pub walk: Ident,
+    /// Whether the node is passed to the visitor by value rather than by a
+    /// node reference, which is the case for `#[node(by_value)]`. Such a node
+    /// is stored without an `AstNode` within its parent, and is not folded.
+    pub by_value: bool,
+}
+
+/// A field of a struct node which contains children.
+#[derive(Debug)]
+pub(crate) struct ChildField {
+    /// How the field is accessed on the node.
+    pub member: Member,
+    /// The name of the member of the walk structure, which is the same as
+    /// the field unless it is renamed with `#[node_field(name = "...")]`.
+    pub walk_member: Member,
+    /// How the child is stored within the field.
+    pub kind: ChildKind,
+    /// The names of the child node.
+    pub node: NodeNames,
+}
+
+/// A variant of a node that holds another node.
+#[derive(Debug)]
+pub(crate) struct NodeVariant {
+    /// The name of the variant.
+    pub name: Ident,
+    /// The names of the node that is held by the variant.
+    pub node: NodeNames,
+}
+
+/// The shape of a node, which determines how it is walked.
+#[derive(Debug)]
+pub(crate) enum NodeShape {
+    /// A node without any children.
+    Leaf,
+    /// A struct node with the fields which contain children, whether the
+    /// struct is a tuple struct, and whether it has any other fields.
+    Struct { fields: Vec<ChildField>, is_tuple: bool, has_other_fields: bool },
+    /// An enum node, or a struct node with a `#[node_kind]` field. Each variant
+    /// holds a node which shares the span and the id of this node.
+    Variants {
+        /// The name of the enum that holds the variants.
+        enum_name: Ident,
+        /// The field of the struct node which holds the variants, if this isn't
+        /// an enum node.
+        field: Option<Member>,
+        variants: Vec<NodeVariant>,
+    },
+}
+
+/// A node within the tree.
+#[derive(Debug)]
+pub(crate) struct NodeDef {
+    pub name: Ident,
+    pub names: NodeNames,
+    pub shape: NodeShape,
+}
+
+/// The items of a tree definition, along with the nodes that were found in
+/// them. The items have the `#[node]`, `#[node_kind]` and `#[node_field]`
+/// attributes removed.
+#[derive(Debug)]
+pub(crate) struct TreeDef {
+    pub items: Vec<Item>,
+    pub nodes: Vec<NodeDef>,
+}
+
+/// Remove the attribute with the given name from the attributes, returning
+/// it if it was present.
+fn take_attr(attrs: &mut Vec<Attribute>, name: &str) -> Option<Attribute> {
+    let index = attrs.iter().position(|attr| attr.path.is_ident(name))?;
+    Some(attrs.remove(index))
+}
+
+/// The arguments of a node attribute.
+#[derive(Default)]
+struct AttrArgs {
+    /// The `key = "value"` arguments.
+    values: Vec<(Ident, Ident)>,
+    /// The keys of the flag arguments.
+    flags: Vec<Ident>,
+}
+
+/// Get the arguments of an attribute, an attribute without arguments has
+/// neither values nor flags.
+fn attr_args(attr: &Attribute) -> syn::Result<AttrArgs> {
+    let mut args = AttrArgs::default();
+
+    let nested = match attr.parse_meta()? {
+        Meta::Path(_) => return Ok(args),
+        Meta::List(list) => list.nested,
+        meta @ Meta::NameValue(_) => {
+            return Err(syn::Error::new_spanned(meta, "Expecting a list of arguments"))
+        }
+    };
+
+    for arg in nested {
+        match arg {
+            NestedMeta::Meta(Meta::NameValue(arg)) if arg.path.get_ident().is_some() => {
+                let key = arg.path.get_ident().unwrap().clone();
+                let value = match &arg.lit {
+                    Lit::Str(value) => value.parse::<Ident>()?,
+                    lit => return Err(syn::Error::new_spanned(lit, "Expecting an identifier")),
+                };
+
+                args.values.push((key, value));
+            }
+            NestedMeta::Meta(Meta::Path(path)) if path.get_ident().is_some() => {
+                args.flags.push(path.get_ident().unwrap().clone());
+            }
+            arg => return Err(syn::Error::new_spanned(arg, "Unexpected argument")),
+        }
+    }
+
+    Ok(args)
+}
+
+impl NodeNames {
+    /// Derive the names of the node with the given type name, applying the
+    /// overrides within its `#[node]` attribute.
+    fn from_attr(name: &Ident, attr: &Attribute) -> syn::Result<Self> {
+        let mut names = NodeNames {
+            method: Ident::new(&name.to_string().to_case(Case::Snake), name.span()),
+            ret: Ident::new(&format!("{}Ret", name), name.span()),
+            walk: name.clone(),
+            by_value: false,
+        };
+
+        let AttrArgs { values, flags } = attr_args(attr)?;
+
+        for (key, value) in values {
+            match key.to_string().as_str() {
+                "visit" => names.method = value,
+                "ret" => names.ret = value,
+                "walk" => names.walk = value,
+                _ => return Err(syn::Error::new_spanned(key, "Unknown node name")),
+            }
+        }
+
+        for flag in flags {
+            match flag.to_string().as_str() {
+                "by_value" => names.by_value = true,
+                _ => return Err(syn::Error::new_spanned(flag, "Unknown node flag")),
+            }
+        }
+
+        Ok(names)
+    }
+}
+
+/// Get the single generic type argument of the last segment of the given type
+/// if the segment has the given name.
+fn generic_arg_of<'t>(ty: &'t Type, name: &str) -> Option<&'t Type> {
+    let segment = match ty {
+        Type::Path(path) if path.qself.is_none() => path.path.segments.last()?,
+        _ => return None,
+    };
+
+    if segment.ident != name {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Get the name of a type that is a single identifier, such as `Expr`.
+fn type_name(ty: &Type) -> Option<&Ident> {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path.path.get_ident(),
+        _ => None,
+    }
+}
+
+/// Check whether the given field type contains a child, and if so, how it is
+/// stored and the type of the child. A field that holds a node without an
+/// `AstNode` is only a child if the node is visited by value.
+fn child_of<'t>(
+    ty: &'t Type,
+    nodes: &HashMap<String, NodeNames>,
+) -> Option<(ChildKind, &'t Ident)> {
+    if let Some(inner) = generic_arg_of(ty, "AstNode") {
+        Some((ChildKind::One, type_name(inner)?))
+    } else if let Some(inner) = generic_arg_of(ty, "AstNodes") {
+        Some((ChildKind::Many, type_name(inner)?))
+    } else if let Some(inner) = generic_arg_of(ty, "Option") {
+        let inner = generic_arg_of(inner, "AstNode")?;
+        Some((ChildKind::Optional, type_name(inner)?))
+    } else {
+        let name = type_name(ty)?;
+        nodes
+            .get(&name.to_string())
+            .filter(|names| names.by_value)
+            .map(|_| (ChildKind::Value, name))
+    }
+}
+
+/// Get the names of the node with the given type, which must be a node
+/// within the tree.
+fn node_names<'n>(
+    node: &Ident,
+    nodes: &'n HashMap<String, NodeNames>,
+) -> syn::Result<&'n NodeNames> {
+    nodes.get(&node.to_string()).ok_or_else(|| {
+        syn::Error::new_spanned(node, format!("`{}` is not a node within the tree", node))
+    })
+}
+
+/// Collect the variants of the given enum, each of which must hold a single
+/// node.
+fn node_variants(
+    item: &syn::ItemEnum,
+    nodes: &HashMap<String, NodeNames>,
+) -> syn::Result<Vec<NodeVariant>> {
+    item.variants
+        .iter()
+        .map(|variant| {
+            let node = match &variant.fields {
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                    type_name(&fields.unnamed[0].ty)
+                }
+                _ => None,
+            }
+            .and_then(|node| nodes.get(&node.to_string()))
+            .filter(|node| !node.by_value)
+            .ok_or_else(|| {
+                syn::Error::new_spanned(variant, "Expecting a variant that holds a single node")
+            })?;
+
+            Ok(NodeVariant { name: variant.ident.clone(), node: node.clone() })
+        })
+        .collect()
+}
+
+impl TreeDef {
+    /// Collect the definitions of the nodes from the given items, removing the
+    /// node attributes from them.
+    fn from_items(mut items: Vec<Item>) -> syn::Result<Self> {
+        // First, find all of the types that are nodes along with their names,
+        // which of the struct nodes have variants, and the renamed fields...
+        let mut node_items = vec![];
+        let mut nodes = HashMap::new();
+        let mut kind_fields = vec![];
+        let mut renamed_fields = HashMap::new();
+
+        for (index, item) in items.iter_mut().enumerate() {
+            let (name, attr) = match item {
+                Item::Struct(item) => (&item.ident, take_attr(&mut item.attrs, NODE_ATTR)),
+                Item::Enum(item) => (&item.ident, take_attr(&mut item.attrs, NODE_ATTR)),
+                _ => continue,
+            };
+
+            let is_node = match attr {
+                Some(attr) => {
+                    nodes.insert(name.to_string(), NodeNames::from_attr(name, &attr)?);
+                    node_items.push(index);
+                    true
+                }
+                None => false,
+            };
+
+            if let Item::Struct(item) = item {
+                for (position, field) in item.fields.iter_mut().enumerate() {
+                    let member = match &field.ident {
+                        Some(ident) => Member::Named(ident.clone()),
+                        None => Member::Unnamed(Index::from(position)),
+                    };
+
+                    let is_kind = take_attr(&mut field.attrs, NODE_KIND_ATTR).is_some();
+                    let rename = take_attr(&mut field.attrs, NODE_FIELD_ATTR);
+
+                    if (is_kind || rename.is_some()) && !is_node {
+                        return Err(syn::Error::new_spanned(
+                            field,
+                            "`#[node_kind]` and `#[node_field]` can only be used within a node",
+                        ));
+                    }
+
+                    if is_kind {
+                        kind_fields.push((index, member.clone(), field.ty.clone()));
+                    }
+
+                    if let Some(attr) = rename {
+                        let AttrArgs { values, flags } = attr_args(&attr)?;
+
+                        if let Some(flag) = flags.first() {
+                            return Err(syn::Error::new_spanned(flag, "Unknown field flag"));
+                        }
+
+                        for (key, value) in values {
+                            if key != "name" {
+                                return Err(syn::Error::new_spanned(key, "Unknown field name"));
+                            }
+
+                            renamed_fields.insert((index, member.clone()), Member::Named(value));
+                        }
+                    }
+                }
+            }
+        }
+
+        let find_enum = |ty: &Type| {
+            let name = type_name(ty)?;
+            items.iter().find_map(|item| match item {
+                Item::Enum(item) if &item.ident == name => Some(item),
+                _ => None,
+            })
+        };
+
+        // Now, work out the shape of each node...
+        let mut defs = vec![];
+
+        for &index in &node_items {
+            let (name, shape) = match &items[index] {
+                Item::Struct(item) => {
+                    let shape = match kind_fields.iter().find(|(node, _, _)| *node == index) {
+                        Some((_, member, ty)) => {
+                            let kind = find_enum(ty).ok_or_else(|| {
+                                syn::Error::new_spanned(
+                                    ty,
+                                    "Expecting an enum that is defined within the tree",
+                                )
+                            })?;
+
+                            NodeShape::Variants {
+                                enum_name: kind.ident.clone(),
+                                field: Some(member.clone()),
+                                variants: node_variants(kind, &nodes)?,
+                            }
+                        }
+                        None => {
+                            let mut fields = vec![];
+
+                            for (position, field) in item.fields.iter().enumerate() {
+                                let (kind, node) = match child_of(&field.ty, &nodes) {
+                                    Some(child) => child,
+                                    None => continue,
+                                };
+
+                                let member = match &field.ident {
+                                    Some(ident) => Member::Named(ident.clone()),
+                                    None => Member::Unnamed(Index::from(position)),
+                                };
+                                let walk_member = renamed_fields
+                                    .remove(&(index, member.clone()))
+                                    .unwrap_or_else(|| member.clone());
+
+                                let node = node_names(node, &nodes)?;
+                                if node.by_value && kind != ChildKind::Value {
+                                    return Err(syn::Error::new_spanned(
+                                        &field.ty,
+                                        "A node that is visited by value can't be in an `AstNode`",
+                                    ));
+                                }
+
+                                fields.push(ChildField {
+                                    member,
+                                    walk_member,
+                                    kind,
+                                    node: node.clone(),
+                                });
+                            }
+
+                            if fields.is_empty() {
+                                NodeShape::Leaf
+                            } else {
+                                let is_tuple = matches!(item.fields, Fields::Unnamed(_));
+                                let has_other_fields = fields.len() != item.fields.len();
+                                NodeShape::Struct { fields, is_tuple, has_other_fields }
+                            }
+                        }
+                    };
+
+                    (&item.ident, shape)
+                }
+                Item::Enum(item) => {
+                    let shape = if item.variants.iter().all(|variant| variant.fields.is_empty()) {
+                        NodeShape::Leaf
+                    } else {
+                        NodeShape::Variants {
+                            enum_name: item.ident.clone(),
+                            field: None,
+                            variants: node_variants(item, &nodes)?,
+                        }
+                    };
+
+                    (&item.ident, shape)
+                }
+                _ => unreachable!(),
+            };
+
+            let names = nodes[&name.to_string()].clone();
+
+            if names.by_value && !matches!(shape, NodeShape::Leaf) {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "Only a node without any children can be visited by value",
+                ));
+            }
+
+            defs.push(NodeDef { name: name.clone(), names, shape });
+        }
+
+        // Any renamed field that is left over doesn't contain a child
+        if let Some((_, member)) = renamed_fields.into_iter().next() {
+            return Err(syn::Error::new_spanned(
+                member,
+                "`#[node_field]` can only be used on a field that contains a child",
+            ));
+        }
+
+        Ok(TreeDef { items, nodes: defs })
+    }
+}
+
+impl Parse for TreeDef {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut items = vec![];
+
+        while !input.is_empty() {
+            items.push(input.parse()?);
+        }
+
+        Self::from_items(items)
+    }
+}
@@ -0,0 +1,516 @@
+//! Generating the visitor traits and the `walk` modules from the definitions
+//! of the nodes within a tree.
+
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::Ident;
+
+use crate::definitions::{
+    ChildField, ChildKind, NodeDef, NodeNames, NodeShape, NodeVariant, TreeDef,
+};
+
+/// The names that differ between the immutable and the mutable visitors.
+struct VisitorKind {
+    /// The name of the visitor trait.
+    visitor: Ident,
+    /// The name of the module with the walk functions.
+    walk: Ident,
+    /// The type of the reference to a node that is given to the visitor.
+    node_ref: Ident,
+    /// Whether the visitor is mutable.
+    mutable: bool,
+}
+
+impl VisitorKind {
+    fn immutable() -> Self {
+        Self {
+            visitor: format_ident!("AstVisitor"),
+            walk: format_ident!("walk"),
+            node_ref: format_ident!("AstNodeRef"),
+            mutable: false,
+        }
+    }
+
+    fn mutable() -> Self {
+        Self {
+            visitor: format_ident!("AstVisitorMut"),
+            walk: format_ident!("walk_mut"),
+            node_ref: format_ident!("AstNodeRefMut"),
+            mutable: true,
+        }
+    }
+}
+
+/// The name of the method that visits the given node, e.g. `visit_fn_def`.
+fn visit_fn_name(node: &NodeNames) -> Ident {
+    format_ident!("visit_{}", node.method)
+}
+
+/// The name of the method that folds the given node, e.g. `fold_fn_def`.
+fn fold_fn_name(node: &NodeNames) -> Ident {
+    format_ident!("fold_{}", node.method)
+}
+
+/// The name of the function that walks the given node, e.g. `walk_fn_def`.
+fn walk_fn_name(node: &NodeNames) -> Ident {
+    format_ident!("walk_{}", node.walk.to_string().to_case(Case::Snake))
+}
+
+/// The name of the function that walks the given node and returns the inner
+/// type of each variant, e.g. `walk_expr_same_children`.
+fn walk_same_children_fn_name(node: &NodeNames) -> Ident {
+    format_ident!("walk_{}_same_children", node.walk.to_string().to_case(Case::Snake))
+}
+
+/// Generate the visitor trait, with a method for each node.
+fn emit_visitor_trait(nodes: &[NodeDef], kind: &VisitorKind) -> TokenStream {
+    let VisitorKind { visitor, walk, node_ref, .. } = kind;
+
+    let doc = if kind.mutable {
+        format!(
+            "The mutable visitor trait for [crate::ast] nodes.\n\n\
+            This is the same as [AstVisitor], but each node is visited through a mutable \
+            reference, which allows the visitor to modify the node. These can be implemented \
+            using the functions defined in [{}] that can traverse the children of each node.",
+            walk
+        )
+    } else {
+        format!(
+            "The main visitor trait for [crate::ast] nodes.\n\n\
+            This contains a method for each AST structure, as well as a dedicated return type \
+            for it. These can be implemented using the functions defined in [{}] that can \
+            traverse the children of each node.",
+            walk
+        )
+    };
+
+    let methods = nodes.iter().map(|node| {
+        let name = &node.name;
+        let ret = &node.names.ret;
+        let visit = visit_fn_name(&node.names);
+        let node_ty = if node.names.by_value { quote!(#name) } else { quote!(#node_ref<#name>) };
+
+        quote! {
+            type #ret;
+            fn #visit(
+                &mut self,
+                ctx: &Self::Ctx,
+                node: #node_ty,
+            ) -> Result<Self::#ret, Self::Error>;
+        }
+    });
+
+    quote! {
+        #[doc = #doc]
+        pub trait #visitor: Sized {
+            /// Context type immutably passed to each visitor method for separating
+            /// mutable from immutable context.
+            type Ctx;
+
+            /// What container to use to collect multiple children, used by the walk
+            /// functions.
+            type CollectionContainer<T>: Sized;
+
+            /// Try collect an iterator of results into a container specified by
+            /// [Self::CollectionContainer].
+            fn try_collect_items<T, E, I: Iterator<Item = Result<T, E>>>(
+                ctx: &Self::Ctx,
+                items: I,
+            ) -> Result<Self::CollectionContainer<T>, E>;
+
+            /// Collect an iterator of items into a container specified by
+            /// [Self::CollectionContainer].
+            fn collect_items<T, E, I: Iterator<Item = T>>(
+                ctx: &Self::Ctx,
+                items: I,
+            ) -> Self::CollectionContainer<T> {
+                Self::try_collect_items::<T, ::std::convert::Infallible, _>(
+                    ctx,
+                    items.map(|item| Ok(item)),
+                )
+                .unwrap()
+            }
+
+            /// The error type to use for each visit method.
+            type Error;
+
+            #(#methods)*
+        }
+    }
+}
+
+/// Generate the walk structure and function for a struct node.
+fn emit_walk_struct(
+    node: &NodeDef,
+    fields: &[ChildField],
+    is_tuple: bool,
+    kind: &VisitorKind,
+) -> TokenStream {
+    let VisitorKind { visitor, node_ref, .. } = kind;
+    let name = &node.name;
+    let walk_name = &node.names.walk;
+    let walk = walk_fn_name(&node.names);
+
+    // The body is accessed explicitly, since the fields of the node reference
+    // may be visible and have the same name as a field of the node.
+    let (body, ast_ref, as_ref, iter) = if kind.mutable {
+        (quote!(body_mut), quote!(ast_ref_mut), quote!(as_mut), quote!(iter_mut))
+    } else {
+        (quote!(body), quote!(ast_ref), quote!(as_ref), quote!(iter))
+    };
+
+    let field_tys = fields.iter().map(|field| {
+        let ret = &field.node.ret;
+
+        match field.kind {
+            ChildKind::One | ChildKind::Value => quote!(V::#ret),
+            ChildKind::Optional => quote!(Option<V::#ret>),
+            ChildKind::Many => quote!(V::CollectionContainer<V::#ret>),
+        }
+    });
+
+    let field_values = fields.iter().map(|field| {
+        let member = &field.member;
+        let visit = visit_fn_name(&field.node);
+
+        match field.kind {
+            ChildKind::Value => quote! {
+                visitor.#visit(ctx, node.#body().#member)?
+            },
+            ChildKind::One => quote! {
+                visitor.#visit(ctx, node.#body().#member.#ast_ref())?
+            },
+            ChildKind::Optional => quote! {
+                node.#body().#member.#as_ref().map(|t| visitor.#visit(ctx, t.#ast_ref())).transpose()?
+            },
+            ChildKind::Many => quote! {
+                V::try_collect_items(
+                    ctx,
+                    node.#body().#member.#iter().map(|t| visitor.#visit(ctx, t.#ast_ref())),
+                )?
+            },
+        }
+    });
+
+    let (definition, value) = if is_tuple {
+        (
+            quote!(pub struct #walk_name<V: #visitor>(#(pub #field_tys),*);),
+            quote!(#walk_name(#(#field_values),*)),
+        )
+    } else {
+        let members: Vec<_> = fields.iter().map(|field| &field.walk_member).collect();
+
+        (
+            quote!(pub struct #walk_name<V: #visitor> { #(pub #members: #field_tys),* }),
+            quote!(#walk_name { #(#members: #field_values),* }),
+        )
+    };
+
+    let node_binding = if kind.mutable { quote!(mut node) } else { quote!(node) };
+
+    quote! {
+        #definition
+
+        pub fn #walk<V: #visitor>(
+            visitor: &mut V,
+            ctx: &V::Ctx,
+            #node_binding: #node_ref<super::#name>,
+        ) -> Result<#walk_name<V>, V::Error> {
+            Ok(#value)
+        }
+    }
+}
+
+/// Generate the walk enum and functions for a node with variants.
+fn emit_walk_variants(
+    node: &NodeDef,
+    enum_name: &Ident,
+    field: Option<&syn::Member>,
+    variants: &[NodeVariant],
+    kind: &VisitorKind,
+) -> TokenStream {
+    let VisitorKind { visitor, node_ref, .. } = kind;
+    let name = &node.name;
+    let walk_name = &node.names.walk;
+    let walk = walk_fn_name(&node.names);
+    let walk_same_children = walk_same_children_fn_name(&node.names);
+
+    let variant_names: Vec<_> = variants.iter().map(|variant| &variant.name).collect();
+    let variant_rets: Vec<_> = variants.iter().map(|variant| &variant.node.ret).collect();
+    let variant_visits = variants.iter().map(|variant| visit_fn_name(&variant.node));
+
+    // Several variants can hold the same node, but each return type can only
+    // be constrained once.
+    let mut same_rets = vec![];
+    for ret in &variant_rets {
+        if !same_rets.contains(ret) {
+            same_rets.push(*ret);
+        }
+    }
+
+    // The inner node of each variant shares the span and the id of this node.
+    let (preamble, node_binding, subject, inner_ref) = if kind.mutable {
+        let body = match field {
+            Some(field) => quote!(&mut node.body_mut().#field),
+            None => quote!(node.body_mut()),
+        };
+
+        (
+            quote!(let (span, id) = (node.span(), node.id());),
+            quote!(mut node),
+            body,
+            quote!(#node_ref::new(inner, span, id)),
+        )
+    } else {
+        let body = match field {
+            Some(field) => quote!(&node.body().#field),
+            None => quote!(node.body()),
+        };
+
+        (quote!(), quote!(node), body, quote!(node.with_body(inner)))
+    };
+
+    quote! {
+        pub enum #walk_name<V: #visitor> {
+            #(#variant_names(V::#variant_rets)),*
+        }
+
+        pub fn #walk<V: #visitor>(
+            visitor: &mut V,
+            ctx: &V::Ctx,
+            #node_binding: #node_ref<super::#name>,
+        ) -> Result<#walk_name<V>, V::Error> {
+            #preamble
+
+            Ok(match #subject {
+                #(
+                    super::#enum_name::#variant_names(inner) => {
+                        #walk_name::#variant_names(visitor.#variant_visits(ctx, #inner_ref)?)
+                    }
+                )*
+            })
+        }
+
+        pub fn #walk_same_children<V, Ret>(
+            visitor: &mut V,
+            ctx: &V::Ctx,
+            node: #node_ref<super::#name>,
+        ) -> Result<Ret, V::Error>
+        where
+            V: #visitor<#(#same_rets = Ret),*>,
+        {
+            Ok(match #walk(visitor, ctx, node)? {
+                #(#walk_name::#variant_names(r) => r),*
+            })
+        }
+    }
+}
+
+/// Generate the module with the walk structures and functions of each node.
+fn emit_walk_module(nodes: &[NodeDef], kind: &VisitorKind) -> TokenStream {
+    let VisitorKind { visitor, walk, node_ref, .. } = kind;
+
+    let doc = format!(
+        "Contains helper functions and structures to traverse AST nodes using a given visitor.\n\n\
+        Structures are defined which mirror the layout of the AST nodes, but instead of having \
+        AST nodes as children, they have the [{}] output type for each node.\n\n\
+        For enums, there is an additional `*_same_children` function, which traverses the \
+        member of each variant and returns the inner type, given that all variants have the \
+        same declared type within the visitor.",
+        visitor
+    );
+
+    let walks = nodes.iter().map(|node| match &node.shape {
+        NodeShape::Leaf => quote!(),
+        NodeShape::Struct { fields, is_tuple, .. } => {
+            emit_walk_struct(node, fields, *is_tuple, kind)
+        }
+        NodeShape::Variants { enum_name, field, variants } => {
+            emit_walk_variants(node, enum_name, field.as_ref(), variants, kind)
+        }
+    });
+
+    quote! {
+        #[doc = #doc]
+        pub mod #walk {
+            use super::{#node_ref, #visitor};
+
+            #(#walks)*
+        }
+    }
+}
+
+/// Generate the fold function for a struct node.
+fn emit_fold_struct(
+    node: &NodeDef,
+    fields: &[ChildField],
+    is_tuple: bool,
+    has_other_fields: bool,
+) -> TokenStream {
+    let name = &node.name;
+    let walk = walk_fn_name(&node.names);
+
+    // Children that are visited by value aren't folded, so they are kept
+    // along with the other fields.
+    let folded: Vec<_> = fields.iter().filter(|field| field.kind != ChildKind::Value).collect();
+    let keeps_fields = has_other_fields || folded.len() != fields.len();
+
+    let members: Vec<_> = folded.iter().map(|field| &field.member).collect();
+    let values = folded.iter().map(|field| {
+        let member = &field.member;
+        let fold = fold_fn_name(&field.node);
+
+        match field.kind {
+            ChildKind::One => quote!(folder.#fold(node.#member)),
+            ChildKind::Optional => quote!(node.#member.map(|t| folder.#fold(t))),
+            ChildKind::Many => quote!(walk_nodes(node.#member, |t| folder.#fold(t))),
+            ChildKind::Value => unreachable!(),
+        }
+    });
+
+    let value = if keeps_fields {
+        quote!(super::#name { #(#members: #values,)* ..node })
+    } else if is_tuple {
+        quote!(super::#name(#(#values),*))
+    } else {
+        quote!(super::#name { #(#members: #values),* })
+    };
+
+    quote! {
+        pub fn #walk<F: AstFolder>(
+            folder: &mut F,
+            node: AstNode<super::#name>,
+        ) -> AstNode<super::#name> {
+            node.map(|node| #value)
+        }
+    }
+}
+
+/// Generate the fold function for a node with variants.
+fn emit_fold_variants(
+    node: &NodeDef,
+    enum_name: &Ident,
+    field: Option<&syn::Member>,
+    variants: &[NodeVariant],
+) -> TokenStream {
+    let name = &node.name;
+    let walk = walk_fn_name(&node.names);
+
+    let variant_names = variants.iter().map(|variant| &variant.name);
+    let variant_folds = variants.iter().map(|variant| fold_fn_name(&variant.node));
+
+    // The inner node of each variant shares the span and the id of this node.
+    let subject = match field {
+        Some(field) => quote!(node.#field),
+        None => quote!(node),
+    };
+    let folded = quote! {
+        match #subject {
+            #(
+                super::#enum_name::#variant_names(inner) => super::#enum_name::#variant_names(
+                    folder.#variant_folds(AstNode::with_id(inner, span, id)).into_body(),
+                ),
+            )*
+        }
+    };
+
+    let map = match field {
+        Some(field) => quote! {
+            node.map(|mut node| {
+                node.#field = #folded;
+                node
+            })
+        },
+        None => quote!(node.map(|node| #folded)),
+    };
+
+    quote! {
+        pub fn #walk<F: AstFolder>(
+            folder: &mut F,
+            node: AstNode<super::#name>,
+        ) -> AstNode<super::#name> {
+            let (span, id) = (node.span(), node.id());
+            #map
+        }
+    }
+}
+
+/// Generate the fold trait, with a method for each node that isn't visited
+/// by value, along with the module with the fold functions of each node.
+fn emit_folder(nodes: &[NodeDef]) -> TokenStream {
+    let nodes: Vec<_> = nodes.iter().filter(|node| !node.names.by_value).collect();
+
+    let methods = nodes.iter().map(|node| {
+        let name = &node.name;
+        let fold = fold_fn_name(&node.names);
+
+        let body = match node.shape {
+            NodeShape::Leaf => quote!(node),
+            _ => {
+                let walk = walk_fn_name(&node.names);
+                quote!(walk_fold::#walk(self, node))
+            }
+        };
+
+        quote! {
+            fn #fold(&mut self, node: AstNode<#name>) -> AstNode<#name> {
+                #body
+            }
+        }
+    });
+
+    let walks = nodes.iter().map(|node| match &node.shape {
+        NodeShape::Leaf => quote!(),
+        NodeShape::Struct { fields, is_tuple, has_other_fields } => {
+            emit_fold_struct(node, fields, *is_tuple, *has_other_fields)
+        }
+        NodeShape::Variants { enum_name, field, variants } => {
+            emit_fold_variants(node, enum_name, field.as_ref(), variants)
+        }
+    });
+
+    quote! {
+        /// The fold trait for [crate::ast] nodes.
+        ///
+        /// This contains a method for each node, which maps the node into a new
+        /// node of the same type. By default, each method folds the children of
+        /// the node using the functions defined in [walk_fold], which keep the
+        /// span and the id of the node. Nodes that have variants fold the node
+        /// within each variant using the method of that node, with the span and
+        /// the id of the enclosing node.
+        pub trait AstFolder: Sized {
+            #(#methods)*
+        }
+
+        /// Contains the functions that fold the children of each node using a
+        /// given [AstFolder], keeping the span and the id of the node.
+        pub mod walk_fold {
+            use super::{AstFolder, AstNode, AstNodes};
+
+            /// Fold each of the given nodes, keeping the span of the nodes.
+            pub fn walk_nodes<T>(
+                nodes: AstNodes<T>,
+                f: impl FnMut(AstNode<T>) -> AstNode<T>,
+            ) -> AstNodes<T> {
+                AstNodes::new(nodes.nodes.into_iter().map(f).collect(), nodes.span)
+            }
+
+            #(#walks)*
+        }
+    }
+}
+
+/// Generate the immutable and the mutable visitor traits, along with their
+/// walk modules, and the fold trait along with its module.
+pub(crate) fn emit_visitors(tree: &TreeDef) -> TokenStream {
+    let mut visitors = TokenStream::new();
+
+    for kind in [VisitorKind::immutable(), VisitorKind::mutable()] {
+        visitors.extend(emit_visitor_trait(&tree.nodes, &kind));
+        visitors.extend(emit_walk_module(&tree.nodes, &kind));
+    }
+
+    visitors.extend(emit_folder(&tree.nodes));
+    visitors
+}
@@ -0,0 +1,77 @@
+//! Hash visitor utilities for deriving the AST visitors from the definitions
+//! of the AST. This file primarily has the `define_tree` macro, which takes
+//! the type definitions of a tree, and generates the visitor traits along with
+//! the `walk` functions that traverse the children of each node, and the fold
+//! trait along with the functions that fold the children of each node.
+//!
+//! The types that are nodes within the tree are marked with a `#[node]`
+//! attribute. The children of a node are the fields that are wrapped in an
+//! `AstNode`, `Option<AstNode>` or `AstNodes`. An enum node has another node
+//! within each of its variants, which shares the span and the id of the
+//! enclosing node. A struct node can be given variants in the same way by
+//! marking one of its fields, with a type of such an enum, as `#[node_kind]`.
+//! Nodes that don't have any children are leaves, and have no `walk` function.
+//!
+//! For example:
+//!
+//! ```ignore
+//! define_tree! {
+//!     #[node]
+//!     pub struct Name {
+//!         pub ident: Identifier,
+//!     }
+//!
+//!     #[node]
+//!     pub struct Param {
+//!         pub name: AstNode<Name>,
+//!         pub ty: Option<AstNode<Ty>>,
+//!     }
+//! }
+//! ```
+//!
+//! This defines the types with the attributes removed, and generates the
+//! `visit_name` and `visit_param` methods of `AstVisitor` and `AstVisitorMut`,
+//! along with the `walk::walk_param` and `walk_mut::walk_param` functions.
+//! Likewise, it generates the `fold_name` and `fold_param` methods of
+//! `AstFolder`, along with the `walk_fold::walk_param` function.
+//!
+//! The names that are derived from a node can be changed with the arguments
+//! of its attribute:
+//!
+//! - `#[node(visit = "param_def")]` names the methods `visit_param_def` and
+//!   `fold_param_def`.
+//! - `#[node(ret = "ParamDefRet")]` names the return type of the visitors.
+//! - `#[node(walk = "ParamDef")]` names the walk structure, and the walk
+//!   functions `walk_param_def`.
+//! - `#[node(by_value)]` visits a leaf node by value rather than by a node
+//!   reference. Such a node is a child when it is stored without an `AstNode`,
+//!   and isn't folded.
+//!
+//! The member of a walk structure for a field can be renamed by marking the
+//! field with `#[node_field(name = "...")]`.
+
+mod definitions;
+mod emit;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::parse_macro_input;
+
+use crate::definitions::TreeDef;
+
+/// Define the types of a tree, and generate the visitors for the nodes within
+/// it. See the crate documentation for the form of the definitions.
+#[proc_macro]
+pub fn define_tree(input: TokenStream) -> TokenStream {
+    let tree = parse_macro_input!(input as TreeDef);
+
+    let items = &tree.items;
+    let visitors = emit::emit_visitors(&tree);
+
+    quote! {
+        #(#items)*
+
+        #visitors
+    }
+    .into()
+}
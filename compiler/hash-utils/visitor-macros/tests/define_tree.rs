@@ -0,0 +1,220 @@
+//! Tests for the visitors and the folder that are generated by `define_tree`,
+//! on a small tree of expressions.
+#![feature(generic_associated_types)]
+
+use std::convert::Infallible;
+
+use hash_source::location::Span;
+use tree::*;
+
+mod tree {
+    pub use hash_ast::ast::{AstNode, AstNodeRef, AstNodeRefMut, AstNodes};
+    use hash_utils_visitor_macros::define_tree;
+
+    define_tree! {
+        #[node]
+        pub struct Name {
+            pub ident: &'static str,
+        }
+
+        #[node]
+        pub struct Lit(pub i32);
+
+        #[node(by_value)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Sign {
+            Pos,
+            Neg,
+        }
+
+        #[node(visit = "variable", ret = "VariableRet", walk = "Variable")]
+        pub struct Var {
+            pub name: AstNode<Name>,
+        }
+
+        #[node]
+        pub struct Unary {
+            pub sign: Sign,
+            pub operand: AstNode<Expr>,
+        }
+
+        #[node]
+        pub struct Call {
+            pub subject: AstNode<Expr>,
+            #[node_field(name = "arguments")]
+            pub args: AstNodes<Expr>,
+            pub label: Option<AstNode<Name>>,
+        }
+
+        #[node]
+        pub enum Expr {
+            Lit(Lit),
+            Var(Var),
+            Unary(Unary),
+            Call(Call),
+        }
+    }
+}
+
+fn node<T>(body: T) -> AstNode<T> {
+    AstNode::new(body, Span::new(0, 0))
+}
+
+fn var(ident: &'static str) -> AstNode<Expr> {
+    node(Expr::Var(Var { name: node(Name { ident }) }))
+}
+
+/// The call `f(+x, -1)`, with a label.
+fn call() -> AstNode<Expr> {
+    let args = vec![
+        node(Expr::Unary(Unary { sign: Sign::Pos, operand: var("x") })),
+        node(Expr::Unary(Unary { sign: Sign::Neg, operand: node(Expr::Lit(Lit(1))) })),
+    ];
+
+    node(Expr::Call(Call {
+        subject: var("f"),
+        args: AstNodes::new(args, None),
+        label: Some(node(Name { ident: "label" })),
+    }))
+}
+
+/// A visitor which records each node when it is entered, before walking its
+/// children.
+#[derive(Default)]
+struct Recorder {
+    visited: Vec<String>,
+}
+
+impl AstVisitor for Recorder {
+    type Ctx = ();
+
+    type CollectionContainer<T> = Vec<T>;
+
+    fn try_collect_items<T, E, I: Iterator<Item = Result<T, E>>>(
+        _: &Self::Ctx,
+        items: I,
+    ) -> Result<Self::CollectionContainer<T>, E> {
+        items.collect()
+    }
+
+    type Error = Infallible;
+
+    type NameRet = ();
+    fn visit_name(&mut self, _: &Self::Ctx, node: AstNodeRef<Name>) -> Result<(), Infallible> {
+        self.visited.push(format!("name {}", node.ident));
+        Ok(())
+    }
+
+    type LitRet = ();
+    fn visit_lit(&mut self, _: &Self::Ctx, node: AstNodeRef<Lit>) -> Result<(), Infallible> {
+        self.visited.push(format!("lit {}", node.0));
+        Ok(())
+    }
+
+    type SignRet = ();
+    fn visit_sign(&mut self, _: &Self::Ctx, node: Sign) -> Result<(), Infallible> {
+        self.visited.push(format!("sign {:?}", node));
+        Ok(())
+    }
+
+    type VariableRet = ();
+    fn visit_variable(&mut self, ctx: &Self::Ctx, node: AstNodeRef<Var>) -> Result<(), Infallible> {
+        self.visited.push("variable".to_string());
+        let walk::Variable { name: () } = walk::walk_variable(self, ctx, node)?;
+        Ok(())
+    }
+
+    type UnaryRet = ();
+    fn visit_unary(&mut self, ctx: &Self::Ctx, node: AstNodeRef<Unary>) -> Result<(), Infallible> {
+        self.visited.push("unary".to_string());
+        walk::walk_unary(self, ctx, node)?;
+        Ok(())
+    }
+
+    type CallRet = usize;
+    fn visit_call(&mut self, ctx: &Self::Ctx, node: AstNodeRef<Call>) -> Result<usize, Infallible> {
+        self.visited.push("call".to_string());
+        let walk::Call { arguments, .. } = walk::walk_call(self, ctx, node)?;
+        Ok(arguments.len())
+    }
+
+    type ExprRet = ();
+    fn visit_expr(&mut self, ctx: &Self::Ctx, node: AstNodeRef<Expr>) -> Result<(), Infallible> {
+        self.visited.push("expr".to_string());
+        walk::walk_expr(self, ctx, node)?;
+        Ok(())
+    }
+}
+
+#[test]
+fn walk_children_in_field_order() {
+    let expr = call();
+    let mut recorder = Recorder::default();
+    recorder.visit_expr(&(), expr.ast_ref()).unwrap();
+
+    assert_eq!(
+        recorder.visited,
+        [
+            "expr",
+            "call",
+            "expr",
+            "variable",
+            "name f",
+            "expr",
+            "unary",
+            "sign Pos",
+            "expr",
+            "variable",
+            "name x",
+            "expr",
+            "unary",
+            "sign Neg",
+            "expr",
+            "lit 1",
+            "name label"
+        ]
+    );
+}
+
+#[test]
+fn walk_renamed_fields() {
+    let expr = call();
+    let call = match expr.body() {
+        Expr::Call(call) => expr.with_body(call),
+        _ => unreachable!(),
+    };
+
+    assert_eq!(Recorder::default().visit_call(&(), call).unwrap(), 2);
+}
+
+/// A folder which negates each literal.
+struct Negate;
+
+impl AstFolder for Negate {
+    fn fold_lit(&mut self, node: AstNode<Lit>) -> AstNode<Lit> {
+        node.map(|Lit(value)| Lit(-value))
+    }
+}
+
+#[test]
+fn fold_keeps_nodes() {
+    let expr = call();
+    let (span, id) = (expr.span(), expr.id());
+    let folded = Negate.fold_expr(expr);
+
+    // The variant keeps the node of the expression, as well as the fields
+    // which aren't folded
+    assert_eq!((folded.span(), folded.id()), (span, id));
+
+    let args = match folded.body() {
+        Expr::Call(call) => &call.args,
+        _ => unreachable!(),
+    };
+
+    match args[1].body() {
+        Expr::Unary(Unary { sign: Sign::Neg, operand }) => {
+            assert!(matches!(operand.body(), Expr::Lit(Lit(-1))));
+        }
+        _ => unreachable!(),
+    }
+}
@@ -1,6 +1,8 @@
 //! Hash Compiler arguments management.
 
-use clap::Parser as ClapParser;
+use std::path::PathBuf;
+
+use clap::{ArgEnum, Parser as ClapParser};
 
 /// CompilerOptions is a structural representation of what arguments the
 /// compiler can take when running. Compiler options are well documented on the
@@ -30,6 +32,11 @@ pub(crate) struct CompilerOptions {
     #[clap(short, long, default_value = Box::leak(num_cpus::get().to_string().into_boxed_str()))]
     pub(crate) worker_count: usize,
 
+    /// Add a directory to the list of paths that are searched for imported
+    /// modules, the directories are searched in the order that they are given
+    #[clap(short = 'I', long = "include", value_name = "DIR", multiple_occurrences = true)]
+    pub(crate) include: Vec<PathBuf>,
+
     /// Compiler mode
     #[clap(subcommand)]
     pub(crate) mode: Option<SubCmd>,
@@ -41,6 +48,8 @@ pub(crate) enum SubCmd {
     DeSugar(DeSugarMode),
     Check(CheckMode),
     IrGen(IrGenMode),
+    Tokens(TokensMode),
+    Fmt(FmtMode),
 }
 
 /// Desugar from given input file
@@ -51,12 +60,25 @@ pub(crate) struct DeSugarMode {
     pub(crate) filename: String,
 }
 
+/// The format that the generated AST is printed in.
+#[derive(ArgEnum, Clone, Copy)]
+pub(crate) enum AstGenFormat {
+    /// A rendered tree of the AST.
+    Tree,
+    /// The JSON representation of the AST, including spans and node ids.
+    Json,
+}
+
 /// Generate AST from given input file
 #[derive(ClapParser)]
 pub(crate) struct AstGenMode {
     /// Input filename of the module
     #[clap(required = true)]
     pub(crate) filename: String,
+
+    /// The format that the AST is printed in
+    #[clap(long, arg_enum, default_value = "tree")]
+    pub(crate) format: AstGenFormat,
 }
 
 /// Generate IR from the given input file
@@ -74,3 +96,41 @@ pub(crate) struct CheckMode {
     #[clap(required = true)]
     pub(crate) filename: String,
 }
+
+/// The format that the token stream is printed in.
+#[derive(ArgEnum, Clone, Copy)]
+pub(crate) enum TokensFormat {
+    /// A human readable listing of the tokens, one token per line.
+    Human,
+    /// A JSON array of the tokens, including their spans.
+    Json,
+}
+
+/// Print the token stream of the provided module
+#[derive(ClapParser)]
+pub(crate) struct TokensMode {
+    /// Input filename of the module
+    #[clap(required = true)]
+    pub(crate) filename: String,
+
+    /// The format that the tokens are printed in
+    #[clap(long, arg_enum, default_value = "human")]
+    pub(crate) format: TokensFormat,
+
+    /// Include comments and whitespace in the output
+    #[clap(long)]
+    pub(crate) trivia: bool,
+}
+
+/// Format the provided module
+#[derive(ClapParser)]
+pub(crate) struct FmtMode {
+    /// Input filename of the module
+    #[clap(required = true)]
+    pub(crate) filename: String,
+
+    /// Don't write the formatted module, and exit with a failure if the module
+    /// isn't formatted
+    #[clap(long)]
+    pub(crate) check: bool,
+}
@@ -0,0 +1,169 @@
+//! Hash Compiler source formatting, this is used by the `fmt` sub-command in
+//! order to re-print a module with the canonical layout of the
+//! [AstPrinter]. The layout can be configured with a `hashfmt.toml` file that
+//! is located in the directory of the module or in any of its ancestors, for
+//! example:
+//!
+//! ```toml
+//! max_width = 80
+//! tab_spaces = 2
+//! trailing_comma = "Never"
+//! ```
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::exit,
+};
+
+use hash_ast::{
+    ast::OwnsAstNode,
+    printer::{AstPrinter, PrinterConfig},
+};
+use hash_lexer::comment_spans;
+use hash_parser::HashParser;
+use hash_pipeline::{
+    fs::{read_in_path, resolve_path},
+    settings::CompilerSettings,
+    sources::{Module, Workspace},
+};
+use hash_reporting::{errors::CompilerError, report::Report, writer::ReportWriter};
+use hash_source::{ModuleKind, SourceId};
+use serde::Deserialize;
+
+use crate::{args::FmtMode, tokens::report_and_exit};
+
+/// The name of the file that contains the configuration of the formatter.
+pub(crate) static FORMATTER_CONFIG_FILE_NAME: &str = "hashfmt.toml";
+
+/// The contents of the formatter configuration file, any option that isn't
+/// specified keeps the default of [PrinterConfig].
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FormatterConfig {
+    max_width: Option<usize>,
+    tab_spaces: Option<usize>,
+    hard_tabs: Option<bool>,
+    trailing_comma: Option<String>,
+}
+
+/// Load the formatter configuration for the modules within the directory
+/// `dir`, by searching for a configuration file in `dir` and then in each of
+/// its ancestors. Returns the default configuration if there is none.
+fn load_config(dir: &Path) -> Result<PrinterConfig, CompilerError> {
+    let path = match dir
+        .ancestors()
+        .map(|ancestor| ancestor.join(FORMATTER_CONFIG_FILE_NAME))
+        .find(|path| path.is_file())
+    {
+        Some(path) => path,
+        None => return Ok(PrinterConfig::default()),
+    };
+
+    let error = |message: String| CompilerError::FormatterConfigError {
+        message: format!("Couldn't load the formatter config `{}`: {message}", path.display()),
+    };
+
+    let contents = fs::read_to_string(&path).map_err(|err| error(err.to_string()))?;
+    let FormatterConfig { max_width, tab_spaces, hard_tabs, trailing_comma } =
+        toml::from_str(&contents).map_err(|err| error(err.to_string()))?;
+
+    let default = PrinterConfig::default();
+
+    Ok(PrinterConfig {
+        max_width: max_width.unwrap_or(default.max_width),
+        tab_spaces: tab_spaces.unwrap_or(default.tab_spaces),
+        hard_tabs: hard_tabs.unwrap_or(default.hard_tabs),
+        trailing_comma: match trailing_comma {
+            Some(trailing_comma) => trailing_comma.parse().map_err(error)?,
+            None => default.trailing_comma,
+        },
+    })
+}
+
+/// Parse the module at `path` with the given `contents` into the
+/// [Workspace], and print it with the given configuration. The module is
+/// formatted only if it can be parsed, the imports of the module aren't
+/// resolved since only the module itself is printed. Any warnings are written
+/// to stderr, and don't stop the module from being formatted.
+fn format_module(
+    workspace: &mut Workspace,
+    path: PathBuf,
+    contents: &str,
+    config: PrinterConfig,
+) -> Result<String, Vec<Report>> {
+    let module_id =
+        workspace.add_module(contents.to_string(), Module::new(path), ModuleKind::Normal);
+    let source_id = SourceId::Module(module_id);
+
+    for warning in HashParser::new().parse_module_only(module_id, workspace)? {
+        eprintln!("{}", ReportWriter::new(warning, workspace.source_map()));
+    }
+
+    let node = workspace.node_map().get_module(module_id).node_ref();
+
+    Ok(AstPrinter::new(config)
+        .with_source(contents, comment_spans(contents, source_id))
+        .print_module(node))
+}
+
+/// Format the file that is specified by the [FmtMode]. The formatted module is
+/// written back to the file, unless the mode is only checking whether the
+/// file is formatted, in which case the process exits with a failure if the
+/// file would be changed by formatting.
+pub(crate) fn format_file(mode: &FmtMode) {
+    let mut workspace = Workspace::new();
+    let settings = CompilerSettings::default();
+
+    let path = resolve_path(&mode.filename, env::current_dir().unwrap(), &settings, None);
+    let contents = path.and_then(|path| Ok((read_in_path(&path)?, path)));
+
+    let (contents, path) = match contents {
+        Ok(result) => result,
+        Err(err) => report_and_exit(vec![err.create_report()], &workspace),
+    };
+
+    let config = path
+        .parent()
+        .map_or_else(|| Ok(PrinterConfig::default()), load_config)
+        .unwrap_or_else(|err| err.report_and_exit());
+
+    let formatted = format_module(&mut workspace, path.clone(), &contents, config)
+        .unwrap_or_else(|reports| report_and_exit(reports, &workspace));
+
+    if formatted == contents {
+        return;
+    }
+
+    if mode.check {
+        eprintln!("`{}` is not formatted", path.display());
+        exit(1);
+    }
+
+    fs::write(&path, formatted).unwrap_or_else(|err| CompilerError::from(err).report_and_exit());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_without_resolving_imports() {
+        let mut workspace = Workspace::new();
+        let path = std::env::temp_dir().join("hash-fmt-imports").join("main.hash");
+        let contents = "{ l } := import(\"missing\");\nl( 24,26 );\n";
+
+        let formatted =
+            format_module(&mut workspace, path, contents, PrinterConfig::default()).unwrap();
+        assert_eq!(formatted, "{ l } := import(\"missing\");\nl(24, 26);\n");
+    }
+
+    #[test]
+    fn format_with_warnings() {
+        let mut workspace = Workspace::new();
+        let path = std::env::temp_dir().join("main.hash");
+
+        let formatted =
+            format_module(&mut workspace, path, "p\u{430}ss   :=  1;", PrinterConfig::default());
+        assert_eq!(formatted.unwrap(), "p\u{430}ss := 1;\n");
+    }
+}
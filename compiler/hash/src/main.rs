@@ -3,14 +3,17 @@
 
 mod args;
 mod crash_handler;
+mod fmt;
 mod logger;
+mod package;
+mod tokens;
 
 use clap::Parser as ClapParser;
 use hash_ast_desugaring::AstDesugarer;
 use hash_ast_passes::HashSemanticAnalysis;
 use hash_parser::HashParser;
 use hash_pipeline::{
-    settings::{CompilerJobParams, CompilerMode, CompilerSettings},
+    settings::{AstFormat, CompilerJobParams, CompilerMode, CompilerSettings},
     Compiler,
 };
 use hash_reporting::errors::CompilerError;
@@ -19,11 +22,12 @@ use hash_typecheck::TcImpl;
 use hash_vm::vm::{Interpreter, InterpreterOptions};
 use log::LevelFilter;
 use logger::CompilerLogger;
-use std::{num::NonZeroUsize, panic};
+use std::{env, num::NonZeroUsize, panic};
 
 use crate::{
-    args::{AstGenMode, CheckMode, CompilerOptions, DeSugarMode, IrGenMode, SubCmd},
+    args::{AstGenFormat, AstGenMode, CheckMode, CompilerOptions, DeSugarMode, IrGenMode, SubCmd},
     crash_handler::panic_handler,
+    package::{find_entry_point, EntryPoint},
 };
 
 pub static CONSOLE_LOGGER: CompilerLogger = CompilerLogger;
@@ -56,16 +60,36 @@ fn main() {
         log::set_max_level(LevelFilter::Info);
     }
 
+    // Printing the token stream doesn't need the compiler pipeline
+    if let Some(SubCmd::Tokens(mode)) = &opts.mode {
+        tokens::print_tokens(mode);
+        return;
+    }
+
+    // Formatting only needs the parser
+    if let Some(SubCmd::Fmt(mode)) = &opts.mode {
+        fmt::format_file(mode);
+        return;
+    }
+
     // We want to figure out the entry point of the compiler by checking if the
-    // compiler has been specified to run in a specific mode.
-    let entry_point = match &opts.mode {
-        Some(SubCmd::AstGen(AstGenMode { filename })) => Some(filename.clone()),
+    // compiler has been specified to run in a specific mode. If no filename is
+    // given, the entry point of the package that the current directory is in
+    // is run instead of launching the interactive mode.
+    let filename = match &opts.mode {
+        Some(SubCmd::AstGen(AstGenMode { filename, .. })) => Some(filename.clone()),
         Some(SubCmd::DeSugar(DeSugarMode { filename })) => Some(filename.clone()),
         Some(SubCmd::IrGen(IrGenMode { filename })) => Some(filename.clone()),
         Some(SubCmd::Check(CheckMode { filename })) => Some(filename.clone()),
+        Some(SubCmd::Tokens(_) | SubCmd::Fmt(_)) => unreachable!(),
         None => opts.filename,
     };
 
+    let EntryPoint { path: entry_point, manifest } = env::current_dir()
+        .map_err(CompilerError::from)
+        .and_then(|dir| find_entry_point(filename, &dir))
+        .unwrap_or_else(|err| err.report_and_exit());
+
     // check that the job count is valid...
     let worker_count = NonZeroUsize::new(opts.worker_count)
         .unwrap_or_else(|| {
@@ -84,7 +108,9 @@ fn main() {
 
     // Create the vm
     let vm = Interpreter::new(InterpreterOptions::new(opts.stack_size));
-    let compiler_settings = CompilerSettings::new(opts.debug, worker_count);
+    let compiler_settings = CompilerSettings::new(opts.debug, worker_count)
+        .with_search_paths(opts.include)
+        .with_manifest(manifest);
 
     // We need at least 2 workers for the parsing loop in order so that the job
     // queue can run within a worker and any other jobs can run inside another
@@ -103,9 +129,15 @@ fn main() {
         match entry_point {
             Some(path) => {
                 let job_settings = match opts.mode {
-                    Some(SubCmd::AstGen { .. }) => {
+                    Some(SubCmd::AstGen(AstGenMode { format: AstGenFormat::Tree, .. })) => {
                         CompilerJobParams::new(CompilerMode::Parse, opts.debug)
                     }
+                    // The JSON is intended to be consumed by other tools, so it is
+                    // always printed
+                    Some(SubCmd::AstGen(AstGenMode { format: AstGenFormat::Json, .. })) => {
+                        CompilerJobParams::new(CompilerMode::Parse, true)
+                            .with_ast_format(AstFormat::Json)
+                    }
                     Some(SubCmd::DeSugar { .. }) => {
                         CompilerJobParams::new(CompilerMode::DeSugar, opts.debug)
                     }
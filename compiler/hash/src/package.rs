@@ -0,0 +1,110 @@
+//! Finding the module that the compiler runs on, and the manifest of the
+//! package that the module is in.
+use std::path::Path;
+
+use hash_pipeline::manifest::Manifest;
+use hash_reporting::errors::CompilerError;
+
+/// The module that the compiler runs on, and the manifest of the package
+/// that the module is in, if any.
+#[derive(Debug)]
+pub(crate) struct EntryPoint {
+    /// The path of the module, this is [None] if no filename is given and the
+    /// compiler isn't run within a package.
+    pub(crate) path: Option<String>,
+    /// The manifest of the package.
+    pub(crate) manifest: Option<Manifest>,
+}
+
+/// Find the [EntryPoint] of the compiler, relative to the directory `dir`.
+///
+/// If a `filename` is given, then the manifest is searched for from the
+/// directory of the file. The file can be run without a manifest, so a
+/// manifest that can't be loaded is only warned about. Otherwise, the
+/// manifest is searched for from `dir` and the entry point of the package
+/// is used, in which case the manifest must be valid.
+pub(crate) fn find_entry_point(
+    filename: Option<String>,
+    dir: &Path,
+) -> Result<EntryPoint, CompilerError> {
+    match filename {
+        Some(filename) => {
+            let path = dir.join(&filename);
+            let manifest = match Manifest::find(path.parent().unwrap_or(dir)) {
+                Ok(manifest) => manifest,
+                Err(err) => {
+                    log::warn!("{}, dependencies can't be imported by name", err);
+                    None
+                }
+            };
+
+            Ok(EntryPoint { path: Some(filename), manifest })
+        }
+        None => {
+            let manifest = Manifest::find(dir)
+                .map_err(|err| CompilerError::ManifestError { message: err.to_string() })?;
+            let path = manifest
+                .as_ref()
+                .map(|manifest| manifest.entry_point().to_string_lossy().into_owned());
+
+            Ok(EntryPoint { path, manifest })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::PathBuf,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use hash_pipeline::manifest::MANIFEST_FILE_NAME;
+
+    use super::*;
+
+    /// Create a fresh package directory with a manifest that has the given
+    /// `contents`, and a `src` directory within it.
+    fn package(contents: &str) -> PathBuf {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let id = COUNTER.fetch_add(1, Ordering::SeqCst);
+        let dir = std::env::temp_dir().join(format!("hash-package-{}-{id}", std::process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join(MANIFEST_FILE_NAME), contents).unwrap();
+
+        dir
+    }
+
+    #[test]
+    fn run_package_entry_point() {
+        let dir = package("[package]\nname = \"app\"\nentry = \"src/main.hash\"\n");
+
+        // Running without a filename anywhere within the package runs its entry point
+        let entry = find_entry_point(None, &dir.join("src")).unwrap();
+        assert_eq!(entry.path, Some(dir.join("src/main.hash").to_string_lossy().into_owned()));
+        assert_eq!(entry.manifest.unwrap().package.name, "app");
+
+        // The manifest applies to a given file within the package
+        let entry = find_entry_point(Some("src/other.hash".to_string()), &dir).unwrap();
+        assert_eq!(entry.path.as_deref(), Some("src/other.hash"));
+        assert!(entry.manifest.is_some());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn malformed_manifest() {
+        let dir = package("[package]\n");
+
+        // A given file is still run, but the package can't be
+        let entry = find_entry_point(Some("main.hash".to_string()), &dir).unwrap();
+        assert_eq!(entry.path.as_deref(), Some("main.hash"));
+        assert!(entry.manifest.is_none());
+
+        assert!(find_entry_point(None, &dir).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,372 @@
+//! Hash Compiler token stream printing, this is used by the `tokens`
+//! sub-command in order to inspect the output of the lexer.
+use std::{env, fmt::Write, process::exit};
+
+use hash_lexer::{
+    error::{LexerErrorWrapper, LexerWarningWrapper},
+    Lexer,
+};
+use hash_parser::parser::error::{create_lexer_warning_report, ParseError};
+use hash_pipeline::{
+    fs::{read_in_path, resolve_path},
+    settings::CompilerSettings,
+    sources::{Module, Workspace},
+};
+use hash_reporting::{report::Report, writer::ReportWriter};
+use hash_source::{ModuleKind, SourceId};
+use hash_token::{
+    trivia::{Trivia, TriviaMap},
+    Token, TokenKind,
+};
+use serde_json::{json, Value};
+
+use crate::args::{TokensFormat, TokensMode};
+
+/// Lex the file that is specified by the [TokensMode], and print the produced
+/// token stream in the specified [TokensFormat]. Any errors or warnings that
+/// are encountered by the lexer are reported after the token stream, the
+/// token stream is still printed since the lexer recovers from errors.
+pub(crate) fn print_tokens(mode: &TokensMode) {
+    let mut workspace = Workspace::new();
+
+    let path = resolve_path(
+        &mode.filename,
+        env::current_dir().unwrap(),
+        &CompilerSettings::default(),
+        None,
+    );
+    let contents = path.and_then(|path| Ok((read_in_path(&path)?, path)));
+
+    let (contents, path) = match contents {
+        Ok(result) => result,
+        Err(err) => report_and_exit(vec![err.create_report()], &workspace),
+    };
+
+    let module_id = workspace.add_module(contents.clone(), Module::new(path), ModuleKind::Normal);
+    let rendered = render_tokens(&contents, SourceId::Module(module_id), mode.format, mode.trivia);
+
+    print!("{}", rendered.output);
+
+    let failed = !rendered.errors.is_empty();
+
+    for report in rendered.errors.into_iter().chain(rendered.warnings) {
+        eprintln!("{}", ReportWriter::new(report, workspace.source_map()));
+    }
+
+    if failed {
+        exit(1);
+    }
+}
+
+/// A token stream that was rendered by [render_tokens], along with the
+/// reports of the lexer.
+struct RenderedTokens {
+    output: String,
+    errors: Vec<Report>,
+    warnings: Vec<Report>,
+}
+
+/// Lex the given contents and render the token stream in the specified
+/// [TokensFormat], the trivia of the tokens is only included if `with_trivia`
+/// is set.
+fn render_tokens(
+    contents: &str,
+    source_id: SourceId,
+    format: TokensFormat,
+    with_trivia: bool,
+) -> RenderedTokens {
+    let mut lexer = Lexer::new(contents, source_id);
+    if with_trivia {
+        lexer = lexer.with_trivia();
+    }
+
+    let (tokens, errors) = lexer.tokenise();
+    let warnings = lexer.take_warnings();
+    let eof_trivia = lexer.eof_trivia().to_vec();
+    let trivia = lexer.take_trivia();
+    let trees = lexer.into_token_trees();
+
+    let printer = TokenPrinter { trees: &trees, trivia: &trivia };
+    let mut output = String::new();
+
+    match format {
+        TokensFormat::Human => {
+            for token in &tokens {
+                printer.print_token(&mut output, token, 0);
+            }
+
+            for trivia in &eof_trivia {
+                printer.print_trivia(&mut output, trivia, 0);
+            }
+        }
+        TokensFormat::Json => {
+            let mut json = json!({ "tokens": printer.tokens_to_json(&tokens) });
+
+            if with_trivia {
+                json["eof_trivia"] = eof_trivia.iter().map(trivia_to_json).collect();
+            }
+
+            writeln!(output, "{}", serde_json::to_string_pretty(&json).unwrap()).unwrap();
+        }
+    }
+
+    let errors = errors
+        .into_iter()
+        .map(|err| ParseError::from(LexerErrorWrapper(source_id, err)).create_report())
+        .collect();
+    let warnings = warnings
+        .into_iter()
+        .map(|warning| create_lexer_warning_report(LexerWarningWrapper(source_id, warning)))
+        .collect();
+
+    RenderedTokens { output, errors, warnings }
+}
+
+/// Print the given [Report]s and exit with a failure.
+pub(crate) fn report_and_exit(reports: Vec<Report>, workspace: &Workspace) -> ! {
+    for report in reports {
+        eprintln!("{}", ReportWriter::new(report, workspace.source_map()));
+    }
+
+    exit(1);
+}
+
+/// Get the value of a token as a string, if the token has a value. For
+/// delimiters and token trees, the value is the opening delimiter.
+fn token_value(kind: &TokenKind) -> Option<String> {
+    match kind {
+        TokenKind::IntLit(_) | TokenKind::FloatLit(_) | TokenKind::Keyword(_) => {
+            Some(kind.to_string())
+        }
+        TokenKind::CharLit(ch) | TokenKind::Unexpected(ch) => Some(ch.to_string()),
+        TokenKind::StrLit(str) | TokenKind::RawStrLit(str) | TokenKind::MultiLineStrLit(str) => {
+            Some(str.to_string())
+        }
+        TokenKind::ByteStrLit(str) => Some(str.to_string()),
+        TokenKind::Ident(ident) => Some(ident.to_string()),
+        TokenKind::Tree(delimiter, _) => Some(delimiter.left().to_string()),
+        TokenKind::Delimiter(delimiter, left) => {
+            Some(if *left { delimiter.left() } else { delimiter.right() }.to_string())
+        }
+        TokenKind::InterpolatedStrLit(_) | TokenKind::Err => None,
+        _ => Some(kind.to_string()),
+    }
+}
+
+/// Convert a [Trivia] into JSON.
+fn trivia_to_json(trivia: &Trivia) -> Value {
+    json!({
+        "kind": format!("{:?}", trivia.kind),
+        "span": { "start": trivia.span.start(), "end": trivia.span.end() },
+    })
+}
+
+/// Utility for printing a token stream, expanding the contents of token trees.
+struct TokenPrinter<'t> {
+    trees: &'t [Vec<Token>],
+    /// The trivia of the tokens, this is empty unless trivia should be
+    /// printed.
+    trivia: &'t TriviaMap,
+}
+
+impl<'t> TokenPrinter<'t> {
+    /// Get the children of a token, if the token is a token tree or an
+    /// interpolated string literal.
+    fn children(&self, token: &Token) -> Option<&'t [Token]> {
+        match token.kind {
+            TokenKind::Tree(_, index) | TokenKind::InterpolatedStrLit(index) => {
+                Some(&self.trees[index])
+            }
+            _ => None,
+        }
+    }
+
+    /// Print a token as a line in the human readable format, the children of
+    /// the token are printed on the following lines with a deeper indentation.
+    fn print_token(&self, out: &mut String, token: &Token, depth: usize) {
+        let trivia = self.trivia.get(token);
+
+        for item in trivia.iter().flat_map(|trivia| &trivia.leading) {
+            self.print_trivia(out, item, depth);
+        }
+
+        let span = format!("{}..{}", token.span.start(), token.span.end());
+        let value = match token.kind {
+            // Escape the contents of string literals so that each token is on one line
+            TokenKind::StrLit(_)
+            | TokenKind::RawStrLit(_)
+            | TokenKind::MultiLineStrLit(_)
+            | TokenKind::CharLit(_) => token_value(&token.kind).map(|value| format!("{value:?}")),
+            kind => token_value(&kind),
+        };
+
+        let name = token.kind.name();
+        match value {
+            Some(value) => {
+                writeln!(out, "{span:<12}{:indent$}{name} {value}", "", indent = depth * 2)
+            }
+            None => writeln!(out, "{span:<12}{:indent$}{name}", "", indent = depth * 2),
+        }
+        .unwrap();
+
+        if let Some(children) = self.children(token) {
+            for child in children {
+                self.print_token(out, child, depth + 1);
+            }
+        }
+
+        for item in trivia.iter().flat_map(|trivia| trivia.dangling.iter()) {
+            self.print_trivia(out, item, depth + 1);
+        }
+
+        for item in trivia.iter().flat_map(|trivia| &trivia.trailing) {
+            self.print_trivia(out, item, depth);
+        }
+    }
+
+    /// Print a [Trivia] as a line in the human readable format.
+    fn print_trivia(&self, out: &mut String, trivia: &Trivia, depth: usize) {
+        let span = format!("{}..{}", trivia.span.start(), trivia.span.end());
+        writeln!(out, "{span:<12}{:indent$}{:?}", "", trivia.kind, indent = depth * 2).unwrap();
+    }
+
+    /// Convert a token stream into JSON.
+    fn tokens_to_json(&self, tokens: &[Token]) -> Value {
+        tokens.iter().map(|token| self.token_to_json(token)).collect()
+    }
+
+    /// Convert a token into JSON, the children of the token are included
+    /// within the `children` field.
+    fn token_to_json(&self, token: &Token) -> Value {
+        let mut value = json!({
+            "kind": token.kind.name(),
+            "span": { "start": token.span.start(), "end": token.span.end() },
+        });
+
+        if let Some(text) = token_value(&token.kind) {
+            value["value"] = Value::String(text);
+        }
+
+        if let Some(children) = self.children(token) {
+            value["children"] = self.tokens_to_json(children);
+        }
+
+        if let Some(trivia) = self.trivia.get(token) {
+            value["trivia"] = json!({
+                "leading": trivia.leading.iter().map(trivia_to_json).collect::<Value>(),
+                "trailing": trivia.trailing.iter().map(trivia_to_json).collect::<Value>(),
+                "dangling": trivia.dangling.iter().map(trivia_to_json).collect::<Value>(),
+            });
+        }
+
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small source with nested token trees and some trivia.
+    const SOURCE: &str = "f := (a, [b]); // c\n";
+
+    fn render(format: TokensFormat, with_trivia: bool) -> String {
+        let mut workspace = Workspace::new();
+        let module = Module::new("tokens.hash".into());
+        let module_id = workspace.add_module(SOURCE.to_string(), module, ModuleKind::Normal);
+
+        let rendered = render_tokens(SOURCE, SourceId::Module(module_id), format, with_trivia);
+        assert!(rendered.errors.is_empty() && rendered.warnings.is_empty());
+        rendered.output
+    }
+
+    #[test]
+    fn print_human_tokens() {
+        assert_eq!(
+            render(TokensFormat::Human, false),
+            "\
+0..1        Ident f
+2..3        Colon :
+3..4        Eq =
+5..13       Tree (
+6..7          Ident a
+7..8          Comma ,
+9..12         Tree [
+10..11          Ident b
+13..14      Semi ;
+"
+        );
+    }
+
+    #[test]
+    fn print_human_tokens_with_trivia() {
+        assert_eq!(
+            render(TokensFormat::Human, true),
+            "\
+0..1        Ident f
+1..2        Whitespace
+2..3        Colon :
+3..4        Eq =
+4..5        Whitespace
+5..13       Tree (
+6..7          Ident a
+7..8          Comma ,
+8..9          Whitespace
+9..12         Tree [
+10..11          Ident b
+13..14      Semi ;
+14..15      Whitespace
+15..19      LineComment
+19..20      Whitespace
+"
+        );
+    }
+
+    #[test]
+    fn print_json_tokens() {
+        let output: Value = serde_json::from_str(&render(TokensFormat::Json, false)).unwrap();
+
+        let token = |kind: &str, start: usize, end: usize, value: &str| json!({ "kind": kind, "span": { "start": start, "end": end }, "value": value });
+
+        let mut inner = token("Tree", 9, 12, "[");
+        inner["children"] = json!([token("Ident", 10, 11, "b")]);
+
+        let mut outer = token("Tree", 5, 13, "(");
+        outer["children"] = json!([token("Ident", 6, 7, "a"), token("Comma", 7, 8, ","), inner]);
+
+        assert_eq!(
+            output,
+            json!({
+                "tokens": [
+                    token("Ident", 0, 1, "f"),
+                    token("Colon", 2, 3, ":"),
+                    token("Eq", 3, 4, "="),
+                    outer,
+                    token("Semi", 13, 14, ";"),
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn print_json_tokens_with_trivia() {
+        let output: Value = serde_json::from_str(&render(TokensFormat::Json, true)).unwrap();
+
+        let trivia = |kind: &str, start: usize, end: usize| json!({ "kind": kind, "span": { "start": start, "end": end } });
+
+        assert_eq!(output["eof_trivia"], json!([trivia("Whitespace", 19, 20)]));
+
+        // The trivia of the tokens within trees is included too
+        let comma = &output["tokens"][3]["children"][1];
+        assert_eq!(
+            comma["trivia"],
+            json!({ "leading": [], "trailing": [trivia("Whitespace", 8, 9)], "dangling": [] })
+        );
+
+        let semi = &output["tokens"][4];
+        assert_eq!(
+            semi["trivia"]["trailing"],
+            json!([trivia("Whitespace", 14, 15), trivia("LineComment", 15, 19)])
+        );
+    }
+}
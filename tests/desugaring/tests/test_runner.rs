@@ -0,0 +1,144 @@
+#![cfg(test)]
+
+use std::{fs, path::Path};
+
+use hash_ast::{
+    ast,
+    ast::OwnsAstNode,
+    fold::{fold_in_place, AstFolder},
+    json::{from_json, to_json},
+    printer::{AstPrinter, PrinterConfig},
+};
+use hash_ast_desugaring::AstDesugarer;
+use hash_parser::HashParser;
+use hash_pipeline::{
+    fs::read_in_path,
+    settings::CompilerSettings,
+    sources::{Module, Workspace},
+    traits::{Desugar, Parser},
+};
+use hash_source::{ModuleId, ModuleKind, SourceId};
+use hash_utils::testing::{compare_output, TestingInput};
+use hash_utils_testing_macros::generate_tests;
+
+/// Create the thread pool that the stages of the pipeline run on.
+fn make_pool() -> rayon::ThreadPool {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(2)
+        .thread_name(|id| format!("desugar-worker-{}", id))
+        .build()
+        .unwrap()
+}
+
+/// Parse the file at the given path into a new [Workspace], returning the
+/// workspace and the id of the module.
+fn parse_module(content_path: &Path, pool: &rayon::ThreadPool) -> (Workspace, ModuleId) {
+    let mut workspace = Workspace::new();
+    let contents = read_in_path(content_path).unwrap();
+    let module = Module::new(content_path.to_path_buf());
+    let module_id = workspace.add_module(contents, module, ModuleKind::Normal);
+
+    let source_id = SourceId::Module(module_id);
+    let parsed =
+        HashParser::new().parse(source_id, &mut workspace, &CompilerSettings::default(), pool);
+    assert!(parsed.is_ok(), "parsing file: {:?} failed", content_path);
+
+    (workspace, module_id)
+}
+
+/// Parse and de-sugar the `case.hash` of the case, and check that the printed
+/// de-sugared module matches the recorded `case.out` entry within the case.
+fn handle_test(input: TestingInput) {
+    let pool = make_pool();
+    let (mut workspace, module_id) = parse_module(&input.path.join("case.hash"), &pool);
+    let source_id = SourceId::Module(module_id);
+
+    let mut desugarer = AstDesugarer;
+    let mut state = desugarer.make_state().unwrap();
+    desugarer.desugar(source_id, &mut workspace, &mut state, &pool).unwrap();
+
+    // The module is printed without its source, since the de-sugared nodes
+    // re-use the spans of the nodes that they replace
+    let node = workspace.node_map().get_module(module_id).node_ref();
+    let output = AstPrinter::new(PrinterConfig::default()).print_module(node);
+
+    compare_output(&input, "case.out", &output);
+}
+
+// "case.hash" is the test pattern.
+generate_tests!("./cases/", r"^case\.hash$", "self", handle_test);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A folder that keeps all of the nodes as they are.
+    struct IdentityFolder;
+
+    impl AstFolder for IdentityFolder {}
+
+    #[test]
+    fn fold_keeps_untouched_nodes() {
+        let pool = make_pool();
+
+        // Each of the modules that the parser accepts is folded, the nodes must
+        // keep their contents, spans and ids. The cases that import modules from
+        // a search path or a package are skipped, since they need more settings.
+        for entry in fs::read_dir("../parser/tests/cases/should_pass").unwrap() {
+            let path = entry.unwrap().path();
+            if path.join("include").is_dir() || path.join("hash.toml").is_file() {
+                continue;
+            }
+
+            let (mut workspace, module_id) = parse_module(&path.join("case.hash"), &pool);
+            let node = workspace.node_map_mut().get_module_mut(module_id).node_mut();
+
+            let before = to_json(node);
+            fold_in_place(node, |module| IdentityFolder.fold_module(module));
+            pretty_assertions::assert_eq!(before, to_json(node));
+        }
+    }
+
+    #[test]
+    fn desugar_module_loaded_from_json() {
+        let pool = make_pool();
+
+        // A module that is loaded from JSON can be fed back into the pipeline,
+        // and it must de-sugar into the same module as the parsed module
+        for entry in fs::read_dir("./tests/cases").unwrap() {
+            let path = entry.unwrap().path();
+            let (workspace, module_id) = parse_module(&path.join("case.hash"), &pool);
+            let json = to_json(workspace.node_map().get_module(module_id).node());
+            let node = from_json::<ast::Module>(&json.to_string()).unwrap();
+
+            let mut workspace = Workspace::new();
+            let contents = read_in_path(&path.join("case.hash")).unwrap();
+            let module = Module::with_node(path.join("case.hash"), node);
+            let module_id = workspace.add_module(contents, module, ModuleKind::Normal);
+
+            let mut desugarer = AstDesugarer;
+            let mut state = desugarer.make_state().unwrap();
+            desugarer
+                .desugar(SourceId::Module(module_id), &mut workspace, &mut state, &pool)
+                .unwrap();
+
+            let node = workspace.node_map().get_module(module_id).node_ref();
+            let output = AstPrinter::new(PrinterConfig::default()).print_module(node);
+            let expected = fs::read_to_string(path.join("case.out")).unwrap();
+            pretty_assertions::assert_eq!(expected, output, "de-sugaring {:?} changed", path);
+        }
+    }
+
+    #[test]
+    fn reject_deeply_nested_json() {
+        let pool = make_pool();
+        let path = std::env::temp_dir().join("hash-deeply-nested.hash");
+        fs::write(&path, format!("x := {}1{};", "{ ".repeat(50), " }".repeat(50))).unwrap();
+
+        // The JSON of the module is nested beyond the recursion limit
+        let (workspace, module_id) = parse_module(&path, &pool);
+        let json = to_json(workspace.node_map().get_module(module_id).node());
+        let err = from_json::<ast::Module>(&json.to_string()).unwrap_err();
+        assert!(err.to_string().contains("recursion limit exceeded"), "{}", err);
+    }
+}
@@ -0,0 +1,392 @@
+//! Grammar aware generation of random inputs for the parser. The
+//! [AstGenerator] builds random trees out of the [hash_ast::ast] types, which
+//! are then printed to source, whilst the [TokenTreeGenerator] builds random
+//! streams of tokens with balanced delimiters that are not required to form a
+//! valid program.
+
+use hash_ast::ast::{
+    AccessExpr, AccessKind, AssignExpr, AssignOpExpr, AstNode, AstNodes, BinOp, BinaryExpr,
+    BindingPat, Block, BlockExpr, BodyBlock, BoolLit, BoolLitPat, BreakStatement, CharLit,
+    ConstructorCallArg, ConstructorCallArgs, ConstructorCallExpr, ConstructorPat,
+    ContinueStatement, Declaration, Expr, ExprKind, FnDef, IfBlock, IfClause, IgnorePat, IndexExpr,
+    IntLit, IntLitPat, ListLit, Lit, LitExpr, LitPat, LoopBlock, MatchBlock, MatchCase,
+    MatchOrigin, Module, Mutability, Name, Param, ParamOrigin, Pat, PropagateExpr, RangeEnd,
+    RangeExpr, ReturnStatement, StrLit, TupleLit, TupleLitEntry, TuplePat, TuplePatEntry, UnOp,
+    UnaryExpr, VariableExpr, WhileLoopBlock,
+};
+use hash_source::{constant::IntConstantValue, location::Span};
+use num_bigint::BigInt;
+
+use crate::rng::Rng;
+
+/// The names that are used for generated bindings, none of which are keywords.
+const NAMES: &[&str] = &["a", "b", "c", "x", "y", "foo", "bar", "items", "value", "count"];
+
+/// The words that generated string literals are made of.
+const WORDS: &[&str] = &["hello", "world", "hash", "lang", "fuzz"];
+
+/// All of the binary operators that are generated, `as` is not included since
+/// the right-hand side of a cast is a type.
+const BIN_OPS: &[BinOp] = &[
+    BinOp::EqEq,
+    BinOp::NotEq,
+    BinOp::BitOr,
+    BinOp::Or,
+    BinOp::BitAnd,
+    BinOp::And,
+    BinOp::BitXor,
+    BinOp::Exp,
+    BinOp::Gt,
+    BinOp::GtEq,
+    BinOp::Lt,
+    BinOp::LtEq,
+    BinOp::Shr,
+    BinOp::Shl,
+    BinOp::Add,
+    BinOp::Sub,
+    BinOp::Mul,
+    BinOp::Div,
+    BinOp::Mod,
+    BinOp::Merge,
+    BinOp::Pipe,
+];
+
+/// Create a node with a dummy span, the spans of generated trees are never
+/// compared with the spans of parsed trees.
+fn node<T>(body: T) -> AstNode<T> {
+    AstNode::new(body, Span::new(0, 0))
+}
+
+fn expr(kind: ExprKind) -> AstNode<Expr> {
+    node(Expr::new(kind))
+}
+
+/// Generates random [Module]s that only contain syntactically valid
+/// constructs. The depth of the generated trees is bounded so that the
+/// generated sources stay reasonably small.
+pub struct AstGenerator<'r> {
+    rng: &'r mut Rng,
+    max_depth: usize,
+}
+
+impl<'r> AstGenerator<'r> {
+    pub fn new(rng: &'r mut Rng, max_depth: usize) -> Self {
+        Self { rng, max_depth }
+    }
+
+    /// Generate a module made up of a number of declarations.
+    pub fn module(&mut self) -> AstNode<Module> {
+        let contents = (0..self.rng.range(1, 4))
+            .map(|_| {
+                let value = self.expr(self.max_depth);
+                self.declaration(value)
+            })
+            .collect();
+
+        node(Module { contents: AstNodes::new(contents, None) })
+    }
+
+    fn name(&mut self) -> AstNode<Name> {
+        node(Name { ident: (*self.rng.pick(NAMES)).into() })
+    }
+
+    fn binding_pat(&mut self, mutability: Option<Mutability>) -> AstNode<Pat> {
+        node(Pat::Binding(BindingPat {
+            name: self.name(),
+            visibility: None,
+            mutability: mutability.map(node),
+        }))
+    }
+
+    fn declaration(&mut self, value: AstNode<Expr>) -> AstNode<Expr> {
+        let mutability = self.rng.one_in(4).then_some(Mutability::Mutable);
+
+        expr(ExprKind::Declaration(Declaration {
+            attributes: AstNodes::empty(),
+            pat: self.binding_pat(mutability),
+            ty: None,
+            value: Some(value),
+        }))
+    }
+
+    fn int_constant(&mut self) -> IntConstantValue {
+        IntConstantValue { value: BigInt::from(self.rng.below(1000)), suffix: None }
+    }
+
+    fn lit(&mut self) -> AstNode<Expr> {
+        let lit = match self.rng.below(4) {
+            0 => Lit::Int(IntLit(self.int_constant().into())),
+            1 => Lit::Bool(BoolLit(self.rng.one_in(2))),
+            2 => Lit::Char(CharLit((b'a' + self.rng.below(26) as u8) as char)),
+            _ => {
+                let words: Vec<_> =
+                    (0..self.rng.range(1, 3)).map(|_| *self.rng.pick(WORDS)).collect();
+                Lit::Str(StrLit(words.join(" ").into()))
+            }
+        };
+
+        expr(ExprKind::LitExpr(LitExpr(node(lit))))
+    }
+
+    fn variable(&mut self) -> AstNode<Expr> {
+        expr(ExprKind::Variable(VariableExpr { name: self.name() }))
+    }
+
+    fn atom(&mut self) -> AstNode<Expr> {
+        if self.rng.one_in(2) {
+            self.lit()
+        } else {
+            self.variable()
+        }
+    }
+
+    fn exprs(&mut self, depth: usize, lo: usize, hi: usize) -> Vec<AstNode<Expr>> {
+        (0..self.rng.range(lo, hi)).map(|_| self.expr(depth)).collect()
+    }
+
+    /// Generate an expression that is at most `depth` levels deep.
+    pub fn expr(&mut self, depth: usize) -> AstNode<Expr> {
+        if depth == 0 {
+            return self.atom();
+        }
+
+        let depth = depth - 1;
+
+        match self.rng.below(14) {
+            0 | 1 => self.atom(),
+            2 | 3 => expr(ExprKind::BinaryExpr(BinaryExpr {
+                lhs: self.expr(depth),
+                rhs: self.expr(depth),
+                operator: node(self.rng.pick(BIN_OPS).clone()),
+            })),
+            4 => expr(ExprKind::UnaryExpr(UnaryExpr {
+                expr: self.expr(depth),
+                operator: node(match self.rng.below(3) {
+                    0 => UnOp::Neg,
+                    1 => UnOp::Not,
+                    _ => UnOp::BitNot,
+                }),
+            })),
+            5 => {
+                // Named arguments can only be followed by other named arguments
+                let named_from = self.rng.range(0, 2);
+                let entries = (0..self.rng.range(0, 3))
+                    .map(|index| {
+                        let name = (index >= named_from).then(|| self.name());
+                        node(ConstructorCallArg { name, value: self.expr(depth) })
+                    })
+                    .collect();
+
+                expr(ExprKind::ConstructorCall(ConstructorCallExpr {
+                    subject: self.expr(depth),
+                    args: node(ConstructorCallArgs { entries: AstNodes::new(entries, None) }),
+                }))
+            }
+            6 => expr(ExprKind::Access(AccessExpr {
+                subject: self.expr(depth),
+                property: self.name(),
+                kind: if self.rng.one_in(2) { AccessKind::Property } else { AccessKind::Namespace },
+            })),
+            7 => expr(ExprKind::Index(IndexExpr {
+                subject: self.expr(depth),
+                index_expr: self.expr(depth),
+            })),
+            8 => expr(ExprKind::Propagate(PropagateExpr { subject: self.expr(depth) })),
+            9 => {
+                let elements = self.exprs(depth, 0, 3);
+                let lit = if self.rng.one_in(2) {
+                    Lit::List(ListLit { elements: AstNodes::new(elements, None) })
+                } else {
+                    let entries = elements
+                        .into_iter()
+                        .map(|value| node(TupleLitEntry { name: None, ty: None, value }))
+                        .collect();
+
+                    Lit::Tuple(TupleLit { elements: AstNodes::new(entries, None) })
+                };
+
+                expr(ExprKind::LitExpr(LitExpr(node(lit))))
+            }
+            10 => expr(ExprKind::Range(RangeExpr {
+                lo: self.expr(depth),
+                hi: self.expr(depth),
+                end: if self.rng.one_in(2) { RangeEnd::Excluded } else { RangeEnd::Included },
+            })),
+            11 => self.fn_def(depth),
+            _ => expr(ExprKind::Block(BlockExpr(self.block(depth)))),
+        }
+    }
+
+    fn fn_def(&mut self, depth: usize) -> AstNode<Expr> {
+        let params = (0..self.rng.range(0, 3))
+            .map(|_| {
+                node(Param { name: self.name(), ty: None, default: None, origin: ParamOrigin::Fn })
+            })
+            .collect();
+
+        let fn_body = if self.rng.one_in(2) {
+            expr(ExprKind::Block(BlockExpr(self.body_block(depth))))
+        } else {
+            self.expr(depth)
+        };
+
+        expr(ExprKind::FnDef(FnDef {
+            params: AstNodes::new(params, None),
+            return_ty: None,
+            fn_body,
+            captures: Default::default(),
+        }))
+    }
+
+    fn body_block(&mut self, depth: usize) -> AstNode<Block> {
+        let statements = (0..self.rng.range(0, 3)).map(|_| self.statement(depth)).collect();
+        let expr = self.rng.one_in(2).then(|| self.expr(depth));
+
+        node(Block::Body(BodyBlock { statements: AstNodes::new(statements, None), expr }))
+    }
+
+    fn block(&mut self, depth: usize) -> AstNode<Block> {
+        match self.rng.below(5) {
+            0 => self.body_block(depth),
+            1 => node(Block::Loop(LoopBlock(self.body_block(depth)))),
+            2 => node(Block::While(WhileLoopBlock {
+                condition: self.expr(depth),
+                body: self.body_block(depth),
+            })),
+            3 => {
+                let clauses = (0..self.rng.range(1, 3))
+                    .map(|_| {
+                        node(IfClause { condition: self.expr(depth), body: self.body_block(depth) })
+                    })
+                    .collect();
+
+                node(Block::If(IfBlock {
+                    clauses: AstNodes::new(clauses, None),
+                    otherwise: self.rng.one_in(2).then(|| self.body_block(depth)),
+                }))
+            }
+            _ => {
+                let cases = (0..self.rng.range(1, 3))
+                    .map(|_| node(MatchCase { pat: self.pat(depth), expr: self.expr(depth) }))
+                    .collect();
+
+                node(Block::Match(MatchBlock {
+                    subject: self.expr(depth),
+                    cases: AstNodes::new(cases, None),
+                    origin: MatchOrigin::Match,
+                }))
+            }
+        }
+    }
+
+    /// Generate a statement of a body block.
+    fn statement(&mut self, depth: usize) -> AstNode<Expr> {
+        match self.rng.below(7) {
+            0 | 1 => {
+                let value = self.expr(depth);
+                self.declaration(value)
+            }
+            2 => expr(ExprKind::Assign(AssignExpr { lhs: self.variable(), rhs: self.expr(depth) })),
+            3 => {
+                let operator = loop {
+                    let op = self.rng.pick(BIN_OPS).clone();
+
+                    if op.is_re_assignable() {
+                        break op;
+                    }
+                };
+
+                expr(ExprKind::AssignOp(AssignOpExpr {
+                    lhs: self.variable(),
+                    rhs: self.expr(depth),
+                    operator: node(operator),
+                }))
+            }
+            4 => match self.rng.below(3) {
+                0 => expr(ExprKind::Return(ReturnStatement(
+                    self.rng.one_in(2).then(|| self.expr(depth)),
+                ))),
+                1 => expr(ExprKind::Break(BreakStatement)),
+                _ => expr(ExprKind::Continue(ContinueStatement)),
+            },
+            _ => self.expr(depth),
+        }
+    }
+
+    fn pat(&mut self, depth: usize) -> AstNode<Pat> {
+        if depth == 0 {
+            return self.binding_pat(None);
+        }
+
+        let depth = depth - 1;
+
+        match self.rng.below(5) {
+            0 => self.binding_pat(None),
+            1 => node(Pat::Ignore(IgnorePat)),
+            2 => node(Pat::Lit(if self.rng.one_in(2) {
+                LitPat::Int(IntLitPat(self.int_constant().into()))
+            } else {
+                LitPat::Bool(BoolLitPat(self.rng.one_in(2)))
+            })),
+            3 => node(Pat::Tuple(TuplePat { fields: self.pat_fields(depth, 2) })),
+            _ => node(Pat::Constructor(ConstructorPat {
+                subject: self.binding_pat(None),
+                fields: self.pat_fields(depth, 0),
+            })),
+        }
+    }
+
+    fn pat_fields(&mut self, depth: usize, lo: usize) -> AstNodes<TuplePatEntry> {
+        let fields = (0..self.rng.range(lo, 3))
+            .map(|_| node(TuplePatEntry { name: None, pat: self.pat(depth) }))
+            .collect();
+
+        AstNodes::new(fields, None)
+    }
+}
+
+/// The tokens that random token trees are made of, this includes all of the
+/// operators and keywords along with a sample of literals and names.
+const TOKENS: &[&str] = &[
+    "a", "foo", "_", "0", "12u8", "3.5", "'c'", "\"str\"", "true", "+", "-", "*", "/", "%", "=",
+    "==", "!", "!=", "<", ">", "<=", ">=", "&", "&&", "|", "||", "^", "^^", "~", ":", "::", ";",
+    ",", ".", "..", "...", "?", "#", "=>", "->", "|>", "mut", "pub", "priv", "if", "else", "match",
+    "loop", "while", "for", "in", "return", "break", "continue", "struct", "enum", "trait", "impl",
+    "mod", "type", "as", "raw", "unsafe", "import",
+];
+
+/// Generates random streams of tokens, the only guarantee about the stream is
+/// that the delimiters are balanced.
+pub struct TokenTreeGenerator<'r> {
+    rng: &'r mut Rng,
+}
+
+impl<'r> TokenTreeGenerator<'r> {
+    pub fn new(rng: &'r mut Rng) -> Self {
+        Self { rng }
+    }
+
+    /// Generate a random token stream that is at most `depth` trees deep.
+    pub fn source(&mut self, depth: usize) -> String {
+        let mut source = String::new();
+        self.tree(depth, &mut source);
+        source
+    }
+
+    fn tree(&mut self, depth: usize, out: &mut String) {
+        for _ in 0..self.rng.range(1, 12) {
+            if depth > 0 && self.rng.one_in(5) {
+                let (open, close) = *self.rng.pick(&[("(", ")"), ("[", "]"), ("{", "}")]);
+
+                out.push_str(open);
+                self.tree(depth - 1, out);
+                out.push_str(close);
+            } else {
+                let token = self.rng.pick(TOKENS);
+                out.push_str(token);
+            }
+
+            out.push(' ');
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! Fuzzing harness for the parser. The harness runs two kinds of inputs
+//! through [HashParser]:
+//!
+//! - Random trees that are built out of the [hash_ast::ast] types, which are
+//!   printed to source, parsed again and then compared structurally with the
+//!   generated tree.
+//!
+//! - Random token trees, which are very unlikely to form a valid program. The
+//!   parser is only expected to report errors for them, and never to panic.
+//!
+//! The inputs are generated from a seed, which is fixed by default so that the
+//! harness is deterministic when it is run as a part of the test suite. The
+//! seed and the number of iterations can be changed with the
+//! `HASH_FUZZ_SEED` and `HASH_FUZZ_ITERATIONS` environment variables:
+//!
+//! ```text
+//! HASH_FUZZ_SEED=$RANDOM HASH_FUZZ_ITERATIONS=100000 cargo test -p hash-parser-tests --test fuzz
+//! ```
+#![cfg(test)]
+
+mod generator;
+mod rng;
+
+use std::{
+    env,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+};
+
+use generator::{AstGenerator, TokenTreeGenerator};
+use hash_ast::{
+    ast::{AstNodeRef, Module as AstModule, OwnsAstNode},
+    printer::{AstPrinter, PrinterConfig},
+    tree::AstTreeGenerator,
+    visitor::AstVisitor,
+};
+use hash_parser::HashParser;
+use hash_pipeline::{
+    settings::CompilerSettings,
+    sources::{Module, Workspace},
+    traits::Parser,
+};
+use hash_reporting::writer::ReportWriter;
+use hash_source::{ModuleKind, SourceId};
+use hash_utils::tree_writing::TreeWriter;
+use rayon::ThreadPool;
+use rng::Rng;
+
+/// The seed that is used when `HASH_FUZZ_SEED` isn't set.
+const DEFAULT_SEED: u64 = 0x4841_5348;
+
+/// The number of inputs that are generated when `HASH_FUZZ_ITERATIONS` isn't
+/// set.
+const DEFAULT_ITERATIONS: u64 = 256;
+
+/// The maximum depth of the generated trees.
+const MAX_DEPTH: usize = 4;
+
+/// Read a numeric setting of the harness from the environment.
+fn env_setting(name: &str, default: u64) -> u64 {
+    match env::var(name) {
+        Ok(value) => value.parse().unwrap_or_else(|_| panic!("`{}` must be a number", name)),
+        Err(_) => default,
+    }
+}
+
+/// Create the thread pool that the parser runs on, the parser requires at
+/// least two workers.
+fn make_pool() -> ThreadPool {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(2)
+        .thread_name(|id| format!("fuzz-worker-{}", id))
+        .build()
+        .unwrap()
+}
+
+/// Parse the given source as a module, returning the rendered tree of the
+/// parsed module or the rendered diagnostics if parsing failed.
+fn parse(pool: &ThreadPool, source: &str) -> Result<String, String> {
+    let mut workspace = Workspace::new();
+    let module = Module::new(PathBuf::from("fuzz.hash"));
+    let module_id = workspace.add_module(source.to_string(), module, ModuleKind::Normal);
+
+    let mut parser = HashParser::new();
+
+    let result = parser.parse(
+        SourceId::Module(module_id),
+        &mut workspace,
+        &CompilerSettings::default(),
+        pool,
+    );
+
+    match result {
+        Ok(_) => Ok(render_tree(workspace.node_map().get_module(module_id).node_ref())),
+        Err(reports) => Err(reports
+            .into_iter()
+            .map(|report| ReportWriter::new(report, workspace.source_map()).to_string())
+            .collect::<Vec<_>>()
+            .join("\n")),
+    }
+}
+
+fn render_tree(module: AstNodeRef<AstModule>) -> String {
+    let tree = AstTreeGenerator.visit_module(&(), module).unwrap();
+    TreeWriter::new(&tree).to_string()
+}
+
+/// Run the parser on the given input, reporting the input if the parser
+/// panics.
+fn parse_without_panicking(pool: &ThreadPool, seed: u64, source: &str) -> Result<String, String> {
+    panic::catch_unwind(AssertUnwindSafe(|| parse(pool, source))).unwrap_or_else(|_| {
+        panic!("parser panicked on input generated from seed {}:\n{}", seed, source)
+    })
+}
+
+#[test]
+fn generated_asts_round_trip() {
+    let seed = env_setting("HASH_FUZZ_SEED", DEFAULT_SEED);
+    let pool = make_pool();
+
+    for iteration in 0..env_setting("HASH_FUZZ_ITERATIONS", DEFAULT_ITERATIONS) {
+        let seed = seed.wrapping_add(iteration);
+        let mut rng = Rng::new(seed);
+
+        let module = AstGenerator::new(&mut rng, MAX_DEPTH).module();
+        let source = AstPrinter::new(PrinterConfig::default()).print_module(module.ast_ref());
+
+        match parse_without_panicking(&pool, seed, &source) {
+            Ok(parsed) => pretty_assertions::assert_eq!(
+                render_tree(module.ast_ref()),
+                parsed,
+                "input generated from seed {} was parsed into a different tree:\n{}",
+                seed,
+                source
+            ),
+            Err(diagnostics) => panic!(
+                "failed to parse input generated from seed {}:\n{}\n{}",
+                seed, source, diagnostics
+            ),
+        }
+    }
+}
+
+#[test]
+fn random_token_trees_do_not_panic() {
+    let seed = env_setting("HASH_FUZZ_SEED", DEFAULT_SEED);
+    let pool = make_pool();
+
+    for iteration in 0..env_setting("HASH_FUZZ_ITERATIONS", DEFAULT_ITERATIONS) {
+        let seed = seed.wrapping_add(iteration);
+        let mut rng = Rng::new(seed);
+
+        let source = TokenTreeGenerator::new(&mut rng).source(MAX_DEPTH);
+        let _ = parse_without_panicking(&pool, seed, &source);
+    }
+}
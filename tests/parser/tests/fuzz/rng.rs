@@ -0,0 +1,47 @@
+//! A small seeded pseudo-random number generator for the fuzzing harness. The
+//! generator is deterministic so that any failing input can be reproduced from
+//! the seed that is reported with it.
+
+/// A `SplitMix64` pseudo-random number generator.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Create a new [Rng] from the given seed.
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Generate the next random 64-bit value.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Generate a random value within `0..bound`.
+    pub fn below(&mut self, bound: usize) -> usize {
+        debug_assert!(bound > 0, "cannot generate a value below zero");
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// Generate a random value within `lo..=hi`.
+    pub fn range(&mut self, lo: usize, hi: usize) -> usize {
+        lo + self.below(hi - lo + 1)
+    }
+
+    /// Returns `true` with a probability of `1 / n`.
+    pub fn one_in(&mut self, n: usize) -> bool {
+        self.below(n) == 0
+    }
+
+    /// Pick a random element from the given slice.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+}
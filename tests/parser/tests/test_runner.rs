@@ -1,82 +1,191 @@
 #![cfg(test)]
 
-use std::fs;
-
+use std::path::Path;
+
+use hash_ast::{
+    ast::{self, OwnsAstNode},
+    index::AstIndex,
+    json::{from_json, to_json},
+    printer::{AstPrinter, PrinterConfig},
+    tree::AstTreeGenerator,
+    visitor::AstVisitor,
+};
+use hash_lexer::comment_spans;
 use hash_parser::HashParser;
 use hash_pipeline::{
     fs::read_in_path,
+    manifest::{Manifest, MANIFEST_FILE_NAME},
+    settings::CompilerSettings,
     sources::{Module, Workspace},
     traits::Parser,
 };
 use hash_reporting::{report::Report, writer::ReportWriter};
-use hash_source::{ModuleKind, SourceId};
-use hash_utils::testing::TestingInput;
+use hash_source::{ModuleId, ModuleKind, SourceId};
+use hash_utils::{
+    testing::{compare_output, normalise_reports, TestingInput},
+    tree_writing::TreeWriter,
+};
 use hash_utils_testing_macros::generate_tests;
-use lazy_static::lazy_static;
-use regex::Regex;
-
-/// Whether or not the UI tests should re-generate the output.
-const REGENERATE_OUTPUT: bool = false;
-
-/// This is the ANSI Regular expression matcher. This will match all the
-/// specified ANSI escape codes that are used by the [`hash_reporting`] crate.
-const ANSI_RE: &str = r"[\x1b\x9b]\[[()#;?]*(?:[0-9]{1,4}(?:;[0-9]{0,4})*)?[0-9A-ORZcf-nqry=><]";
-
-lazy_static! {
-    pub static ref ANSI_REGEX: Regex = Regex::new(ANSI_RE).unwrap();
-}
 
 /// This function is used to handle the case of verifying that a parser test was
 /// expected to fail. This function verifies that it does fail and that the
 /// generated [Report] (which is rendered) matches the recorded `case.stderr`
 /// entry within the case.
 fn handle_failure_case(
-    input: TestingInput,
-    result: Result<(), Vec<Report>>,
-    sources: Workspace,
-) -> std::io::Result<()> {
+    input: &TestingInput,
+    result: Result<Vec<Report>, Vec<Report>>,
+    sources: &Workspace,
+) {
     let content_path = input.path.join("case.hash");
 
     // Verify that the parser failed to parse this file
     assert!(result.is_err(), "parsing file: {:?} did not fail", content_path);
 
-    let diagnostics = result.unwrap_err();
+    handle_reports(input, result.unwrap_err(), sources)
+}
+
+/// Verify that the given rendered [Report]s match the recorded `case.stderr`
+/// entry within the case.
+fn handle_reports(input: &TestingInput, diagnostics: Vec<Report>, sources: &Workspace) {
     let contents = diagnostics
         .into_iter()
         .map(|report| format!("{}", ReportWriter::new(report, sources.source_map())))
         .collect::<Vec<_>>()
         .join("\n");
 
-    // Remove any ANSI escape codes generated from the reporting...
-    let report_contents = ANSI_REGEX.replace_all(contents.as_str(), "");
+    compare_output(input, "case.stderr", &normalise_reports(input, &contents));
+}
+
+/// Format the given module, returning the formatted source along with the
+/// rendered tree of the module.
+fn format_module(workspace: &Workspace, id: ModuleId) -> (String, String) {
+    let contents = workspace.source_map().contents_by_id(SourceId::Module(id));
+    let comments = comment_spans(contents, SourceId::Module(id));
+    let node = workspace.node_map().get_module(id).node_ref();
 
-    // Replace the directory by `$DIR`
-    let dir_regex = Regex::new(input.path.as_path().to_str().unwrap()).unwrap();
-    let report_contents = dir_regex.replace_all(report_contents.as_ref(), r"$$DIR").to_string();
+    let formatted = AstPrinter::new(PrinterConfig::default())
+        .with_source(contents, comments)
+        .print_module(node);
 
-    // We want to load the `.stderr` file and verify that the contents of the
-    // file match to the created report. If the `.stderr` file does not exist
-    // then we create it and write the generated report to that file
-    let stderr_path = input.path.join("case.stderr");
+    let tree = AstTreeGenerator.visit_module(&(), node).unwrap();
+    (formatted, TreeWriter::new(&tree).to_string())
+}
+
+/// This function is used to verify that formatting a case that parses
+/// successfully doesn't change the meaning of the case, and that formatting
+/// the case again doesn't change the formatted source.
+fn handle_format_case(
+    content_path: &Path,
+    workspace: &Workspace,
+    target_id: ModuleId,
+    settings: &CompilerSettings,
+    pool: &rayon::ThreadPool,
+) {
+    let (formatted, tree) = format_module(workspace, target_id);
+
+    let mut formatted_workspace = Workspace::new();
+    let target = Module::new(content_path.to_path_buf());
+    let formatted_id =
+        formatted_workspace.add_module(formatted.clone(), target, ModuleKind::Normal);
+
+    let result = HashParser::new().parse(
+        SourceId::Module(formatted_id),
+        &mut formatted_workspace,
+        settings,
+        pool,
+    );
+    assert!(result.is_ok(), "parsing formatted file failed: {:?}\n{}", content_path, formatted);
+
+    let (reformatted, formatted_tree) = format_module(&formatted_workspace, formatted_id);
+    pretty_assertions::assert_eq!(tree, formatted_tree, "formatting changed the tree");
+    pretty_assertions::assert_eq!(formatted, reformatted, "formatting is not idempotent");
+
+    let original = workspace.source_map().contents_by_id(SourceId::Module(target_id));
+    assert_eq!(
+        comment_spans(original, SourceId::Module(target_id)).len(),
+        comment_spans(&formatted, SourceId::Module(formatted_id)).len(),
+        "formatting dropped comments:\n{}",
+        formatted
+    );
+}
 
-    // If we specify to re-generate the output, then we will always write the
-    // content of the report into the specified file
-    if REGENERATE_OUTPUT || !stderr_path.exists() {
-        fs::write(&stderr_path, &report_contents)?;
+/// Remove all of the node ids from the given JSON, the ids are regenerated
+/// when a tree is loaded from JSON.
+fn strip_ids(json: &mut serde_json::Value) {
+    match json {
+        serde_json::Value::Object(fields) => {
+            fields.remove("id");
+            fields.values_mut().for_each(strip_ids);
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(strip_ids),
+        _ => {}
     }
+}
 
-    if stderr_path.exists() {
-        let err_contents = fs::read_to_string(stderr_path).unwrap();
+/// This function is used to verify that a module which parses successfully
+/// can be converted into JSON and loaded back into the same tree.
+fn handle_json_case(content_path: &Path, workspace: &Workspace, target_id: ModuleId) {
+    let node = workspace.node_map().get_module(target_id).node();
+    let json = to_json(node);
+
+    let loaded = from_json::<ast::Module>(&json.to_string())
+        .unwrap_or_else(|err| panic!("loading JSON of {:?} failed: {}", content_path, err));
+
+    let tree = AstTreeGenerator.visit_module(&(), node.ast_ref()).unwrap();
+    let loaded_tree = AstTreeGenerator.visit_module(&(), loaded.ast_ref()).unwrap();
+    pretty_assertions::assert_eq!(
+        TreeWriter::new(&tree).to_string(),
+        TreeWriter::new(&loaded_tree).to_string(),
+        "loading JSON changed the tree"
+    );
+
+    let (mut json, mut loaded_json) = (json, to_json(&loaded));
+    strip_ids(&mut json);
+    strip_ids(&mut loaded_json);
+    assert!(json == loaded_json, "loading JSON of {:?} changed the JSON", content_path);
+}
 
-        pretty_assertions::assert_eq!(err_contents, report_contents);
-    } else {
-        panic!(
-            "Missing `.stderr` file for `{:?}`. Consider running with `REGENERATE_OUTPUT=true`",
+/// This function is used to verify that the [AstIndex] of a module which
+/// parses successfully is consistent, that the span of each node is within the
+/// span of its parent, and that each node can be found by its span.
+fn handle_index_case(content_path: &Path, workspace: &Workspace, target_id: ModuleId) {
+    let index = AstIndex::for_module(workspace.node_map().get_module(target_id).node_ref());
+    let mut queue = vec![index.root()];
+
+    while let Some(id) = queue.pop() {
+        let span = index.span_of(id).unwrap();
+
+        for &child in index.children_of(id) {
+            let child_span = index.span_of(child).unwrap();
+
+            assert_eq!(index.parent_of(child), Some(id));
+            assert!(
+                span.start() <= child_span.start() && child_span.end() <= span.end(),
+                "span of {} {:?} is not within the span of its parent {} {:?} in {:?}",
+                index.kind_of(child).unwrap(),
+                child_span,
+                index.kind_of(id).unwrap(),
+                span,
+                content_path
+            );
+
+            queue.push(child);
+        }
+
+        // The node that is found is either within this node, or it is another node
+        // with the same span, such as the name of a shorthand module pattern entry
+        let found = index.node_at_offset(span).unwrap();
+        assert!(
+            found == id
+                || index.ancestors(found).any(|ancestor| ancestor == id)
+                || index.span_of(found) == Some(span),
+            "looking up the span {:?} of {} found {} in {:?}",
+            span,
+            index.kind_of(id).unwrap(),
+            index.kind_of(found).unwrap(),
             content_path
         );
     }
-
-    Ok(())
 }
 
 /// Generic test handler in the event whether a case should pass or fail.
@@ -99,34 +208,34 @@ fn handle_test(input: TestingInput) {
         .build()
         .unwrap();
 
+    // If the case has an `include` directory, then it is used as a module search
+    // path, and if the case has a manifest, it is used to resolve dependencies
+    let include_path = input.path.join("include");
+    let search_paths = if include_path.is_dir() { vec![include_path] } else { vec![] };
+
+    let manifest_path = input.path.join(MANIFEST_FILE_NAME);
+    let manifest = manifest_path.is_file().then(|| Manifest::load(&manifest_path).unwrap());
+    let settings = CompilerSettings { search_paths, manifest, ..CompilerSettings::default() };
+
     // Now parse the module and store the result
-    let result = parser.parse(SourceId::Module(target_id), &mut workspace, &pool);
+    let result = parser.parse(SourceId::Module(target_id), &mut workspace, &settings, &pool);
 
     if should_fail {
-        handle_failure_case(input, result, workspace).unwrap();
+        handle_failure_case(&input, result, &workspace);
     } else {
         // Check whether the result fails or not, depending on if the file_path begins
         // with 'should_fail'...
-        assert!(result.is_ok(), "parsing file failed: {:?}", content_path);
-    }
-}
-// "case.hash" is the test pattern.
-generate_tests!("./cases/", r"^case\.hash$", "self", handle_test);
+        let warnings = result.unwrap_or_else(|_| panic!("parsing file failed: {:?}", content_path));
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        // Any warnings that are generated must match the recorded warnings
+        if !warnings.is_empty() {
+            handle_reports(&input, warnings, &workspace);
+        }
 
-    #[test]
-    #[allow(clippy::assertions_on_constants)]
-    fn ensure_regenerate_output_is_disabled() {
-        assert!(
-            !REGENERATE_OUTPUT,
-            "
-        Verify that the `REGENERATE_OUTPUT` module flag is not accidentally left
-        on making all of the test cases that observe compiler output
-        automatically overwrite old results with current ones.
-        "
-        );
+        handle_format_case(&content_path, &workspace, target_id, &settings, &pool);
+        handle_json_case(&content_path, &workspace, target_id);
+        handle_index_case(&content_path, &workspace, target_id);
     }
 }
+// "case.hash" is the test pattern.
+generate_tests!("./cases/", r"^case\.hash$", "self", handle_test);
@@ -0,0 +1,92 @@
+#![cfg(test)]
+
+use hash_ast_desugaring::AstDesugarer;
+use hash_ast_passes::HashSemanticAnalysis;
+use hash_parser::HashParser;
+use hash_pipeline::{
+    fs::read_in_path,
+    settings::{CompilerJobParams, CompilerSettings},
+    sources::{Module, Workspace},
+    traits::{Desugar, Parser, SemanticPass, Tc},
+    CompilerResult,
+};
+use hash_reporting::writer::ReportWriter;
+use hash_source::{ModuleKind, SourceId};
+use hash_typecheck::TcImpl;
+use hash_utils::testing::{compare_output, normalise_reports, TestingInput};
+use hash_utils_testing_macros::generate_tests;
+
+/// Run the stages of the pipeline up to and including typechecking on the
+/// given module, stopping at the first stage that fails. The prelude isn't
+/// loaded, so the cases can only use the core definitions of the typechecker.
+fn typecheck_module(workspace: &mut Workspace, source_id: SourceId) -> CompilerResult<()> {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(2)
+        .thread_name(|id| format!("tc-worker-{}", id))
+        .build()
+        .unwrap();
+
+    HashParser::new().parse(source_id, workspace, &CompilerSettings::default(), &pool)?;
+
+    let mut desugarer = AstDesugarer;
+    let mut state = desugarer.make_state()?;
+    desugarer.desugar(source_id, workspace, &mut state, &pool)?;
+
+    let mut analysis = HashSemanticAnalysis;
+    let mut state = analysis.make_state()?;
+    analysis.perform_pass(source_id, workspace, &mut state, &pool)?;
+
+    let mut checker = TcImpl;
+    let mut state = checker.make_state()?;
+
+    match source_id {
+        SourceId::Module(id) => {
+            checker.check_module(id, workspace, &mut state, &CompilerJobParams::default())
+        }
+        SourceId::Interactive(_) => unreachable!(),
+    }
+}
+
+/// Generic test handler in the event whether a case should pass or fail. If
+/// the case should fail, then the rendered reports must match the recorded
+/// `case.stderr` entry within the case.
+fn handle_test(input: TestingInput) {
+    let should_fail = input.snake_name.starts_with("should_fail");
+
+    let mut workspace = Workspace::new();
+    let content_path = input.path.join("case.hash");
+    let contents = read_in_path(content_path.as_path()).unwrap();
+    let module = Module::new(content_path.clone());
+    let target_id = workspace.add_module(contents, module, ModuleKind::Normal);
+
+    let result = typecheck_module(&mut workspace, SourceId::Module(target_id));
+
+    if !should_fail {
+        if let Err(reports) = result {
+            let contents = reports
+                .into_iter()
+                .map(|report| format!("{}", ReportWriter::new(report, workspace.source_map())))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            panic!("typechecking file failed: {:?}\n{}", content_path, contents);
+        }
+
+        return;
+    }
+
+    // Verify that the typechecker failed to check this file
+    assert!(result.is_err(), "typechecking file: {:?} did not fail", content_path);
+
+    let contents = result
+        .unwrap_err()
+        .into_iter()
+        .map(|report| format!("{}", ReportWriter::new(report, workspace.source_map())))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    compare_output(&input, "case.stderr", &normalise_reports(&input, &contents));
+}
+
+// "case.hash" is the test pattern.
+generate_tests!("./cases/", r"^case\.hash$", "self", handle_test);